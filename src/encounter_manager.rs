@@ -0,0 +1,269 @@
+// encounter_manager.rs
+//
+// This file is responsible for all data management operations related to saved encounters.
+// It handles reading from and writing to the "Encounters" directory.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use serde_json::Value;
+
+use super::monster_manager::{ self, get_base_path };
+use super::simulation::StaticSimulationState;
+
+/// Name given to the old single `active_simulation.json` when it is pulled into the library.
+const LEGACY_ENCOUNTER_NAME: &str = "Active Simulation";
+
+/// Returns the path to the "Encounters" directory, creating it if needed.
+fn get_encounters_path() -> io::Result<PathBuf> {
+    let mut path = get_base_path()?;
+    path.push("Encounters");
+    if !path.exists() {
+        fs::create_dir_all(&path)?;
+    }
+    Ok(path)
+}
+
+/// Turns an encounter name into something that is safe to use as a file name.
+fn encounter_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == ' ' || c == '-' { c } else { '_' })
+        .collect();
+    format!("{}.json", cleaned)
+}
+
+/// Returns the full path of the file backing the named encounter.
+fn encounter_path(name: &str) -> io::Result<PathBuf> {
+    let mut path = get_encounters_path()?;
+    path.push(encounter_file_name(name));
+    Ok(path)
+}
+
+/// Checks if any saved encounters exist.
+pub fn check_for_encounters() -> bool {
+    let Ok(path) = get_encounters_path() else {
+        return false;
+    };
+    fs::read_dir(path).is_ok_and(|mut entries| {
+        entries.any(|entry| entry.is_ok_and(|e| e.path().extension().is_some_and(|ext| ext == "json")))
+    })
+}
+
+/// Checks if an encounter is already stored under this name (or one that maps to the same file).
+pub fn encounter_exists(name: &str) -> bool {
+    encounter_path(name).is_ok_and(|path| path.exists())
+}
+
+/// Returns `base` if no encounter uses it yet, otherwise `base` followed by the first free counter.
+pub fn unique_encounter_name(base: &str) -> String {
+    let mut name = base.trim().to_string();
+    let mut counter = 1;
+    while encounter_exists(&name) {
+        counter += 1;
+        name = format!("{} {}", base.trim(), counter);
+    }
+    name
+}
+
+/// Saves an encounter that isn't in the library yet, refusing to replace one with the same name.
+pub fn save_new_encounter(encounter: &StaticSimulationState) -> io::Result<()> {
+    if encounter_exists(&encounter.name) {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, "An encounter with that name already exists"));
+    }
+    save_encounter(encounter)
+}
+
+/// Saves an encounter to its JSON file, replacing any encounter with the same name.
+pub fn save_encounter(encounter: &StaticSimulationState) -> io::Result<()> {
+    if encounter.name.trim().is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Encounter name is empty"));
+    }
+
+    let path = encounter_path(&encounter.name)?;
+    let json_data = serde_json::to_string_pretty(encounter)?;
+    let mut file = File::create(&path)?;
+    file.write_all(json_data.as_bytes())?;
+
+    println!("Saved encounter to file: {:?}", path);
+    Ok(())
+}
+
+/// Runs the monster schema migrations on the monster copies stored in an encounter.
+/// Ids are left as they were so `upgrade_format` can still link old copies to the library by name.
+fn migrate_monster_copies(value: &mut Value) {
+    for list in ["combatants", "killed_monsters"] {
+        let Some(combatants) = value.get_mut(list).and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for template in combatants.iter_mut().filter_map(|c| c.get_mut("monster_template")) {
+            let id = template.get("id").cloned();
+            monster_manager::migrate_monster_value(template);
+            if let Some(obj) = template.as_object_mut() {
+                match id {
+                    Some(id) => obj.insert("id".to_string(), id),
+                    None => obj.remove("id"),
+                };
+            }
+        }
+    }
+}
+
+/// Reads an encounter from a JSON file at the given path.
+fn read_encounter_file(path: &PathBuf) -> Option<StaticSimulationState> {
+    let mut file = File::open(path).ok()?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).ok()?;
+
+    let parsed = serde_json::from_str::<Value>(&contents).and_then(|mut value| {
+        migrate_monster_copies(&mut value);
+        serde_json::from_value::<StaticSimulationState>(value)
+    });
+    match parsed {
+        Ok(mut encounter) => {
+            encounter.upgrade_format();
+            Some(encounter)
+        }
+        Err(e) => {
+            eprintln!("Failed to parse encounter JSON for {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Reads a saved encounter by name.
+pub fn read_encounter(name: &str) -> Option<StaticSimulationState> {
+    let path = encounter_path(name).ok()?;
+    let mut encounter = read_encounter_file(&path)?;
+    encounter.in_library = true;
+    Some(encounter)
+}
+
+/// Reads all saved encounters, sorted by name.
+pub fn list_encounters() -> Vec<StaticSimulationState> {
+    let path = match get_encounters_path() {
+        Ok(p) => p,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut encounters = Vec::new();
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json")
+                && let Some(mut encounter) = read_encounter_file(&path)
+            {
+                encounter.in_library = true;
+                encounters.push(encounter);
+            }
+        }
+    }
+    encounters.sort_by(|a, b| a.name.cmp(&b.name));
+    encounters
+}
+
+/// Deletes a saved encounter's JSON file.
+pub fn delete_encounter(name: &str) -> io::Result<()> {
+    let path = encounter_path(name)?;
+    fs::remove_file(&path)?;
+    println!("Deleted encounter file: {:?}", path);
+    Ok(())
+}
+
+/// Renames a saved encounter, refusing to overwrite another encounter.
+pub fn rename_encounter(old_name: &str, new_name: &str) -> io::Result<()> {
+    let mut encounter = read_encounter(old_name)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Encounter not found"))?;
+
+    let same_file = encounter_file_name(old_name) == encounter_file_name(new_name);
+    if !same_file && encounter_exists(new_name) {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, "An encounter with that name already exists"));
+    }
+
+    // Write the new file first so a failed save never loses the encounter
+    encounter.name = new_name.trim().to_string();
+    save_encounter(&encounter)?;
+    if !same_file {
+        delete_encounter(old_name)?;
+    }
+    Ok(())
+}
+
+/// Copies a saved encounter under a new name.
+pub fn duplicate_encounter(name: &str, new_name: &str) -> io::Result<()> {
+    let mut encounter = read_encounter(name)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Encounter not found"))?;
+
+    encounter.name = new_name.trim().to_string();
+    save_new_encounter(&encounter)
+}
+
+/// Moves the old single `active_simulation.json` into the encounter library so it isn't lost.
+/// Run once at startup.
+pub fn import_legacy_simulation() {
+    let mut path = match get_base_path() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+    path.push("active_simulation.json");
+    if !path.exists() {
+        return;
+    }
+
+    let Some(mut encounter) = read_encounter_file(&path) else {
+        return;
+    };
+
+    // Never clobber an encounter the user already has under the same name
+    encounter.name = unique_encounter_name(LEGACY_ENCOUNTER_NAME);
+    encounter.started = true;
+
+    match save_new_encounter(&encounter) {
+        Ok(()) => {
+            if let Err(e) = fs::remove_file(&path) {
+                eprintln!("Failed to remove legacy simulation file: {}", e);
+            }
+        }
+        Err(e) => eprintln!("Failed to import legacy simulation: {}", e),
+    }
+}
+
+/// Returns the path of the autosave file used for crash recovery.
+fn autosave_path() -> io::Result<PathBuf> {
+    let mut path = get_base_path()?;
+    if !path.exists() {
+        fs::create_dir_all(&path)?;
+    }
+    path.push("autosave.json");
+    Ok(path)
+}
+
+/// Checks if an autosave was left behind by a simulation that never exited cleanly.
+pub fn check_for_autosave() -> bool {
+    autosave_path().map(|p| p.exists()).unwrap_or(false)
+}
+
+/// Writes the live simulation to the autosave file.
+pub fn write_autosave(encounter: &StaticSimulationState) -> io::Result<()> {
+    let path = autosave_path()?;
+    let json_data = serde_json::to_string_pretty(encounter)?;
+    let mut file = File::create(&path)?;
+    file.write_all(json_data.as_bytes())?;
+    Ok(())
+}
+
+/// Reads the autosaved simulation, if there is one.
+pub fn read_autosave() -> Option<StaticSimulationState> {
+    let path = autosave_path().ok()?;
+    read_encounter_file(&path)
+}
+
+/// Removes the autosave file once the simulation has been exited cleanly.
+pub fn clear_autosave() -> io::Result<()> {
+    let path = autosave_path()?;
+    if path.exists() {
+        fs::remove_file(&path)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,330 @@
+// export_interface.rs
+//
+// This file holds the dialogs used to get monsters out of the app, e.g. to share them.
+
+use std::rc::Rc;
+use gtk::{ prelude::*, Align, CheckButton, ListBox, Orientation, TextView };
+use libadwaita::Application as AdwApplication;
+use gtk::ApplicationWindow as AdwWindow;
+
+use super::monster_manager::{ self, Monster };
+use super::monster_bundle;
+use super::monster_csv;
+use super::statblock_export;
+use super::ui_factory::UiFactory;
+
+/// Builds the "Export" button of the monster list, with one entry per export format.
+pub fn create_export_menu_button(app: &AdwApplication, window: &AdwWindow) -> gtk::MenuButton {
+    let menu_box = UiFactory::create_box(Orientation::Vertical, 6, (6, 6, 6, 6));
+    let popover = gtk::Popover::builder().child(&menu_box).build();
+    let menu_button = gtk::MenuButton::builder().label("Export").popover(&popover).build();
+
+    let bundle_button = UiFactory::create_button("Monster bundle...", Align::Fill, None);
+    let csv_button = UiFactory::create_button("CSV spreadsheet...", Align::Fill, None);
+    menu_box.append(&bundle_button);
+    menu_box.append(&csv_button);
+
+    let app_clone = app.clone();
+    let window_clone = window.clone();
+    let popover_clone = popover.clone();
+    bundle_button.connect_clicked(move |_| {
+        popover_clone.popdown();
+        show_bundle_export_menu(&app_clone, &window_clone);
+    });
+
+    let app_clone = app.clone();
+    let window_clone = window.clone();
+    let popover_clone = popover.clone();
+    csv_button.connect_clicked(move |_| {
+        popover_clone.popdown();
+        export_csv(&app_clone, &window_clone);
+    });
+
+    menu_button
+}
+
+/// Writes the whole library to a monster CSV plus an attack CSV next to it.
+fn export_csv(app: &AdwApplication, parent_window: &AdwWindow) {
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    UiFactory::choose_file(
+        parent_window,
+        "Export Monsters to CSV",
+        gtk::FileChooserAction::Save,
+        &["*.csv"],
+        Some("monsters.csv"),
+        move |path| {
+            let monsters = monster_manager::read_all_monsters();
+            let message = match monster_csv::export_csv(&path, &monsters) {
+                Ok(attack_path) => format!(
+                    "Exported {} monster(s) to {}\nAttacks were written to {}",
+                    monsters.len(),
+                    path.display(),
+                    attack_path.display()
+                ),
+                Err(e) => format!("Failed to export monsters to CSV: {}", e),
+            };
+            show_export_result(&app_clone, &parent_window_clone, "CSV Export", &message);
+        }
+    );
+}
+
+/// Small modal reporting how an export went.
+fn show_export_result(app: &AdwApplication, parent_window: &AdwWindow, title: &str, message: &str) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title(title)
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(400)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let message_label = UiFactory::create_label(message, Align::Center, false, &[]);
+    message_label.set_wrap(true);
+    main_vbox.append(&message_label);
+
+    let close_button = UiFactory::create_button("Close", Align::End, None);
+    let window_clone = window.clone();
+    close_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+    main_vbox.append(&close_button);
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// Lets the user pick folders and monsters to write into a shareable bundle file.
+fn show_bundle_export_menu(app: &AdwApplication, parent_window: &AdwWindow) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title("Export Bundle")
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(500)
+        .default_height(600)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let title = UiFactory::create_label("Export a Monster Bundle", Align::Center, false, &["title-2"]);
+    main_vbox.append(&title);
+
+    let details_grid = UiFactory::create_grid(6, 12, Align::Fill);
+    let (title_label, title_entry) = UiFactory::create_label_entry_pair("Bundle Title:", "e.g., Campaign 2 Homebrew");
+    let (description_label, description_entry) = UiFactory::create_label_entry_pair("Description:", "optional");
+    title_entry.set_hexpand(true);
+    description_entry.set_hexpand(true);
+    details_grid.attach(&title_label, 0, 0, 1, 1);
+    details_grid.attach(&title_entry, 1, 0, 1, 1);
+    details_grid.attach(&description_label, 0, 1, 1, 1);
+    details_grid.attach(&description_entry, 1, 1, 1, 1);
+    main_vbox.append(&details_grid);
+
+    let hint = UiFactory::create_label(
+        "Checking a folder exports everything in it, including subfolders.",
+        Align::Start,
+        false,
+        &[]
+    );
+    main_vbox.append(&hint);
+
+    let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
+    let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
+    list_box.add_css_class("boxed-list");
+
+    let monsters = monster_manager::read_all_monsters();
+    let folders = monster_manager::list_folders();
+
+    let mut folder_checks: Vec<(CheckButton, String)> = Vec::new();
+    for folder in folders {
+        let count = monsters.iter().filter(|m| in_folder(m, &folder)).count();
+        let check = CheckButton::with_label(&format!("Folder: {} ({} monsters)", folder, count));
+        check.set_margin_start(6 + folder.matches('/').count() as i32 * 24);
+        list_box.append(&check);
+        folder_checks.push((check, folder));
+    }
+
+    let mut monster_checks: Vec<(CheckButton, Monster)> = Vec::new();
+    for monster in monsters {
+        let label = if monster.folder.is_empty() {
+            monster.name.clone()
+        } else {
+            format!("{} ({})", monster.name, monster.folder)
+        };
+        let check = CheckButton::with_label(&label);
+        check.set_margin_start(6);
+        list_box.append(&check);
+        monster_checks.push((check, monster));
+    }
+
+    scrolled_window.set_child(Some(&list_box));
+    main_vbox.append(&scrolled_window);
+
+    let status_label = UiFactory::create_label("", Align::Center, false, &[]);
+    status_label.set_wrap(true);
+    main_vbox.append(&status_label);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let select_all_button = UiFactory::create_button("Select All", Align::Center, None);
+    let export_button = UiFactory::create_button("Export...", Align::Center, Some("suggested-action"));
+    let cancel_button = UiFactory::create_button("Cancel", Align::Center, None);
+    button_box.append(&select_all_button);
+    button_box.append(&export_button);
+    button_box.append(&cancel_button);
+    main_vbox.append(&button_box);
+
+    let folder_checks = Rc::new(folder_checks);
+    let monster_checks = Rc::new(monster_checks);
+
+    let folder_checks_clone = Rc::clone(&folder_checks);
+    let monster_checks_clone = Rc::clone(&monster_checks);
+    select_all_button.connect_clicked(move |_| {
+        folder_checks_clone.iter().for_each(|(check, _)| check.set_active(true));
+        monster_checks_clone.iter().for_each(|(check, _)| check.set_active(true));
+    });
+
+    let window_clone = window.clone();
+    export_button.connect_clicked(move |_| {
+        let selected_folders: Vec<String> = folder_checks
+            .iter()
+            .filter(|(check, _)| check.is_active())
+            .map(|(_, folder)| folder.clone())
+            .collect();
+        let selected_monsters: Vec<Monster> = monster_checks
+            .iter()
+            .filter(|(check, monster)| check.is_active() || selected_folders.iter().any(|f| in_folder(monster, f)))
+            .map(|(_, monster)| monster.clone())
+            .collect();
+
+        if selected_monsters.is_empty() && selected_folders.is_empty() {
+            status_label.set_text("Select at least one folder or monster.");
+            return;
+        }
+
+        let bundle_title = title_entry.text().trim().to_string();
+        let description = description_entry.text().to_string();
+        let suggested_name = format!("{}.json", if bundle_title.is_empty() { "monster-bundle" } else { &bundle_title });
+
+        let status_label_clone = status_label.clone();
+        let export_window = window_clone.clone();
+        UiFactory::choose_file(
+            &window_clone,
+            "Save Monster Bundle",
+            gtk::FileChooserAction::Save,
+            &["*.json"],
+            Some(&suggested_name),
+            move |path| {
+                match monster_bundle::export_bundle(&path, &bundle_title, &description, &selected_monsters, &selected_folders) {
+                    Ok(()) => export_window.close(),
+                    Err(e) => status_label_clone.set_text(&format!("Failed to export bundle: {}", e)),
+                }
+            }
+        );
+    });
+
+    let window_clone = window.clone();
+    cancel_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// True if the monster is filed in the folder or one of its subfolders.
+fn in_folder(monster: &Monster, folder: &str) -> bool {
+    monster.folder == folder || monster.folder.starts_with(&format!("{}/", folder))
+}
+
+/// Shows a monster's rendered stat block with options to copy it or save it as Markdown or HTML.
+pub fn show_stat_block_menu(app: &AdwApplication, parent_window: &AdwWindow, monster: &Monster) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title(format!("{} Stat Block", monster.name))
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(600)
+        .default_height(600)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+
+    let markdown = statblock_export::render_markdown(monster);
+    let text_view = TextView::builder()
+        .editable(false)
+        .monospace(true)
+        .wrap_mode(gtk::WrapMode::WordChar)
+        .build();
+    text_view.buffer().set_text(&markdown);
+    let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
+    scrolled_window.set_has_frame(true);
+    scrolled_window.set_child(Some(&text_view));
+    main_vbox.append(&scrolled_window);
+
+    let status_label = UiFactory::create_label("", Align::Center, false, &[]);
+    main_vbox.append(&status_label);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let copy_button = UiFactory::create_button("Copy Markdown", Align::Center, None);
+    let markdown_button = UiFactory::create_button("Save Markdown...", Align::Center, None);
+    let html_button = UiFactory::create_button("Save HTML...", Align::Center, Some("suggested-action"));
+    let close_button = UiFactory::create_button("Close", Align::Center, None);
+    button_box.append(&copy_button);
+    button_box.append(&markdown_button);
+    button_box.append(&html_button);
+    button_box.append(&close_button);
+    main_vbox.append(&button_box);
+
+    let status_label_clone = status_label.clone();
+    let markdown_clone = markdown.clone();
+    copy_button.connect_clicked(move |button| {
+        button.clipboard().set_text(&markdown_clone);
+        status_label_clone.set_text("Copied to the clipboard.");
+    });
+
+    for (button, extension, rendered) in [
+        (markdown_button, "md", markdown),
+        (html_button, "html", statblock_export::render_html(monster)),
+    ] {
+        let window_clone = window.clone();
+        let status_label_clone = status_label.clone();
+        let suggested_name = format!("{}.{}", monster.name, extension);
+        button.connect_clicked(move |_| {
+            let status_label_clone = status_label_clone.clone();
+            let rendered = rendered.clone();
+            UiFactory::choose_file(
+                &window_clone,
+                "Save Stat Block",
+                gtk::FileChooserAction::Save,
+                &[&format!("*.{}", extension)],
+                Some(&suggested_name),
+                move |path| {
+                    match std::fs::write(&path, &rendered) {
+                        Ok(()) => status_label_clone.set_text(&format!("Saved to {}", path.display())),
+                        Err(e) => status_label_clone.set_text(&format!("Failed to save: {}", e)),
+                    }
+                }
+            );
+        });
+    }
+
+    let window_clone = window.clone();
+    close_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
@@ -0,0 +1,767 @@
+// import_interface.rs
+//
+// This file holds the dialogs used to bring monsters in from outside the app.
+// Every importer ends in a preview so nothing is written until the user confirms.
+
+use std::path::Path;
+use std::rc::Rc;
+use gtk::{ prelude::*, Align, Box, CheckButton, DropDown, ListBox, Orientation, TextView };
+use libadwaita::Application as AdwApplication;
+use gtk::ApplicationWindow as AdwWindow;
+
+use super::interface;
+use super::monster_import::{ self, ImportedMonster };
+use super::monster_csv::{ self, CsvImport };
+use super::monster_bundle::{ self, BundleConflict, BundleResolution, MonsterBundle };
+use super::monster_manager::{ self, Monster, NO_FOLDER_LABEL };
+use super::ui_factory::UiFactory;
+
+/// Choices for a bundled monster that isn't in the library yet.
+const NEW_OPTIONS: [&str; 2] = ["Import", "Skip"];
+
+/// Choices for a bundled monster that clashes with one in the library.
+const CONFLICT_OPTIONS: [&str; 3] = ["Skip", "Overwrite", "Import as Copy"];
+
+/// Builds the "Import" button of the monster list, with one entry per import source.
+pub fn create_import_menu_button(app: &AdwApplication, window: &AdwWindow) -> gtk::MenuButton {
+    let menu_box = UiFactory::create_box(Orientation::Vertical, 6, (6, 6, 6, 6));
+    let popover = gtk::Popover::builder().child(&menu_box).build();
+    let menu_button = gtk::MenuButton::builder().label("Import").popover(&popover).build();
+
+    let json_button = UiFactory::create_button("5e.tools / Open5e JSON...", Align::Fill, None);
+    let text_button = UiFactory::create_button("Stat block text...", Align::Fill, None);
+    let bundle_button = UiFactory::create_button("Monster bundle...", Align::Fill, None);
+    let csv_button = UiFactory::create_button("CSV spreadsheet...", Align::Fill, None);
+    menu_box.append(&json_button);
+    menu_box.append(&text_button);
+    menu_box.append(&bundle_button);
+    menu_box.append(&csv_button);
+
+    let app_clone = app.clone();
+    let window_clone = window.clone();
+    let popover_clone = popover.clone();
+    json_button.connect_clicked(move |_| {
+        popover_clone.popdown();
+        show_json_import(&app_clone, &window_clone);
+    });
+
+    let app_clone = app.clone();
+    let window_clone = window.clone();
+    let popover_clone = popover.clone();
+    text_button.connect_clicked(move |_| {
+        popover_clone.popdown();
+        show_text_import(&app_clone, &window_clone);
+    });
+
+    let app_clone = app.clone();
+    let window_clone = window.clone();
+    let popover_clone = popover.clone();
+    bundle_button.connect_clicked(move |_| {
+        popover_clone.popdown();
+        show_bundle_import(&app_clone, &window_clone);
+    });
+
+    let app_clone = app.clone();
+    let window_clone = window.clone();
+    let popover_clone = popover.clone();
+    csv_button.connect_clicked(move |_| {
+        popover_clone.popdown();
+        show_csv_import(&app_clone, &window_clone);
+    });
+
+    menu_button
+}
+
+/// Lets the user pick a local 5e.tools or Open5e JSON file and previews what it contains.
+fn show_json_import(app: &AdwApplication, parent_window: &AdwWindow) {
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    UiFactory::choose_file(
+        parent_window,
+        "Import Monsters from JSON",
+        gtk::FileChooserAction::Open,
+        &["*.json"],
+        None,
+        move |path| {
+            match monster_import::import_json_file(&path) {
+                Ok(imported) => show_import_preview(&app_clone, &parent_window_clone, imported),
+                Err(e) => show_import_error(&app_clone, &parent_window_clone, &path, &e.to_string()),
+            }
+        }
+    );
+}
+
+/// Lets the user paste a stat block, check what the parser understood and open it in the monster form.
+fn show_text_import(app: &AdwApplication, parent_window: &AdwWindow) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title("Import from Text")
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(600)
+        .default_height(650)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let title = UiFactory::create_label("Paste a Stat Block", Align::Center, false, &["title-2"]);
+    let hint = UiFactory::create_label(
+        "The first line is used as the monster's name. Check the result, then open it in the monster form to review and save.",
+        Align::Center,
+        false,
+        &[]
+    );
+    hint.set_wrap(true);
+    main_vbox.append(&title);
+    main_vbox.append(&hint);
+
+    let text_view = TextView::builder()
+        .editable(true)
+        .wrap_mode(gtk::WrapMode::WordChar)
+        .accepts_tab(false)
+        .build();
+    text_view.add_css_class("view");
+    let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
+    scrolled_window.set_has_frame(true);
+    scrolled_window.set_child(Some(&text_view));
+    main_vbox.append(&scrolled_window);
+
+    let result_label = UiFactory::create_label("", Align::Start, false, &[]);
+    result_label.set_wrap(true);
+    result_label.set_selectable(true);
+    main_vbox.append(&result_label);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let check_button = UiFactory::create_button("Check", Align::Center, None);
+    let open_button = UiFactory::create_button("Open in Monster Form", Align::Center, Some("suggested-action"));
+    let cancel_button = UiFactory::create_button("Cancel", Align::Center, None);
+    button_box.append(&check_button);
+    button_box.append(&open_button);
+    button_box.append(&cancel_button);
+    main_vbox.append(&button_box);
+
+    let pasted_text = {
+        let text_view = text_view.clone();
+        move || {
+            let buffer = text_view.buffer();
+            buffer.text(&buffer.start_iter(), &buffer.end_iter(), false).to_string()
+        }
+    };
+    let pasted_text = Rc::new(pasted_text);
+
+    let result_label_clone = result_label.clone();
+    let pasted_text_clone = Rc::clone(&pasted_text);
+    check_button.connect_clicked(move |_| {
+        match monster_import::import_stat_block_text(&pasted_text_clone()) {
+            Ok(entry) => result_label_clone.set_text(&describe_text_import(&entry)),
+            Err(e) => result_label_clone.set_text(&e.to_string()),
+        }
+    });
+
+    let window_clone = window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    open_button.connect_clicked(move |_| {
+        match monster_import::import_stat_block_text(&pasted_text()) {
+            Ok(entry) => {
+                window_clone.close();
+                interface::show_imported_monster_form(&app_clone, &parent_window_clone, entry.monster);
+            }
+            Err(e) => result_label.set_text(&e.to_string()),
+        }
+    });
+
+    let window_clone = window.clone();
+    cancel_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// Short summary of a parsed stat block for the text import dialog.
+fn describe_text_import(entry: &ImportedMonster) -> String {
+    let monster = &entry.monster;
+    let attacks = monster.attacks
+        .iter()
+        .map(|a| a.attack_name.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ");
+    let mut lines = vec![
+        format!(
+            "{}: HP {} ({}), AC {}, Speed {}, EXP {}, PB {}",
+            monster.name,
+            monster.hp,
+            monster.hitdie,
+            monster.ac,
+            monster.speeds.describe(),
+            monster.exp,
+            monster.pb
+        ),
+        interface::format_ability_scores(monster),
+        format!("Attacks: {}", if attacks.is_empty() { "None" } else { &attacks }),
+    ];
+    lines.extend(entry.notes.iter().map(|n| format!("• {}", n)));
+    lines.join("\n")
+}
+
+/// Lets the user pick a bundle file exported from this app and previews it.
+fn show_bundle_import(app: &AdwApplication, parent_window: &AdwWindow) {
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    UiFactory::choose_file(
+        parent_window,
+        "Import Monster Bundle",
+        gtk::FileChooserAction::Open,
+        &["*.json"],
+        None,
+        move |path| {
+            match monster_bundle::read_bundle(&path) {
+                Ok(bundle) => show_bundle_import_preview(&app_clone, &parent_window_clone, bundle),
+                Err(e) => show_import_error(&app_clone, &parent_window_clone, &path, &e.to_string()),
+            }
+        }
+    );
+}
+
+/// Lists the monsters of a bundle with any clash against the library, letting the user
+/// skip, overwrite or rename each one before anything is saved.
+fn show_bundle_import_preview(app: &AdwApplication, parent_window: &AdwWindow, bundle: MonsterBundle) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title("Import Bundle")
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(650)
+        .default_height(600)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let title_text = if bundle.title.is_empty() { "Untitled Bundle" } else { &bundle.title };
+    let title = UiFactory::create_label(title_text, Align::Center, false, &["title-2"]);
+    main_vbox.append(&title);
+
+    let created = chrono::DateTime::parse_from_rfc3339(&bundle.created_at)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+    let mut details = format!(
+        "{} monster(s), {} folder(s). Created {} with version {}.",
+        bundle.monsters.len(),
+        bundle.folders.len(),
+        created,
+        bundle.app_version
+    );
+    if !bundle.description.is_empty() {
+        details = format!("{}\n{}", bundle.description, details);
+    }
+    let details_label = UiFactory::create_label(&details, Align::Center, false, &[]);
+    details_label.set_wrap(true);
+    main_vbox.append(&details_label);
+
+    let library = monster_manager::read_all_monsters();
+    let (monsters, failures) = monster_bundle::bundle_monsters(&bundle);
+
+    let conflict_row = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    let all_conflicts_dropdown = UiFactory::create_dropdown(&CONFLICT_OPTIONS, Some(0), None);
+    conflict_row.append(&UiFactory::create_label("For all conflicts:", Align::Start, false, &[]));
+    conflict_row.append(&all_conflicts_dropdown);
+    main_vbox.append(&conflict_row);
+
+    let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
+    let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
+    list_box.add_css_class("boxed-list");
+
+    let mut rows: Vec<(DropDown, Monster, BundleConflict)> = Vec::new();
+    for monster in monsters {
+        let conflict = monster_bundle::find_conflict(&monster, &library);
+        let row = UiFactory::create_box(Orientation::Horizontal, 12, (6, 6, 12, 12));
+        let info_vbox = UiFactory::create_box(Orientation::Vertical, 3, (0, 0, 0, 0));
+        info_vbox.set_hexpand(true);
+
+        let folder = if monster.folder.is_empty() { String::new() } else { format!(" ({})", monster.folder) };
+        info_vbox.append(&UiFactory::create_label(
+            &format!("<b>{}</b>{}", gtk::glib::markup_escape_text(&monster.name), gtk::glib::markup_escape_text(&folder)),
+            Align::Start,
+            true,
+            &[]
+        ));
+        let status = match &conflict {
+            BundleConflict::None => "New monster".to_string(),
+            BundleConflict::Identical(existing) => format!("Already in the library as \"{}\" with the same stats", existing.name),
+            BundleConflict::Differs(existing) => format!("Conflicts with \"{}\": same name or id, different stats", existing.name),
+        };
+        info_vbox.append(&UiFactory::create_label(&status, Align::Start, false, &["dim-label"]));
+
+        let dropdown = match conflict {
+            BundleConflict::None => UiFactory::create_dropdown(&NEW_OPTIONS, Some(0), None),
+            _ => UiFactory::create_dropdown(&CONFLICT_OPTIONS, Some(0), None),
+        };
+        dropdown.set_valign(Align::Center);
+
+        row.append(&info_vbox);
+        row.append(&dropdown);
+        list_box.append(&row);
+        rows.push((dropdown, monster, conflict));
+    }
+    for failure in &failures {
+        list_box.append(&UiFactory::create_label(&format!("Can't be read: {}", failure), Align::Start, false, &[]));
+    }
+    let rows = Rc::new(rows);
+
+    let rows_clone = Rc::clone(&rows);
+    all_conflicts_dropdown.connect_selected_notify(move |dropdown| {
+        for (row_dropdown, _, conflict) in rows_clone.iter() {
+            if !matches!(conflict, BundleConflict::None) {
+                row_dropdown.set_selected(dropdown.selected());
+            }
+        }
+    });
+
+    scrolled_window.set_child(Some(&list_box));
+    main_vbox.append(&scrolled_window);
+
+    let error_label = UiFactory::create_label("", Align::Center, false, &[]);
+    error_label.set_wrap(true);
+    main_vbox.append(&error_label);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let import_button = UiFactory::create_button("Import", Align::Center, Some("suggested-action"));
+    let cancel_button = UiFactory::create_button("Cancel", Align::Center, None);
+    button_box.append(&import_button);
+    button_box.append(&cancel_button);
+    main_vbox.append(&button_box);
+
+    let window_clone = window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    import_button.connect_clicked(move |_| {
+        let mut failures = Vec::new();
+        for folder in &bundle.folders {
+            if let Err(e) = monster_manager::create_folder(folder) {
+                failures.push(format!("Folder {}: {}", folder, e));
+            }
+        }
+
+        // Read again so copies saved by an earlier, partly failed attempt count as taken
+        let mut taken_names: Vec<String> = monster_manager::read_all_monsters().into_iter().map(|m| m.name).collect();
+        for (dropdown, monster, conflict) in rows.iter() {
+            // Rows are disabled once saved, so a retry doesn't import them twice
+            if !dropdown.is_sensitive() {
+                continue;
+            }
+            let resolution = match (conflict, UiFactory::get_dropdown_text(dropdown).as_str()) {
+                (BundleConflict::None, "Import") => BundleResolution::Import,
+                (_, "Overwrite") => BundleResolution::Overwrite,
+                (_, "Import as Copy") => BundleResolution::Rename,
+                _ => BundleResolution::Skip,
+            };
+            let skipped = matches!(resolution, BundleResolution::Skip);
+            match monster_bundle::import_bundled_monster(monster.clone(), conflict, resolution, &mut taken_names) {
+                Ok(()) if skipped => {}
+                Ok(()) => {
+                    dropdown.set_sensitive(false);
+                    dropdown.set_tooltip_text(Some("Already imported"));
+                }
+                Err(e) => failures.push(format!("{}: {}", monster.name, e)),
+            }
+        }
+
+        if failures.is_empty() {
+            window_clone.close();
+            interface::switch_to_monster_list(&app_clone, &parent_window_clone);
+        } else {
+            error_label.set_text(&format!(
+                "Some monsters couldn't be imported; the rest were saved and are now greyed out:\n{}",
+                failures.join("\n")
+            ));
+        }
+    });
+
+    let window_clone = window.clone();
+    cancel_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// Lets the user pick an edited monster CSV. Its attack file is picked up from next to it.
+fn show_csv_import(app: &AdwApplication, parent_window: &AdwWindow) {
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    UiFactory::choose_file(
+        parent_window,
+        "Import Monsters from CSV",
+        gtk::FileChooserAction::Open,
+        &["*.csv"],
+        None,
+        move |path| {
+            let library = monster_manager::read_all_monsters();
+            match monster_csv::read_csv(&path, &library) {
+                Ok(import) => show_csv_import_preview(&app_clone, &parent_window_clone, import),
+                Err(e) => show_import_error(&app_clone, &parent_window_clone, &path, &e.to_string()),
+            }
+        }
+    );
+}
+
+/// Lists the new and changed monsters of a CSV import along with every row that failed the checks.
+/// Rows without changes start unchecked.
+fn show_csv_import_preview(app: &AdwApplication, parent_window: &AdwWindow, import: CsvImport) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title("CSV Import")
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(650)
+        .default_height(600)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let title = UiFactory::create_label(
+        &format!("{} valid row(s), {} problem(s)", import.rows.len(), import.errors.len()),
+        Align::Center,
+        false,
+        &["title-2"]
+    );
+    main_vbox.append(&title);
+
+    let attacks_text = match &import.attacks_path {
+        Some(path) => format!("Monsters with rows in {} get exactly those attacks; the others keep theirs.", path.display()),
+        None => "No attack file was found next to this one, so attacks are left unchanged.".to_string(),
+    };
+    let hint = UiFactory::create_label(&attacks_text, Align::Center, false, &[]);
+    hint.set_wrap(true);
+    main_vbox.append(&hint);
+
+    let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
+    let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
+    list_box.add_css_class("boxed-list");
+
+    for error in &import.errors {
+        list_box.append(&UiFactory::create_label(
+            &format!("{}, line {}: {}", error.file, error.line, error.message),
+            Align::Start,
+            false,
+            &["error"]
+        ));
+    }
+
+    let mut checks: Vec<CheckButton> = Vec::new();
+    for row in &import.rows {
+        let (status, changes) = match &row.existing {
+            Some(existing) => ("Update", monster_csv::describe_changes(existing, &row.monster)),
+            None => ("New", Vec::new()),
+        };
+        let check = UiFactory::create_check_button(row.existing.is_none() || !changes.is_empty());
+        check.set_valign(Align::Start);
+
+        let row_box = UiFactory::create_box(Orientation::Horizontal, 12, (6, 6, 12, 12));
+        let info_vbox = UiFactory::create_box(Orientation::Vertical, 3, (0, 0, 0, 0));
+        info_vbox.set_hexpand(true);
+        info_vbox.append(&UiFactory::create_label(
+            &format!("<b>{}</b> ({}, line {})", gtk::glib::markup_escape_text(&row.monster.name), status, row.line),
+            Align::Start,
+            true,
+            &[]
+        ));
+        let details = if row.existing.is_none() {
+            format!("HP: {}, AC: {}, EXP: {}, {} attack(s)", row.monster.hp, row.monster.ac, row.monster.exp, row.monster.attacks.len())
+        } else if changes.is_empty() {
+            "No changes".to_string()
+        } else {
+            changes.join("\n")
+        };
+        let details_label = UiFactory::create_label(&details, Align::Start, false, &["dim-label"]);
+        details_label.set_wrap(true);
+        info_vbox.append(&details_label);
+        // The row is still importable; the values just don't match the challenge rating
+        if let Some(warning) = monster_manager::check_cr(&row.monster) {
+            let warning_label = UiFactory::create_label(&warning, Align::Start, false, &["warning"]);
+            warning_label.set_wrap(true);
+            info_vbox.append(&warning_label);
+        }
+
+        row_box.append(&check);
+        row_box.append(&info_vbox);
+        list_box.append(&row_box);
+        checks.push(check);
+    }
+
+    scrolled_window.set_child(Some(&list_box));
+    main_vbox.append(&scrolled_window);
+
+    let error_label = UiFactory::create_label("", Align::Center, false, &[]);
+    error_label.set_wrap(true);
+    main_vbox.append(&error_label);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let import_button = UiFactory::create_button("Save Selected", Align::Center, Some("suggested-action"));
+    let cancel_button = UiFactory::create_button("Cancel", Align::Center, None);
+    button_box.append(&import_button);
+    button_box.append(&cancel_button);
+    main_vbox.append(&button_box);
+
+    let window_clone = window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    import_button.connect_clicked(move |_| {
+        let selected = CsvImport {
+            rows: import.rows
+                .iter()
+                .zip(checks.iter())
+                .filter(|(_, check)| check.is_active())
+                .map(|(row, _)| row.clone())
+                .collect(),
+            ..CsvImport::default()
+        };
+
+        let failures = monster_csv::apply_csv_import(&selected);
+        if failures.is_empty() {
+            window_clone.close();
+            interface::switch_to_monster_list(&app_clone, &parent_window_clone);
+        } else {
+            error_label.set_text(&format!("Some monsters couldn't be saved:\n{}", failures.join("\n")));
+        }
+    });
+
+    let window_clone = window.clone();
+    cancel_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// Shows why a file couldn't be imported.
+fn show_import_error(app: &AdwApplication, parent_window: &AdwWindow, path: &Path, message: &str) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title("Import Failed")
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(400)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let message_label = UiFactory::create_label(
+        &format!("Couldn't import {}:\n{}", path.display(), message),
+        Align::Start,
+        false,
+        &[]
+    );
+    message_label.set_wrap(true);
+    let close_button = UiFactory::create_button("Close", Align::End, None);
+    main_vbox.append(&message_label);
+    main_vbox.append(&close_button);
+
+    let window_clone = window.clone();
+    close_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// Lists the converted monsters with everything that couldn't be mapped, and saves the checked ones on confirm.
+fn show_import_preview(app: &AdwApplication, parent_window: &AdwWindow, imported: Vec<ImportedMonster>) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title("Import Preview")
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(650)
+        .default_height(600)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let title = UiFactory::create_label(
+        &format!("Found {} monster(s)", imported.len()),
+        Align::Center,
+        false,
+        &["title-2"]
+    );
+    let hint = UiFactory::create_label(
+        "Review what will be imported. Unchecked monsters are skipped.",
+        Align::Center,
+        false,
+        &[]
+    );
+    main_vbox.append(&title);
+    main_vbox.append(&hint);
+
+    let mut folder_options = vec![NO_FOLDER_LABEL.to_string()];
+    folder_options.extend(monster_manager::list_folders());
+    let folder_option_refs: Vec<&str> = folder_options.iter().map(|f| f.as_str()).collect();
+    let folder_row = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    let folder_dropdown = UiFactory::create_dropdown(&folder_option_refs, Some(0), None);
+    folder_row.append(&UiFactory::create_label("Save into folder:", Align::Start, false, &[]));
+    folder_row.append(&folder_dropdown);
+    main_vbox.append(&folder_row);
+
+    let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
+    let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
+    list_box.add_css_class("boxed-list");
+
+    let existing_names: Vec<String> = monster_manager::read_all_monsters()
+        .into_iter()
+        .map(|m| m.name.to_lowercase())
+        .collect();
+
+    let mut rows: Vec<(CheckButton, ImportedMonster)> = Vec::new();
+    for entry in imported {
+        let check = UiFactory::create_check_button(true);
+        list_box.append(&create_preview_row(&entry, &check, &existing_names));
+        rows.push((check, entry));
+    }
+    let rows = Rc::new(rows);
+
+    scrolled_window.set_child(Some(&list_box));
+    main_vbox.append(&scrolled_window);
+
+    let error_label = UiFactory::create_label("", Align::Center, false, &[]);
+    error_label.set_wrap(true);
+    main_vbox.append(&error_label);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let import_button = UiFactory::create_button("Import Selected", Align::Center, Some("suggested-action"));
+    let cancel_button = UiFactory::create_button("Cancel", Align::Center, None);
+    button_box.append(&import_button);
+    button_box.append(&cancel_button);
+    main_vbox.append(&button_box);
+
+    let window_clone = window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    import_button.connect_clicked(move |_| {
+        let folder = match UiFactory::get_dropdown_text(&folder_dropdown) {
+            f if f == NO_FOLDER_LABEL => String::new(),
+            f => f,
+        };
+
+        let mut failures = Vec::new();
+        for (check, entry) in rows.iter() {
+            if !check.is_active() {
+                continue;
+            }
+            let mut monster = entry.monster.clone();
+            monster.folder = folder.clone();
+            match monster_manager::save_monster(monster) {
+                // Saved monsters are unchecked so retrying after a failure doesn't import them twice
+                Ok(_) => {
+                    check.set_active(false);
+                    check.set_sensitive(false);
+                    check.set_tooltip_text(Some("Already imported"));
+                }
+                Err(e) => failures.push(format!("{}: {}", entry.monster.name, e)),
+            }
+        }
+
+        if failures.is_empty() {
+            window_clone.close();
+            interface::switch_to_monster_list(&app_clone, &parent_window_clone);
+        } else {
+            error_label.set_text(&format!(
+                "Some monsters couldn't be saved; the rest were imported and are now unchecked:\n{}",
+                failures.join("\n")
+            ));
+        }
+    });
+
+    let window_clone = window.clone();
+    cancel_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// One monster of the import preview: the converted stats plus the notes about what was left out.
+fn create_preview_row(entry: &ImportedMonster, check: &CheckButton, existing_names: &[String]) -> Box {
+    let monster = &entry.monster;
+    let row = UiFactory::create_box(Orientation::Horizontal, 12, (6, 6, 12, 12));
+    check.set_valign(Align::Start);
+    row.append(check);
+
+    let info_vbox = UiFactory::create_box(Orientation::Vertical, 3, (0, 0, 0, 0));
+    info_vbox.set_hexpand(true);
+
+    let name_label = UiFactory::create_label(
+        &format!("<b>{}</b>", gtk::glib::markup_escape_text(&monster.name)),
+        Align::Start,
+        true,
+        &[]
+    );
+    let stats_label = UiFactory::create_label(
+        &format!(
+            "HP: {} ({}), AC: {}, Speed: {}, EXP: {}, PB: {}\n{}",
+            monster.hp,
+            monster.hitdie,
+            monster.ac,
+            monster.speeds.describe(),
+            monster.exp,
+            monster.pb,
+            interface::format_ability_scores(monster)
+        ),
+        Align::Start,
+        false,
+        &[]
+    );
+    info_vbox.append(&name_label);
+    info_vbox.append(&stats_label);
+
+    let attacks_str = monster.attacks
+        .iter()
+        .map(|a| format!("{} ({}{}, {})", a.attack_name, a.num_dice, a.dice_used, a.ability_used))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let attacks_label = UiFactory::create_label(
+        &format!("Attacks: {}", if attacks_str.is_empty() { "None" } else { &attacks_str }),
+        Align::Start,
+        false,
+        &[]
+    );
+    attacks_label.set_wrap(true);
+    info_vbox.append(&attacks_label);
+
+    let mut notes = entry.notes.clone();
+    if existing_names.contains(&monster.name.to_lowercase()) {
+        notes.insert(0, "A monster with this name already exists; importing adds a second one.".to_string());
+    }
+    if !notes.is_empty() {
+        let notes_label = UiFactory::create_label(
+            &notes.iter().map(|n| format!("• {}", n)).collect::<Vec<String>>().join("\n"),
+            Align::Start,
+            false,
+            &["dim-label"]
+        );
+        notes_label.set_wrap(true);
+        info_vbox.append(&notes_label);
+    }
+
+    row.append(&info_vbox);
+    row
+}
@@ -9,10 +9,22 @@ use gtk::{ Button, Align, Box };
 use libadwaita::Application as AdwApplication;
 use gtk::ApplicationWindow as AdwWindow;
 
-use crate::monster_manager::Monster;
+use crate::monster_manager::{ Monster, NO_FOLDER_LABEL };
 use crate::ui_factory::UiFactory;
 
-use super::{ monster_manager, simulation };
+use super::{ encounter_manager, export_interface, import_interface, monster_manager, simulation };
+
+/// Dropdown entry for attacks whose damage type isn't recorded.
+const UNSPECIFIED_DAMAGE_TYPE: &str = "Unspecified";
+
+/// Proficiency levels offered for each skill in the monster form.
+const SKILL_LEVELS: [&str; 3] = ["-", "Proficient", "Expertise"];
+
+/// CR dropdown entry for monsters without a challenge rating.
+const NO_CR_LABEL: &str = "None";
+
+/// Size and type dropdown entry for monsters where it isn't recorded.
+const UNSET_LABEL: &str = "-";
 
 // =========================================================================
 // Monster Creation/Editing Form
@@ -28,31 +40,40 @@ pub fn edit_monster_creation_menu(app: &AdwApplication, parent_window: &AdwWindo
     show_monster_form(app, parent_window, Some(monster));
 }
 
+/// Opens the creation form pre-filled with an imported monster so it can be reviewed before saving
+pub fn show_imported_monster_form(app: &AdwApplication, parent_window: &AdwWindow, monster: Monster) {
+    show_monster_form(app, parent_window, Some(monster));
+}
+
 /// The messy display logic that actually creates the gui for it
 fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_monster: Option<Monster>) {
-    let is_edit = existing_monster.is_some();
+    // Imported monsters pre-fill the form but have no id yet, so they are still created rather than edited
+    let is_edit = existing_monster.as_ref().is_some_and(|m| !m.id.is_empty());
 
     // Unpack data fields based on create/edit mode
-    let (name, hp, ac, speed, exp, pb, hitdie_idx, mods, saves, vulns, rests, immuns, abilities) =
+    let (name, hp, ac, cr, exp, pb, hitdie_idx, scores, saves, vulns, rests, immuns, condition_immuns, abilities, folder, tags) =
         match &existing_monster {
             Some(m) =>
                 (
                     m.name.clone(),
                     m.hp.to_string(),
                     m.ac.to_string(),
-                    m.speed.to_string(),
+                    m.cr.clone(),
                     m.exp.to_string(),
                     m.pb.to_string(),
                     ["d4", "d6", "d8", "d10", "d12", "d20"]
                         .iter()
                         .position(|&x| x == m.hitdie)
                         .unwrap_or(0) as u32,
-                    m.mods,
+                    m.scores,
                     m.saves,
                     m.vulnerabilities.clone(),
-                    m.restistances.clone(),
+                    m.resistances.clone(),
                     m.immunities.clone(),
+                    m.condition_immunities.clone(),
                     m.abilities.clone(),
+                    m.folder.clone(),
+                    m.tags.join(", "),
                 ),
             None =>
                 (
@@ -63,11 +84,14 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
                     "".to_string(),
                     "".to_string(),
                     0,
-                    [0; 6],
+                    [10; 6],
                     [false; 6],
                     vec![],
                     vec![],
                     vec![],
+                    vec![],
+                    "".to_string(),
+                    "".to_string(),
                     "".to_string(),
                 ),
         };
@@ -99,10 +123,21 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
     let hp_entry = UiFactory::create_entry(Some(&hp), Some("Enter hp..."), 15);
 
     let ac_label = UiFactory::create_label("AC:", Align::Start, false, &[]);
-    let ac_entry = UiFactory::create_entry(Some(&ac), Some("ac..."), 5);
-
-    let speed_label = UiFactory::create_label("Speed:", Align::Start, false, &[]);
-    let speed_entry = UiFactory::create_entry(Some(&speed), Some("speed..."), 7);
+    let ac_entry = UiFactory::create_entry(Some(&ac), Some("ac..."), 15);
+
+    // Picking a CR fills in XP and PB unless they are overridden
+    let cr_label = UiFactory::create_label("CR:", Align::Start, false, &[]);
+    let mut cr_options = vec![NO_CR_LABEL];
+    cr_options.extend(monster_manager::CR_TABLE.iter().map(|(rating, _, _)| *rating));
+    let cr_idx = cr_options.iter().position(|c| *c == cr).unwrap_or(0) as u32;
+    let cr_dropdown = UiFactory::create_dropdown(&cr_options, Some(cr_idx), None);
+    let override_check = gtk::CheckButton::with_label("Override XP/PB");
+    let cr_matches = monster_manager::cr_stats(&cr)
+        .is_some_and(|stats| (exp.parse().unwrap_or(0), pb.parse().unwrap_or(0)) == stats);
+    override_check.set_active(cr_idx != 0 && !cr_matches);
+    let cr_block = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    cr_block.append(&cr_dropdown);
+    cr_block.append(&override_check);
 
     let exp_label = UiFactory::create_label("EXP:", Align::Start, false, &[]);
     let exp_entry = UiFactory::create_entry(Some(&exp), Some("Enter xp..."), 15);
@@ -114,10 +149,18 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
     let dice_options = ["d4", "d6", "d8", "d10", "d12", "d20"];
     let die_dropdown = UiFactory::create_dropdown(&dice_options, Some(hitdie_idx), Some(15));
 
-    let ac_speed_block = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
-    ac_speed_block.append(&ac_entry);
-    ac_speed_block.append(&speed_label);
-    ac_speed_block.append(&speed_entry);
+    let folder_label = UiFactory::create_label("Folder:", Align::Start, false, &[]);
+    let mut folder_options = vec![NO_FOLDER_LABEL.to_string()];
+    folder_options.extend(monster_manager::list_folders());
+    if !folder.is_empty() && !folder_options.contains(&folder) {
+        folder_options.push(folder.clone());
+    }
+    let folder_idx = folder_options.iter().position(|f| *f == folder).unwrap_or(0) as u32;
+    let folder_option_refs: Vec<&str> = folder_options.iter().map(|f| f.as_str()).collect();
+    let folder_dropdown = UiFactory::create_dropdown(&folder_option_refs, Some(folder_idx), Some(15));
+
+    let tags_label = UiFactory::create_label("Tags:", Align::Start, false, &[]);
+    let tags_entry = UiFactory::create_entry(Some(&tags), Some("Homebrew, Campaign 2..."), 15);
 
     // Row 0 Layout
     top_grid.attach(&monster_name_label, 0, 0, 1, 1);
@@ -125,14 +168,130 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
     top_grid.attach(&hp_label, 2, 0, 1, 1);
     top_grid.attach(&hp_entry, 3, 0, 1, 1);
     top_grid.attach(&ac_label, 4, 0, 1, 1);
-    top_grid.attach(&ac_speed_block, 5, 0, 1, 1);
+    top_grid.attach(&ac_entry, 5, 0, 1, 1);
     // Row 1 Layout
-    top_grid.attach(&exp_label, 0, 1, 1, 1);
-    top_grid.attach(&exp_entry, 1, 1, 1, 1);
-    top_grid.attach(&pb_label, 2, 1, 1, 1);
-    top_grid.attach(&pb_entry, 3, 1, 1, 1);
-    top_grid.attach(&die_label, 4, 1, 1, 1);
-    top_grid.attach(&die_dropdown, 5, 1, 1, 1);
+    top_grid.attach(&cr_label, 0, 1, 1, 1);
+    top_grid.attach(&cr_block, 1, 1, 1, 1);
+    top_grid.attach(&exp_label, 2, 1, 1, 1);
+    top_grid.attach(&exp_entry, 3, 1, 1, 1);
+    top_grid.attach(&pb_label, 4, 1, 1, 1);
+    top_grid.attach(&pb_entry, 5, 1, 1, 1);
+    // Row 2 Layout
+    top_grid.attach(&die_label, 0, 2, 1, 1);
+    top_grid.attach(&die_dropdown, 1, 2, 1, 1);
+    top_grid.attach(&folder_label, 2, 2, 1, 1);
+    top_grid.attach(&folder_dropdown, 3, 2, 1, 1);
+    top_grid.attach(&tags_label, 4, 2, 1, 1);
+    top_grid.attach(&tags_entry, 5, 2, 1, 1);
+
+    let cr_warning_label = UiFactory::create_label("", Align::Center, false, &["warning"]);
+    cr_warning_label.set_wrap(true);
+
+    // --- Size, Type, Speeds and Senses Grid ---
+    let details = existing_monster.clone().unwrap_or_default();
+    let details_grid = UiFactory::create_grid(6, 12, Align::Center);
+
+    let size_label = UiFactory::create_label("Size:", Align::Start, false, &[]);
+    let mut size_options = vec![UNSET_LABEL];
+    size_options.extend(monster_manager::SIZES);
+    let size_idx = size_options.iter().position(|s| *s == details.size).unwrap_or(0) as u32;
+    let size_dropdown = UiFactory::create_dropdown(&size_options, Some(size_idx), None);
+
+    // Homebrew types that aren't in the SRD list are kept as an extra option
+    let type_label = UiFactory::create_label("Type:", Align::Start, false, &[]);
+    let mut type_options = vec![UNSET_LABEL];
+    type_options.extend(monster_manager::CREATURE_TYPES);
+    if !details.creature_type.is_empty() && !type_options.contains(&details.creature_type.as_str()) {
+        type_options.push(&details.creature_type);
+    }
+    let type_idx = type_options.iter().position(|t| *t == details.creature_type).unwrap_or(0) as u32;
+    let type_dropdown = UiFactory::create_dropdown(&type_options, Some(type_idx), None);
+
+    let subtypes_label = UiFactory::create_label("Subtypes:", Align::Start, false, &[]);
+    let subtypes_entry = UiFactory::create_entry(Some(&details.subtypes.join(", ")), Some("goblinoid..."), 10);
+
+    let alignment_label = UiFactory::create_label("Alignment:", Align::Start, false, &[]);
+    let alignment_entry = UiFactory::create_entry(Some(&details.alignment), Some("neutral evil..."), 10);
+
+    details_grid.attach(&size_label, 0, 0, 1, 1);
+    details_grid.attach(&size_dropdown, 1, 0, 1, 1);
+    details_grid.attach(&type_label, 2, 0, 1, 1);
+    details_grid.attach(&type_dropdown, 3, 0, 1, 1);
+    details_grid.attach(&subtypes_label, 4, 0, 1, 1);
+    details_grid.attach(&subtypes_entry, 5, 0, 1, 1);
+    details_grid.attach(&alignment_label, 6, 0, 1, 1);
+    details_grid.attach(&alignment_entry, 7, 0, 1, 1);
+
+    // Speeds and sense ranges are in feet, blank meaning none
+    let feet_text = |feet: i32| if feet > 0 { feet.to_string() } else { String::new() };
+    let speeds = &details.speeds;
+    let mut speed_entries = Vec::new();
+    for (i, (name, feet)) in [
+        ("Walk", speeds.walk),
+        ("Fly", speeds.fly),
+        ("Swim", speeds.swim),
+        ("Climb", speeds.climb),
+        ("Burrow", speeds.burrow),
+    ].iter().enumerate() {
+        let label = UiFactory::create_label(&format!("{}:", name), Align::Start, false, &[]);
+        let entry = UiFactory::create_entry(Some(&feet_text(*feet)), Some("ft..."), 5);
+        let (row, column) = (1 + (i / 4) as i32, (i % 4) as i32 * 2);
+        details_grid.attach(&label, column, row, 1, 1);
+        details_grid.attach(&entry, column + 1, row, 1, 1);
+        speed_entries.push(entry);
+    }
+    let hover_check = gtk::CheckButton::with_label("Hover");
+    hover_check.set_active(speeds.hover);
+    details_grid.attach(&hover_check, 2, 2, 2, 1);
+
+    let languages_label = UiFactory::create_label("Languages:", Align::Start, false, &[]);
+    let languages_entry = UiFactory::create_entry(Some(&details.languages.join(", ")), Some("Common, Goblin..."), 10);
+    details_grid.attach(&languages_label, 4, 2, 1, 1);
+    details_grid.attach(&languages_entry, 5, 2, 3, 1);
+
+    // Senses that aren't in the SRD list (e.g. from a CSV import) get their own entries so they survive an edit
+    let mut sense_names: Vec<String> = monster_manager::SENSES.iter().map(|s| s.to_string()).collect();
+    for sense in &details.senses {
+        if !sense_names.iter().any(|name| name.eq_ignore_ascii_case(&sense.sense)) {
+            sense_names.push(sense.sense.clone());
+        }
+    }
+    let mut sense_entries: Vec<(String, gtk::Entry)> = Vec::new();
+    for (i, sense) in sense_names.into_iter().enumerate() {
+        let range = details.senses
+            .iter()
+            .find(|s| s.sense.eq_ignore_ascii_case(&sense))
+            .map_or(0, |s| s.range);
+        let label = UiFactory::create_label(&format!("{}:", sense), Align::Start, false, &[]);
+        let entry = UiFactory::create_entry(Some(&feet_text(range)), Some("ft..."), 5);
+        let (row, column) = (3 + (i / 4) as i32, (i % 4) as i32 * 2);
+        details_grid.attach(&label, column, row, 1, 1);
+        details_grid.attach(&entry, column + 1, row, 1, 1);
+        sense_entries.push((sense, entry));
+    }
+
+    // --- Skill Proficiency Grid ---
+    let skills = existing_monster.as_ref().map(|m| m.skills.clone()).unwrap_or_default();
+    let skills_grid = UiFactory::create_grid(6, 12, Align::Center);
+    let mut skill_dropdowns = Vec::new();
+    for (i, (skill, _)) in monster_manager::SKILLS.iter().enumerate() {
+        let level = match skills.iter().find(|s| s.skill == *skill) {
+            Some(s) if s.expertise => 2,
+            Some(_) => 1,
+            None => 0,
+        };
+        let label = UiFactory::create_label(&format!("{}:", skill), Align::Start, false, &[]);
+        let dropdown = UiFactory::create_dropdown(&SKILL_LEVELS, Some(level), None);
+        let (row, column) = ((i / 3) as i32, (i % 3) as i32 * 2);
+        skills_grid.attach(&label, column, row, 1, 1);
+        skills_grid.attach(&dropdown, column + 1, row, 1, 1);
+        skill_dropdowns.push(dropdown);
+    }
+    let skills_expander = gtk::Expander::builder()
+        .label("Skills")
+        .expanded(!skills.is_empty())
+        .child(&skills_grid)
+        .build();
 
     // --- Lower Layout Columns ---
     let lower_hbox = UiFactory::create_box(Orientation::Horizontal, 12, (24, 0, 24, 24));
@@ -141,37 +300,51 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
     let left_vbox = UiFactory::create_box(Orientation::Vertical, 12, (24, 0, 24, 24));
     left_vbox.set_halign(Align::Center);
     
-    // --- Modifier and Saving Throw Grid ---
+    // --- Ability Score and Saving Throw Grid ---
     let mod_grid = UiFactory::create_grid(12, 12, Align::Center);
-    let mod_label = UiFactory::create_label("Mods", Align::Center, false, &[]);
+    let score_label = UiFactory::create_label("Score", Align::Center, false, &[]);
+    let mod_label = UiFactory::create_label("Mod", Align::Center, false, &[]);
     let save_label = UiFactory::create_label("Save Prof", Align::Center, false, &[]);
 
     let stats_data = [
-        ("Str:", mods[0], saves[0]),
-        ("Dex:", mods[1], saves[1]),
-        ("Con:", mods[2], saves[2]),
-        ("Int:", mods[3], saves[3]),
-        ("Wis:", mods[4], saves[4]),
-        ("Cha:", mods[5], saves[5]),
+        ("Str:", scores[0], saves[0]),
+        ("Dex:", scores[1], saves[1]),
+        ("Con:", scores[2], saves[2]),
+        ("Int:", scores[3], saves[3]),
+        ("Wis:", scores[4], saves[4]),
+        ("Cha:", scores[5], saves[5]),
     ];
 
-    let mut mod_entries = Vec::new();
+    let mut score_entries = Vec::new();
     let mut save_checks = Vec::new();
 
-    for (i, &(lbl_text, m_val, s_val)) in stats_data.iter().enumerate() {
+    for (i, &(lbl_text, s_val, p_val)) in stats_data.iter().enumerate() {
         let label = UiFactory::create_label(lbl_text, Align::Start, false, &[]);
-        let entry = UiFactory::create_entry(Some(&m_val.to_string()), None, 3);
-        let check = UiFactory::create_check_button(s_val);
+        let entry = UiFactory::create_entry(Some(&s_val.to_string()), None, 3);
+        let modifier = UiFactory::create_label(&format!("{:+}", monster_manager::score_to_mod(s_val)), Align::Center, false, &[]);
+        let check = UiFactory::create_check_button(p_val);
+
+        // The modifier follows the score as it is typed
+        let modifier_clone = modifier.clone();
+        entry.connect_changed(move |entry| {
+            let text = match entry.text().trim().parse::<i32>() {
+                Ok(score) => format!("{:+}", monster_manager::score_to_mod(score)),
+                Err(_) => "?".to_string(),
+            };
+            modifier_clone.set_text(&text);
+        });
 
         mod_grid.attach(&label, 0, (i + 1) as i32, 1, 1);
         mod_grid.attach(&entry, 1, (i + 1) as i32, 1, 1);
-        mod_grid.attach(&check, 2, (i + 1) as i32, 1, 1);
+        mod_grid.attach(&modifier, 2, (i + 1) as i32, 1, 1);
+        mod_grid.attach(&check, 3, (i + 1) as i32, 1, 1);
 
-        mod_entries.push(entry);
+        score_entries.push(entry);
         save_checks.push(check);
     }
-    mod_grid.attach(&mod_label, 1, 0, 1, 1);
-    mod_grid.attach(&save_label, 2, 0, 1, 1);
+    mod_grid.attach(&score_label, 1, 0, 1, 1);
+    mod_grid.attach(&mod_label, 2, 0, 1, 1);
+    mod_grid.attach(&save_label, 3, 0, 1, 1);
 
     let make_monster_button = UiFactory::create_button(submit_btn_text, Align::Center, None);
     let cancel_button = UiFactory::create_button("Cancel", Align::Center, None);
@@ -195,23 +368,20 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
     let selected_res = Rc::new(RefCell::new(rests));
     let selected_imun = Rc::new(RefCell::new(immuns));
 
-    let res_options = [
-        "Acid",
-        "Bludgening",
-        "Cold",
-        "Fire",
-        "Force",
-        "Lightning",
-        "Necrotic",
-        "Piercing",
-        "Poison",
-        "Psychic",
-        "Radiant",
-        "Slashing",
-        "Thunder",
-    ];
+    // SRD damage types followed by the user-defined ones; "Other..." registers a new one
+    let damage_types = monster_manager::damage_types();
+    let res_options: Vec<&str> = damage_types.iter().map(String::as_str).collect();
     let res_dropdown = UiFactory::create_dropdown(&res_options, None, Some(30));
-    res_dropdown.set_margin_end(170);
+    let other_type_entry = UiFactory::create_entry(None, Some("Other..."), 10);
+    let qualifier_options: Vec<&str> = monster_manager::DamageQualifier::ALL.iter().map(|q| q.label()).collect();
+    let qualifier_dropdown = UiFactory::create_dropdown(&qualifier_options, Some(0), None);
+    qualifier_dropdown.set_tooltip_text(Some("When the vulnerability, resistance or immunity applies"));
+
+    let res_input_hbox = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    res_input_hbox.append(&res_dropdown);
+    res_input_hbox.append(&other_type_entry);
+    res_input_hbox.append(&qualifier_dropdown);
+    let res_error_label = UiFactory::create_label("", Align::Start, false, &[]);
 
     let button_hbox = UiFactory::create_box(Orientation::Horizontal, 12, (0, 0, 0, 0));
     let vuln_button = UiFactory::create_button("Vulnerable", Align::Start, None);
@@ -240,6 +410,7 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
                 &flow_box,
                 Rc::clone(&selected_vulns),
                 &[Rc::clone(&selected_res), Rc::clone(&selected_imun)],
+                v.describe(),
                 v,
                 "Vulnerable",
                 &no_res_options,
@@ -251,6 +422,7 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
                 &flow_box,
                 Rc::clone(&selected_res),
                 &[Rc::clone(&selected_vulns), Rc::clone(&selected_imun)],
+                r.describe(),
                 r,
                 "Resistant",
                 &no_res_options,
@@ -262,8 +434,9 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
                 &flow_box,
                 Rc::clone(&selected_imun),
                 &[Rc::clone(&selected_vulns), Rc::clone(&selected_res)],
+                i.describe(),
                 i,
-                "imunerable",
+                "Immune",
                 &no_res_options,
                 &no_res_label
             );
@@ -272,6 +445,42 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
         flow_box.insert(&no_res_label, -1);
     }
 
+    // --- Condition Immunities ---
+    let condition_label = UiFactory::create_label("Condition Immunities", Align::Start, false, &["title-3"]);
+    let condition_dropdown = UiFactory::create_dropdown(&monster_manager::CONDITIONS, None, None);
+    let condition_button = UiFactory::create_button("Immune", Align::Start, None);
+    let condition_hbox = UiFactory::create_box(Orientation::Horizontal, 12, (0, 0, 0, 0));
+    condition_hbox.append(&condition_dropdown);
+    condition_hbox.append(&condition_button);
+
+    let condition_flow_box = FlowBox::builder()
+        .valign(Align::Start)
+        .max_children_per_line(3)
+        .min_children_per_line(3)
+        .selection_mode(gtk::SelectionMode::None)
+        .row_spacing(5)
+        .column_spacing(1)
+        .build();
+    let no_conditions_label = UiFactory::create_label("No Condition Immunities", Align::Start, false, &[]);
+    let no_conditions = Rc::new(Cell::new(condition_immuns.is_empty()));
+    let selected_conditions: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(condition_immuns));
+    if no_conditions.get() {
+        condition_flow_box.insert(&no_conditions_label, -1);
+    } else {
+        for condition in selected_conditions.borrow().clone() {
+            add_resistance_chip(
+                &condition_flow_box,
+                Rc::clone(&selected_conditions),
+                &[],
+                condition.clone(),
+                condition,
+                "Immune",
+                &no_conditions,
+                &no_conditions_label
+            );
+        }
+    }
+
     let abil_label = UiFactory::create_label("Abilities", Align::Start, false, &["title-3"]);
     let abil_entry = TextView::builder()
         .editable(true)
@@ -282,9 +491,7 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
         .build();
     abil_entry.add_css_class("view");
     abil_entry.grab_focus();
-    if is_edit {
-        abil_entry.buffer().set_text(&abilities);
-    }
+    abil_entry.buffer().set_text(&abilities);
 
     let scrolled_container = UiFactory::create_scrolled_window(false, false, Some(150));
     scrolled_container.set_width_request(300);
@@ -293,9 +500,13 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
     scrolled_container.add_css_class("frame");
 
     right_vbox.append(&res_label);
-    right_vbox.append(&res_dropdown);
+    right_vbox.append(&res_input_hbox);
     right_vbox.append(&button_hbox);
+    right_vbox.append(&res_error_label);
     right_vbox.append(&flow_box);
+    right_vbox.append(&condition_label);
+    right_vbox.append(&condition_hbox);
+    right_vbox.append(&condition_flow_box);
     right_vbox.append(&abil_label);
     right_vbox.append(&scrolled_container);
 
@@ -304,66 +515,134 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
 
     big_vbox.append(&title_label);
     big_vbox.append(&top_grid);
+    big_vbox.append(&cr_warning_label);
+    big_vbox.append(&details_grid);
+    big_vbox.append(&skills_expander);
     big_vbox.append(&lower_hbox);
 
-    // --- Wire Up Chip Insertion Controls ---
-    let flow_box_clone = flow_box.clone();
-    let res_dropdown_clone = res_dropdown.clone();
-    let no_res_label_clone = no_res_label.clone();
-    let no_res_options_clone = Rc::clone(&no_res_options);
-    let selected_vulns_clone = Rc::clone(&selected_vulns);
-    let selected_res_clone = Rc::clone(&selected_res);
-    let selected_imun_clone = Rc::clone(&selected_imun);
-    vuln_button.connect_clicked(move |_| {
-        let term = UiFactory::get_dropdown_text(&res_dropdown_clone);
-        add_resistance_chip(
-            &flow_box_clone,
-            Rc::clone(&selected_vulns_clone),
-            &[Rc::clone(&selected_res_clone), Rc::clone(&selected_imun_clone)],
-            term,
-            "Vulnerable",
-            &no_res_options_clone,
-            &no_res_label_clone
-        );
-    });
+    // --- Wire Up CR Auto-Fill and Warning ---
+    let update_cr_fields = {
+        let cr_dropdown = cr_dropdown.clone();
+        let override_check = override_check.clone();
+        let exp_entry = exp_entry.clone();
+        let pb_entry = pb_entry.clone();
+        let cr_warning_label = cr_warning_label.clone();
+        Rc::new(move || {
+            let cr = selected_cr(&cr_dropdown);
+            let derived = monster_manager::cr_stats(&cr).filter(|_| !override_check.is_active());
+            exp_entry.set_sensitive(derived.is_none());
+            pb_entry.set_sensitive(derived.is_none());
+            if let Some((exp, pb)) = derived {
+                exp_entry.set_text(&exp.to_string());
+                pb_entry.set_text(&pb.to_string());
+            }
+            override_check.set_sensitive(!cr.is_empty());
+
+            let check = Monster {
+                cr,
+                exp: exp_entry.text().parse().unwrap_or(0),
+                pb: pb_entry.text().parse().unwrap_or(0),
+                ..Monster::default()
+            };
+            let warning = monster_manager::check_cr(&check).unwrap_or_default();
+            cr_warning_label.set_text(&warning);
+            cr_warning_label.set_visible(!warning.is_empty());
+        })
+    };
+    update_cr_fields();
+
+    let update_clone = Rc::clone(&update_cr_fields);
+    cr_dropdown.connect_selected_notify(move |_| update_clone());
+    let update_clone = Rc::clone(&update_cr_fields);
+    override_check.connect_toggled(move |_| update_clone());
+    for entry in [&exp_entry, &pb_entry] {
+        let update_clone = Rc::clone(&update_cr_fields);
+        // Only typed edits need a re-check; auto-filled values already match
+        entry.connect_changed(move |entry| {
+            if entry.is_sensitive() {
+                update_clone();
+            }
+        });
+    }
 
-    let flow_box_clone = flow_box.clone();
-    let res_dropdown_clone = res_dropdown.clone();
-    let no_res_label_clone = no_res_label.clone();
-    let no_res_options_clone = Rc::clone(&no_res_options);
-    let selected_vulns_clone = Rc::clone(&selected_vulns);
-    let selected_res_clone = Rc::clone(&selected_res);
-    let selected_imun_clone = Rc::clone(&selected_imun);
-    res_button.connect_clicked(move |_| {
-        let term = UiFactory::get_dropdown_text(&res_dropdown_clone);
-        add_resistance_chip(
-            &flow_box_clone,
-            Rc::clone(&selected_res_clone),
-            &[Rc::clone(&selected_vulns_clone), Rc::clone(&selected_imun_clone)],
-            term,
-            "Resistance",
-            &no_res_options_clone,
-            &no_res_label_clone
-        );
-    });
+    // --- Wire Up Chip Insertion Controls ---
+    // Types typed into "Other..." are only registered when the monster is saved
+    let pending_damage_types: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+
+    // Reads the picked damage type, or the typed "Other..." one
+    let read_modifier = {
+        let res_dropdown = res_dropdown.clone();
+        let other_type_entry = other_type_entry.clone();
+        let qualifier_dropdown = qualifier_dropdown.clone();
+        let pending_damage_types = Rc::clone(&pending_damage_types);
+        Rc::new(move || -> monster_manager::DamageModifier {
+            let other = other_type_entry.text().trim().to_string();
+            let damage_type = if other.is_empty() {
+                UiFactory::get_dropdown_text(&res_dropdown)
+            } else {
+                other_type_entry.set_text("");
+                let known = monster_manager::find_damage_type(&other).or_else(|| {
+                    pending_damage_types.borrow().iter().find(|t| t.eq_ignore_ascii_case(&other)).cloned()
+                });
+                match known {
+                    Some(damage_type) => damage_type,
+                    None => {
+                        // Offer the new type in the dropdown for the rest of the form
+                        pending_damage_types.borrow_mut().push(other.clone());
+                        if let Some(list) = res_dropdown.model().and_downcast::<gtk::StringList>() {
+                            list.append(&other);
+                            res_dropdown.set_selected(list.n_items() - 1);
+                        }
+                        other
+                    }
+                }
+            };
+            let qualifier = monster_manager::DamageQualifier::ALL
+                .get(qualifier_dropdown.selected() as usize)
+                .copied()
+                .unwrap_or_default();
+            monster_manager::DamageModifier { damage_type, qualifier }
+        })
+    };
+
+    for (button, list, other_lists, label_suffix) in [
+        (&vuln_button, &selected_vulns, [&selected_res, &selected_imun], "Vulnerable"),
+        (&res_button, &selected_res, [&selected_vulns, &selected_imun], "Resistant"),
+        (&immun_button, &selected_imun, [&selected_vulns, &selected_res], "Immune"),
+    ] {
+        let flow_box_clone = flow_box.clone();
+        let no_res_label_clone = no_res_label.clone();
+        let no_res_options_clone = Rc::clone(&no_res_options);
+        let list_clone = Rc::clone(list);
+        let other_lists_clone = other_lists.map(Rc::clone);
+        let read_modifier_clone = Rc::clone(&read_modifier);
+        button.connect_clicked(move |_| {
+            let modifier = read_modifier_clone();
+            add_resistance_chip(
+                &flow_box_clone,
+                Rc::clone(&list_clone),
+                &other_lists_clone,
+                modifier.describe(),
+                modifier,
+                label_suffix,
+                &no_res_options_clone,
+                &no_res_label_clone
+            );
+        });
+    }
 
-    let flow_box_clone = flow_box.clone();
-    let res_dropdown_clone = res_dropdown.clone();
-    let no_res_label_clone = no_res_label.clone();
-    let no_res_options_clone = Rc::clone(&no_res_options);
-    let selected_vulns_clone = Rc::clone(&selected_vulns);
-    let selected_res_clone = Rc::clone(&selected_res);
-    let selected_imun_clone = Rc::clone(&selected_imun);
-    immun_button.connect_clicked(move |_| {
-        let term = UiFactory::get_dropdown_text(&res_dropdown_clone);
+    let selected_conditions_clone = Rc::clone(&selected_conditions);
+    condition_button.connect_clicked(move |_| {
+        let condition = UiFactory::get_dropdown_text(&condition_dropdown);
         add_resistance_chip(
-            &flow_box_clone,
-            Rc::clone(&selected_imun_clone),
-            &[Rc::clone(&selected_vulns_clone), Rc::clone(&selected_res_clone)],
-            term,
+            &condition_flow_box,
+            Rc::clone(&selected_conditions_clone),
+            &[],
+            condition.clone(),
+            condition,
             "Immune",
-            &no_res_options_clone,
-            &no_res_label_clone
+            &no_conditions,
+            &no_conditions_label
         );
     });
 
@@ -374,15 +653,29 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
     let monster_name_entry_clone = monster_name_entry.clone();
     let hp_entry_clone = hp_entry.clone();
     let ac_entry_clone = ac_entry.clone();
-    let speed_entry_clone = speed_entry.clone();
+    let cr_dropdown_clone = cr_dropdown.clone();
     let exp_entry_clone = exp_entry.clone();
     let pb_entry_clone = pb_entry.clone();
     let die_dropdown_clone = die_dropdown.clone();
-    let mod_entries_clone = mod_entries.clone();
+    let folder_dropdown_clone = folder_dropdown.clone();
+    let tags_entry_clone = tags_entry.clone();
+    let size_dropdown_clone = size_dropdown.clone();
+    let type_dropdown_clone = type_dropdown.clone();
+    let subtypes_entry_clone = subtypes_entry.clone();
+    let alignment_entry_clone = alignment_entry.clone();
+    let speed_entries_clone = speed_entries.clone();
+    let hover_check_clone = hover_check.clone();
+    let sense_entries_clone = sense_entries.clone();
+    let languages_entry_clone = languages_entry.clone();
+    let score_entries_clone = score_entries.clone();
+    let skill_dropdowns_clone = skill_dropdowns.clone();
     let save_checks_clone = save_checks.clone();
     let selected_vulns_save = Rc::clone(&selected_vulns);
     let selected_res_save = Rc::clone(&selected_res);
     let selected_imun_save = Rc::clone(&selected_imun);
+    let selected_conditions_save = Rc::clone(&selected_conditions);
+    let pending_damage_types_save = Rc::clone(&pending_damage_types);
+    let res_error_label_save = res_error_label.clone();
     let abil_entry_clone = abil_entry.clone();
     let existing_monster_for_save = existing_monster.clone();
 
@@ -396,17 +689,17 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
 
         let hp = parse_int(&hp_entry_clone);
         let ac = parse_int(&ac_entry_clone);
-        let speed = parse_int(&speed_entry_clone);
+        let cr = selected_cr(&cr_dropdown_clone);
         let exp = parse_int(&exp_entry_clone);
         let pb = parse_int(&pb_entry_clone);
 
-        let mods: [i32; 6] = [
-            parse_int(&mod_entries_clone[0]),
-            parse_int(&mod_entries_clone[1]),
-            parse_int(&mod_entries_clone[2]),
-            parse_int(&mod_entries_clone[3]),
-            parse_int(&mod_entries_clone[4]),
-            parse_int(&mod_entries_clone[5]),
+        let scores: [i32; 6] = [
+            parse_int(&score_entries_clone[0]),
+            parse_int(&score_entries_clone[1]),
+            parse_int(&score_entries_clone[2]),
+            parse_int(&score_entries_clone[3]),
+            parse_int(&score_entries_clone[4]),
+            parse_int(&score_entries_clone[5]),
         ];
 
         let saves: [bool; 6] = [
@@ -418,7 +711,55 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
             save_checks_clone[5].is_active(),
         ];
 
+        let skills: Vec<monster_manager::SkillProficiency> = monster_manager::SKILLS
+            .iter()
+            .zip(skill_dropdowns_clone.iter())
+            .filter(|(_, dropdown)| dropdown.selected() > 0)
+            .map(|((skill, _), dropdown)| monster_manager::SkillProficiency {
+                skill: skill.to_string(),
+                expertise: dropdown.selected() == 2,
+            })
+            .collect();
+
         let hitdie = UiFactory::get_dropdown_text(&die_dropdown_clone);
+        let folder = match UiFactory::get_dropdown_text(&folder_dropdown_clone) {
+            f if f == NO_FOLDER_LABEL => String::new(),
+            f => f,
+        };
+        let tags = parse_tags(&tags_entry_clone.text());
+
+        let unset_to_empty = |text: String| if text == UNSET_LABEL { String::new() } else { text };
+        let size = unset_to_empty(UiFactory::get_dropdown_text(&size_dropdown_clone));
+        let creature_type = unset_to_empty(UiFactory::get_dropdown_text(&type_dropdown_clone));
+        let speeds = monster_manager::Speeds {
+            walk: parse_int(&speed_entries_clone[0]),
+            fly: parse_int(&speed_entries_clone[1]),
+            swim: parse_int(&speed_entries_clone[2]),
+            climb: parse_int(&speed_entries_clone[3]),
+            burrow: parse_int(&speed_entries_clone[4]),
+            hover: hover_check_clone.is_active(),
+        };
+        let senses: Vec<monster_manager::Sense> = sense_entries_clone
+            .iter()
+            .filter(|(_, entry)| parse_int(entry) > 0)
+            .map(|(sense, entry)| monster_manager::Sense {
+                sense: sense.clone(),
+                range: parse_int(entry),
+            })
+            .collect();
+
+        // Register the typed damage types the saved lists still use
+        for damage_type in pending_damage_types_save.borrow().iter() {
+            let in_use = [&selected_vulns_save, &selected_res_save, &selected_imun_save]
+                .iter()
+                .any(|list| list.borrow().iter().any(|m| &m.damage_type == damage_type));
+            if in_use && let Err(e) = monster_manager::add_custom_damage_type(damage_type) {
+                eprintln!("Failed to add damage type '{}': {}", damage_type, e);
+                res_error_label_save.set_text(&format!("Failed to add damage type \"{}\": {}", damage_type, e));
+                return;
+            }
+        }
+
         let abilities = abil_entry_clone
             .buffer()
             .text(
@@ -428,27 +769,47 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
             );
 
         let new_monster = Monster {
+            schema_version: monster_manager::MONSTER_SCHEMA_VERSION,
+            // Keeping the id means a rename updates the same file instead of duplicating it
+            id: existing_monster_for_save
+                .as_ref()
+                .map(|m| m.id.clone())
+                .unwrap_or_default(),
             name,
             hp,
             ac,
+            cr,
             exp,
             pb,
-            speed,
+            speeds,
             hitdie,
-            mods,
+            size,
+            creature_type,
+            subtypes: parse_tags(&subtypes_entry_clone.text()),
+            alignment: alignment_entry_clone.text().trim().to_string(),
+            senses,
+            languages: parse_tags(&languages_entry_clone.text()),
+            scores,
             saves,
+            skills,
             vulnerabilities: selected_vulns_save.borrow().clone(),
-            restistances: selected_res_save.borrow().clone(),
+            resistances: selected_res_save.borrow().clone(),
             immunities: selected_imun_save.borrow().clone(),
+            condition_immunities: selected_conditions_save.borrow().clone(),
             abilities: abilities.to_string(),
-            attacks: if is_edit {
-                existing_monster_for_save
-                    .as_ref()
-                    .map(|m| m.attacks.clone())
-                    .unwrap_or_default()
-            } else {
-                Vec::new()
-            },
+            attacks: existing_monster_for_save
+                .as_ref()
+                .map(|m| m.attacks.clone())
+                .unwrap_or_default(),
+            multiattacks: existing_monster_for_save
+                .as_ref()
+                .map(|m| m.multiattacks.clone())
+                .unwrap_or_default(),
+            spellcasting: existing_monster_for_save
+                .as_ref()
+                .and_then(|m| m.spellcasting.clone()),
+            tags,
+            folder,
         };
 
         if let Err(e) = monster_manager::save_monster(new_monster) {
@@ -469,9 +830,50 @@ fn show_monster_form(app: &AdwApplication, parent_window: &AdwWindow, existing_m
     window.present();
 }
 
+/// The challenge rating picked in a CR dropdown, empty when none is.
+fn selected_cr(dropdown: &gtk::DropDown) -> String {
+    match UiFactory::get_dropdown_text(dropdown) {
+        cr if cr == NO_CR_LABEL => String::new(),
+        cr => cr,
+    }
+}
+
+/// Ability scores with their modifiers for summaries, e.g. "STR: 18 (+4), DEX: 12 (+1), ...".
+pub fn format_ability_scores(monster: &Monster) -> String {
+    ["STR", "DEX", "CON", "INT", "WIS", "CHA"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| format!("{}: {} ({:+})", name, monster.scores[i], monster.ability_mod(i)))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Splits a comma separated list (tags, subtypes, languages), dropping blanks and duplicates.
+fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',') {
+        let tag = tag.trim();
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
 /// Helper function to build a clean resistance tag chip and manage UI changes and backing state vectors.
-fn add_resistance_chip(flow_box: &FlowBox, list: Rc<RefCell<Vec<String>>>, other_lists: &[Rc<RefCell<Vec<String>>>], term: String,label_suffix: &str, no_res_options: &Rc<Cell<bool>>, no_res_label: &Label) {
-    if term.trim().is_empty() {
+/// `term_text` is what the chip shows for `term`.
+#[allow(clippy::too_many_arguments)]
+fn add_resistance_chip<T: Clone + PartialEq + 'static>(
+    flow_box: &FlowBox,
+    list: Rc<RefCell<Vec<T>>>,
+    other_lists: &[Rc<RefCell<Vec<T>>>],
+    term_text: String,
+    term: T,
+    label_suffix: &str,
+    no_res_options: &Rc<Cell<bool>>,
+    no_res_label: &Label
+) {
+    if term_text.trim().is_empty() {
         return;
     }
 
@@ -487,11 +889,10 @@ fn add_resistance_chip(flow_box: &FlowBox, list: Rc<RefCell<Vec<String>>>, other
     }
 
     list.borrow_mut().push(term.clone());
-    list.borrow_mut().sort();
 
     let surrounding_hbox = UiFactory::create_box(Orientation::Horizontal, 12, (0, 0, 0, 0));
     let chip_label = UiFactory::create_label(
-        &format!("{}: ({})", term, label_suffix),
+        &format!("{}: ({})", term_text, label_suffix),
         Align::Start,
         false,
         &[]
@@ -511,7 +912,7 @@ fn add_resistance_chip(flow_box: &FlowBox, list: Rc<RefCell<Vec<String>>>, other
         let mut target_list = list_clone.borrow_mut();
         if let Some(flowbox_child) = surrounding_hbox_clone.parent() {
             flow_box_clone.remove(&flowbox_child);
-            if let Ok(idx) = target_list.binary_search(&term_clone) {
+            if let Some(idx) = target_list.iter().position(|t| *t == term_clone) {
                 target_list.remove(idx);
             }
         }
@@ -577,16 +978,24 @@ pub fn switch_to_monster_list(app: &AdwApplication, window: &AdwWindow) {
         Align::Center,
         Some("suggested-action")
     );
-    let continue_simulation_button = UiFactory::create_button(
-        "Continue Simulation",
+    let encounters_button = UiFactory::create_button(
+        "Encounters",
         Align::Center,
         None
     );
 
+    let folders_button = UiFactory::create_button("Folders", Align::Center, None);
+
+    let import_menu_button = import_interface::create_import_menu_button(app, window);
+    let export_menu_button = export_interface::create_export_menu_button(app, window);
+
     top_button_box.append(&create_monster_button);
+    top_button_box.append(&import_menu_button);
+    top_button_box.append(&export_menu_button);
+    top_button_box.append(&folders_button);
     top_button_box.append(&start_simulation_button);
-    if simulation::check_for_simulation() {
-        top_button_box.append(&continue_simulation_button);
+    if encounter_manager::check_for_encounters() {
+        top_button_box.append(&encounters_button);
     }
 
     main_vbox.append(&title_label);
@@ -598,30 +1007,31 @@ pub fn switch_to_monster_list(app: &AdwApplication, window: &AdwWindow) {
         show_monster_creation_menu(&app_clone, &window_clone);
     });
 
+    let app_clone_folders = app.clone();
+    let window_clone_folders = window.clone();
+    folders_button.connect_clicked(move |_| {
+        show_folder_menu(&app_clone_folders, &window_clone_folders);
+    });
+
     let app_clone_sim = app.clone();
     let window_clone_sim = window.clone();
     start_simulation_button.connect_clicked(move |_| {
-        let _ = simulation::remove_simulation_file();
         simulation::show_simulation_setup_menu(&app_clone_sim, &window_clone_sim);
     });
 
-    let app_clone_continue_sim = app.clone();
-    let window_clone_continue_sim = window.clone();
-    continue_simulation_button.connect_clicked(move |_| {
-        simulation::start_simulation_view(
-            &app_clone_continue_sim,
-            &window_clone_continue_sim,
-            Vec::new()
-        );
+    let app_clone_encounters = app.clone();
+    let window_clone_encounters = window.clone();
+    encounters_button.connect_clicked(move |_| {
+        simulation::show_encounter_library(&app_clone_encounters, &window_clone_encounters);
     });
 
     let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
-    let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
-    list_box.add_css_class("boxed-list");
+    let groups_vbox = UiFactory::create_box(Orientation::Vertical, 12, (0, 0, 0, 0));
 
-    let monsters = monster_manager::read_all_monsters();
+    let monsters = Rc::new(monster_manager::read_all_monsters());
+    let folders = Rc::new(monster_manager::list_folders());
 
-    if monsters.is_empty() {
+    if monsters.is_empty() && folders.is_empty() {
         let no_monsters_label = Label::builder()
             .label("No monsters found. Click 'Create New Monster' to add one.")
             .halign(Align::Center)
@@ -629,138 +1039,622 @@ pub fn switch_to_monster_list(app: &AdwApplication, window: &AdwWindow) {
             .vexpand(true)
             .hexpand(true)
             .build();
-        list_box.append(&no_monsters_label);
+        groups_vbox.append(&no_monsters_label);
     } else {
-        for monster in monsters {
-            let row = UiFactory::create_box(Orientation::Horizontal, 12, (6, 6, 12, 12));
-
-            let info_vbox = UiFactory::create_box(Orientation::Vertical, 3, (0, 0, 0, 0));
-            info_vbox.set_halign(Align::Start);
-            info_vbox.set_hexpand(true);
-
-            let name_label = UiFactory::create_label(
-                &format!("<b>{}</b>", monster.name),
-                Align::Start,
-                true,
-                &[]
-            );
-            let stats_label = UiFactory::create_label(
-                &format!(
-                    "HP: {}, AC: {}, EXP: {}, PB: {}, \nSTR: {}, DEX: {}, CON: {}, INT: {}, WIS: {}, CHA: {}",
-                    monster.hp,
-                    monster.ac,
-                    monster.exp,
-                    monster.pb,
-                    monster.mods[0],
-                    monster.mods[1],
-                    monster.mods[2],
-                    monster.mods[3],
-                    monster.mods[4],
-                    monster.mods[5]
-                ),
-                Align::Start,
-                false,
-                &[]
-            );
+        let filter_bar = MonsterFilterBar::new(&monsters);
+        main_vbox.append(&filter_bar.container);
+        populate_monster_groups(&groups_vbox, &monsters, &folders, &filter_bar, app, window);
+
+        let filter_bar_clone = filter_bar.clone();
+        let groups_vbox_clone = groups_vbox.clone();
+        let app_clone = app.clone();
+        let window_clone = window.clone();
+        filter_bar.connect_changed(move || {
+            populate_monster_groups(&groups_vbox_clone, &monsters, &folders, &filter_bar_clone, &app_clone, &window_clone);
+        });
+    }
 
-            let attacks_str = monster.attacks
-                .iter()
-                .map(|a| a.attack_name.as_str())
-                .collect::<Vec<&str>>()
-                .join(", ");
-
-            let attacks_label = UiFactory::create_label(
-                &format!("Attacks: {}", if attacks_str.is_empty() { "None" } else { &attacks_str }),
-                Align::Start,
-                false,
-                &[]
-            );
-            attacks_label.set_ellipsize(pango::EllipsizeMode::End);
-            attacks_label.set_tooltip_text(Some(&attacks_str));
-
-            info_vbox.append(&name_label);
-            info_vbox.append(&stats_label);
-            info_vbox.append(&attacks_label);
-
-            let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
-            button_box.set_halign(Align::End);
-
-            let edit_monster_button = Button::with_label("Edit");
-            let add_attack_button = Button::with_label("Add Attack");
-            let remove_attack_button = Button::with_label("Remove Attack");
-            let delete_button = Button::with_label("Delete");
-            delete_button.add_css_class("destructive-action");
-
-            button_box.append(&edit_monster_button);
-            button_box.append(&add_attack_button);
-            button_box.append(&remove_attack_button);
-            button_box.append(&delete_button);
-
-            let monster_for_edit = monster.clone();
-            let app_clone_for_edit = app.clone();
-            let window_clone_for_edit = window.clone();
-            edit_monster_button.connect_clicked(move |_| {
-                edit_monster_creation_menu(
-                    &app_clone_for_edit,
-                    &window_clone_for_edit,
-                    monster_for_edit.clone()
-                )
-            });
-
-            let monster_name_for_attack = monster.name.clone();
-            let app_clone_for_attack = app.clone();
-            let window_clone_for_attack = window.clone();
-            add_attack_button.connect_clicked(move |_| {
-                show_attack_creation_menu(
-                    &app_clone_for_attack,
-                    &window_clone_for_attack,
-                    &monster_name_for_attack
-                );
-            });
-
-            let monster_name_for_remove = monster.name.clone();
-            let app_clone_for_remove = app.clone();
-            let window_clone_for_remove = window.clone();
-            remove_attack_button.connect_clicked(move |_| {
-                show_remove_attack_menu(
-                    &app_clone_for_remove,
-                    &window_clone_for_remove,
-                    &monster_name_for_remove
-                );
-            });
-
-            let monster_name_to_delete = monster.name.clone();
-            let app_clone_for_refresh = app.clone();
-            let window_clone_for_refresh = window.clone();
-            delete_button.connect_clicked(move |_| {
-                if let Err(e) = monster_manager::delete_monster(&monster_name_to_delete) {
-                    eprintln!("Failed to delete monster '{}': {}", monster_name_to_delete, e);
-                }
-                switch_to_monster_list(&app_clone_for_refresh, &window_clone_for_refresh);
-            });
+    scrolled_window.set_child(Some(&groups_vbox));
+    main_vbox.append(&scrolled_window);
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// Fills the monster list with the monsters passing the filter bar, grouped by folder.
+fn populate_monster_groups(
+    groups_vbox: &Box,
+    monsters: &[Monster],
+    folders: &[String],
+    filter_bar: &MonsterFilterBar,
+    app: &AdwApplication,
+    window: &AdwWindow
+) {
+    while let Some(child) = groups_vbox.first_child() {
+        groups_vbox.remove(&child);
+    }
+
+    let filter = filter_bar.filter();
+    let sort = filter_bar.sort();
+    let mut shown: Vec<&Monster> = monsters.iter().filter(|m| filter.matches(m)).collect();
+    shown.sort_by(|a, b| sort.compare(a, b));
+
+    if shown.is_empty() && filter.is_active() {
+        groups_vbox.append(&UiFactory::create_label("No monsters match the current search.", Align::Center, false, &[]));
+        return;
+    }
+
+    // Unfiled monsters first, then one collapsible group per folder, indented by depth
+    let unfiled: Vec<&Monster> = shown.iter().copied().filter(|m| m.folder.is_empty()).collect();
+    if !unfiled.is_empty() {
+        let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
+        list_box.add_css_class("boxed-list");
+        for monster in unfiled {
+            list_box.append(&create_monster_row(monster, app, window));
+        }
+        groups_vbox.append(&list_box);
+    }
+
+    for folder in folders {
+        let in_folder: Vec<&Monster> = shown.iter().copied().filter(|m| m.folder == *folder).collect();
+        // While searching, folders without a match only add noise
+        if in_folder.is_empty() && filter.is_active() {
+            continue;
+        }
+
+        let depth = folder.matches('/').count() as i32;
+        let folder_name = folder.rsplit('/').next().unwrap_or(folder);
+        let expander = gtk::Expander::builder()
+            .label(format!(
+                "<b>{}</b> ({})",
+                gtk::glib::markup_escape_text(folder_name),
+                in_folder.len()
+            ))
+            .use_markup(true)
+            .expanded(true)
+            .margin_start(depth * 24)
+            .build();
+
+        let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
+        list_box.add_css_class("boxed-list");
+        if in_folder.is_empty() {
+            list_box.append(&Label::new(Some("This folder is empty.")));
+        }
+        for monster in in_folder {
+            list_box.append(&create_monster_row(monster, app, window));
+        }
+        expander.set_child(Some(&list_box));
+        groups_vbox.append(&expander);
+    }
+}
+
+/// Helper function building a single monster row of the monster list
+fn create_monster_row(monster: &Monster, app: &AdwApplication, window: &AdwWindow) -> Box {
+    let row = UiFactory::create_box(Orientation::Horizontal, 12, (6, 6, 12, 12));
+
+    let info_vbox = UiFactory::create_box(Orientation::Vertical, 3, (0, 0, 0, 0));
+    info_vbox.set_halign(Align::Start);
+    info_vbox.set_hexpand(true);
+
+    let name_label = UiFactory::create_label(
+        &format!("<b>{}</b>", monster.name),
+        Align::Start,
+        true,
+        &[]
+    );
+    let type_text = monster.describe_type();
+    let stats_label = UiFactory::create_label(
+        &format!(
+            "{}{}HP: {}, AC: {}, CR: {}, EXP: {}, PB: {}, Speed: {}\n{}",
+            type_text,
+            if type_text.is_empty() { "" } else { "\n" },
+            monster.hp,
+            monster.ac,
+            if monster.cr.is_empty() { "-" } else { &monster.cr },
+            monster.exp,
+            monster.pb,
+            monster.speeds.describe(),
+            format_ability_scores(monster)
+        ),
+        Align::Start,
+        false,
+        &[]
+    );
+
+    let attacks_str = monster.attacks
+        .iter()
+        .map(|a| a.attack_name.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ");
+
+    let attacks_label = UiFactory::create_label(
+        &format!("Attacks: {}", if attacks_str.is_empty() { "None" } else { &attacks_str }),
+        Align::Start,
+        false,
+        &[]
+    );
+    attacks_label.set_ellipsize(pango::EllipsizeMode::End);
+    attacks_label.set_tooltip_text(Some(&attacks_str));
+
+    info_vbox.append(&name_label);
+    info_vbox.append(&stats_label);
+    info_vbox.append(&attacks_label);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+
+    let edit_monster_button = Button::with_label("Edit");
+    let add_attack_button = Button::with_label("Add Attack");
+    let remove_attack_button = Button::with_label("Remove Attack");
+    let multiattack_button = Button::with_label("Multiattack");
+    let spellcasting_button = Button::with_label("Spells");
+    let move_button = Button::with_label("Move");
+    let stat_block_button = Button::with_label("Stat Block");
+    let delete_button = Button::with_label("Delete");
+    delete_button.add_css_class("destructive-action");
+
+    button_box.append(&edit_monster_button);
+    button_box.append(&add_attack_button);
+    button_box.append(&remove_attack_button);
+    button_box.append(&multiattack_button);
+    button_box.append(&spellcasting_button);
+    button_box.append(&move_button);
+    button_box.append(&stat_block_button);
+    button_box.append(&delete_button);
+
+    let monster_for_edit = monster.clone();
+    let app_clone_for_edit = app.clone();
+    let window_clone_for_edit = window.clone();
+    edit_monster_button.connect_clicked(move |_| {
+        edit_monster_creation_menu(
+            &app_clone_for_edit,
+            &window_clone_for_edit,
+            monster_for_edit.clone()
+        )
+    });
+
+    let monster_for_attack = monster.clone();
+    let app_clone_for_attack = app.clone();
+    let window_clone_for_attack = window.clone();
+    add_attack_button.connect_clicked(move |_| {
+        show_attack_creation_menu(
+            &app_clone_for_attack,
+            &window_clone_for_attack,
+            &monster_for_attack
+        );
+    });
+
+    let monster_id_for_remove = monster.id.clone();
+    let app_clone_for_remove = app.clone();
+    let window_clone_for_remove = window.clone();
+    remove_attack_button.connect_clicked(move |_| {
+        show_remove_attack_menu(
+            &app_clone_for_remove,
+            &window_clone_for_remove,
+            &monster_id_for_remove
+        );
+    });
+
+    let monster_for_multiattack = monster.clone();
+    let app_clone_for_multiattack = app.clone();
+    let window_clone_for_multiattack = window.clone();
+    multiattack_button.connect_clicked(move |_| {
+        show_multiattack_menu(
+            &app_clone_for_multiattack,
+            &window_clone_for_multiattack,
+            &monster_for_multiattack
+        );
+    });
+
+    let monster_for_spells = monster.clone();
+    let app_clone_for_spells = app.clone();
+    let window_clone_for_spells = window.clone();
+    spellcasting_button.connect_clicked(move |_| {
+        show_spellcasting_menu(&app_clone_for_spells, &window_clone_for_spells, &monster_for_spells);
+    });
+
+    let monster_for_move = monster.clone();
+    let app_clone_for_move = app.clone();
+    let window_clone_for_move = window.clone();
+    move_button.connect_clicked(move |_| {
+        show_move_monster_menu(&app_clone_for_move, &window_clone_for_move, &monster_for_move);
+    });
+
+    let monster_for_stat_block = monster.clone();
+    let app_clone_for_stat_block = app.clone();
+    let window_clone_for_stat_block = window.clone();
+    stat_block_button.connect_clicked(move |_| {
+        export_interface::show_stat_block_menu(
+            &app_clone_for_stat_block,
+            &window_clone_for_stat_block,
+            &monster_for_stat_block
+        );
+    });
 
-            row.append(&info_vbox);
-            row.append(&button_box);
-            list_box.append(&row);
+    let monster_id_to_delete = monster.id.clone();
+    let monster_name_to_delete = monster.name.clone();
+    let app_clone_for_refresh = app.clone();
+    let window_clone_for_refresh = window.clone();
+    delete_button.connect_clicked(move |_| {
+        if let Err(e) = monster_manager::delete_monster(&monster_id_to_delete) {
+            eprintln!("Failed to delete monster '{}': {}", monster_name_to_delete, e);
         }
+        switch_to_monster_list(&app_clone_for_refresh, &window_clone_for_refresh);
+    });
+
+    row.append(&info_vbox);
+    row.append(&button_box);
+    row
+}
+
+/// Lets the user create, rename/move and delete monster folders.
+fn show_folder_menu(app: &AdwApplication, parent_window: &AdwWindow) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title("Manage Folders")
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(500)
+        .default_height(450)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let title = UiFactory::create_label("Monster Folders", Align::Center, false, &["title-2"]);
+    let hint = UiFactory::create_label(
+        "Use \"/\" to nest folders, e.g. \"Campaign 2/Goblinoids\". Editing a path renames or moves the folder.",
+        Align::Center,
+        false,
+        &[]
+    );
+    hint.set_wrap(true);
+    main_vbox.append(&title);
+    main_vbox.append(&hint);
+
+    let error_label = UiFactory::create_label("", Align::Center, false, &[]);
+
+    let create_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    let new_folder_entry = UiFactory::create_entry(None, Some("New folder..."), 20);
+    new_folder_entry.set_hexpand(true);
+    let create_button = UiFactory::create_button("Create Folder", Align::Center, Some("suggested-action"));
+    create_box.append(&new_folder_entry);
+    create_box.append(&create_button);
+    main_vbox.append(&create_box);
+
+    let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
+    let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
+    list_box.add_css_class("boxed-list");
+
+    let folders = monster_manager::list_folders();
+    if folders.is_empty() {
+        list_box.append(&Label::new(Some("No folders yet.")));
+    }
+    for folder in folders {
+        let row = UiFactory::create_box(Orientation::Horizontal, 6, (6, 6, 12, 12));
+        let path_entry = UiFactory::create_entry(Some(&folder), None, 20);
+        path_entry.set_hexpand(true);
+        let rename_button = UiFactory::create_button("Rename/Move", Align::Center, None);
+        let delete_button = UiFactory::create_button("Delete", Align::Center, Some("destructive-action"));
+        row.append(&path_entry);
+        row.append(&rename_button);
+        row.append(&delete_button);
+
+        let window_clone = window.clone();
+        let app_clone = app.clone();
+        let parent_window_clone = parent_window.clone();
+        let error_label_clone = error_label.clone();
+        let old_folder = folder.clone();
+        rename_button.connect_clicked(move |_| {
+            match monster_manager::rename_folder(&old_folder, &path_entry.text()) {
+                Ok(()) => {
+                    window_clone.close();
+                    switch_to_monster_list(&app_clone, &parent_window_clone);
+                    show_folder_menu(&app_clone, &parent_window_clone);
+                }
+                Err(e) => error_label_clone.set_text(&format!("Failed to rename folder: {}", e)),
+            }
+        });
+
+        let window_clone = window.clone();
+        let app_clone = app.clone();
+        let parent_window_clone = parent_window.clone();
+        let error_label_clone = error_label.clone();
+        delete_button.connect_clicked(move |_| {
+            match monster_manager::delete_folder(&folder) {
+                Ok(()) => {
+                    window_clone.close();
+                    switch_to_monster_list(&app_clone, &parent_window_clone);
+                    show_folder_menu(&app_clone, &parent_window_clone);
+                }
+                Err(_) => error_label_clone.set_text("Only empty folders can be deleted."),
+            }
+        });
+
+        list_box.append(&row);
     }
 
     scrolled_window.set_child(Some(&list_box));
     main_vbox.append(&scrolled_window);
+    main_vbox.append(&error_label);
+
+    let close_button = UiFactory::create_button("Close", Align::End, None);
+    let window_clone = window.clone();
+    close_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+    main_vbox.append(&close_button);
+
+    let window_clone = window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    create_button.connect_clicked(move |_| {
+        match monster_manager::create_folder(&new_folder_entry.text()) {
+            Ok(()) => {
+                window_clone.close();
+                switch_to_monster_list(&app_clone, &parent_window_clone);
+                show_folder_menu(&app_clone, &parent_window_clone);
+            }
+            Err(e) => error_label.set_text(&format!("Failed to create folder: {}", e)),
+        }
+    });
 
     window.set_child(Some(&main_vbox));
     window.present();
 }
 
+/// Displays a folder picker to move a monster into.
+fn show_move_monster_menu(app: &AdwApplication, parent_window: &AdwWindow, monster: &Monster) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title(format!("Move {}", monster.name))
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(350)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let mut folder_options = vec![NO_FOLDER_LABEL.to_string()];
+    folder_options.extend(monster_manager::list_folders());
+    let selected = folder_options.iter().position(|f| *f == monster.folder).unwrap_or(0) as u32;
+    let folder_option_refs: Vec<&str> = folder_options.iter().map(|f| f.as_str()).collect();
+    let (folder_label, folder_dropdown) = UiFactory::create_label_dropdown_pair("Folder:", &folder_option_refs);
+    folder_dropdown.set_selected(selected);
+    let error_label = UiFactory::create_label("", Align::Center, false, &[]);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let move_button = UiFactory::create_button("Move", Align::Center, Some("suggested-action"));
+    let cancel_button = UiFactory::create_button("Cancel", Align::Center, None);
+    button_box.append(&move_button);
+    button_box.append(&cancel_button);
+
+    main_vbox.append(&folder_label);
+    main_vbox.append(&folder_dropdown);
+    main_vbox.append(&error_label);
+    main_vbox.append(&button_box);
+
+    let window_clone = window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    let monster_id = monster.id.clone();
+    move_button.connect_clicked(move |_| {
+        let folder = match UiFactory::get_dropdown_text(&folder_dropdown) {
+            f if f == NO_FOLDER_LABEL => String::new(),
+            f => f,
+        };
+        match monster_manager::move_monster(&monster_id, &folder) {
+            Ok(()) => {
+                window_clone.close();
+                switch_to_monster_list(&app_clone, &parent_window_clone);
+            }
+            Err(e) => error_label.set_text(&format!("Failed to move monster: {}", e)),
+        }
+    });
+
+    let window_clone = window.clone();
+    cancel_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+// =========================================================================
+// Search, Filter & Sort Bar
+// =========================================================================
+
+/// Dropdown entry meaning "don't filter on this".
+const ANY_FILTER_LABEL: &str = "Any";
+
+/// Search entry plus filter and sort controls shared by the monster list and simulation setup.
+#[derive(Clone)]
+pub struct MonsterFilterBar {
+    pub container: Box,
+    search_entry: gtk::SearchEntry,
+    folder_dropdown: gtk::DropDown,
+    tag_dropdown: gtk::DropDown,
+    resistance_dropdown: gtk::DropDown,
+    type_dropdown: gtk::DropDown,
+    min_exp: gtk::SpinButton,
+    max_exp: gtk::SpinButton,
+    min_cr: gtk::DropDown,
+    max_cr: gtk::DropDown,
+    min_ac: gtk::SpinButton,
+    max_ac: gtk::SpinButton,
+    sort_dropdown: gtk::DropDown,
+    exp_bounds: (i32, i32),
+    ac_bounds: (i32, i32),
+}
+
+impl MonsterFilterBar {
+    /// Builds the bar, taking folder, tag, resistance and creature type options and range bounds from the given monsters.
+    pub fn new(monsters: &[Monster]) -> Self {
+        let container = UiFactory::create_box(Orientation::Vertical, 6, (0, 0, 0, 0));
+
+        let search_entry = gtk::SearchEntry::builder()
+            .placeholder_text("Search names and abilities...")
+            .hexpand(true)
+            .build();
+        container.append(&search_entry);
+
+        let with_any = |options: Vec<String>| -> Vec<String> {
+            let mut all = vec![ANY_FILTER_LABEL.to_string()];
+            all.extend(options);
+            all
+        };
+        let folder_options = with_any(monster_manager::list_folders());
+        let tag_options = with_any(monster_manager::list_tags(monsters));
+        let resistance_options = with_any(monster_manager::list_resistance_types(monsters));
+        let type_options = with_any(monster_manager::list_creature_types(monsters));
+        let sort_options: Vec<&str> = monster_manager::MonsterSort::ALL.iter().map(|s| s.label()).collect();
+
+        let folder_dropdown = create_string_dropdown(&folder_options);
+        let tag_dropdown = create_string_dropdown(&tag_options);
+        let resistance_dropdown = create_string_dropdown(&resistance_options);
+        let type_dropdown = create_string_dropdown(&type_options);
+        let sort_dropdown = UiFactory::create_dropdown(&sort_options, Some(0), None);
+
+        let choice_row = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+        choice_row.append(&UiFactory::create_label("Folder:", Align::Start, false, &[]));
+        choice_row.append(&folder_dropdown);
+        choice_row.append(&UiFactory::create_label("Tag:", Align::Start, false, &[]));
+        choice_row.append(&tag_dropdown);
+        choice_row.append(&UiFactory::create_label("Resists:", Align::Start, false, &[]));
+        choice_row.append(&resistance_dropdown);
+        choice_row.append(&UiFactory::create_label("Type:", Align::Start, false, &[]));
+        choice_row.append(&type_dropdown);
+        container.append(&choice_row);
+
+        let exp_bounds = (0, monsters.iter().map(|m| m.exp).max().unwrap_or(0).max(0));
+        let ac_bounds = (0, monsters.iter().map(|m| m.ac).max().unwrap_or(0).max(0));
+        let create_range_spin = |bounds: (i32, i32), value: i32| {
+            UiFactory::create_spin_button(bounds.0 as f64, bounds.1 as f64, 1.0, value as f64)
+        };
+        let min_exp = create_range_spin(exp_bounds, exp_bounds.0);
+        let max_exp = create_range_spin(exp_bounds, exp_bounds.1);
+        let min_ac = create_range_spin(ac_bounds, ac_bounds.0);
+        let max_ac = create_range_spin(ac_bounds, ac_bounds.1);
+        let cr_options = with_any(monster_manager::CR_TABLE.iter().map(|(rating, _, _)| rating.to_string()).collect());
+        let min_cr = create_string_dropdown(&cr_options);
+        let max_cr = create_string_dropdown(&cr_options);
+
+        let range_row = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+        range_row.append(&UiFactory::create_label("XP:", Align::Start, false, &[]));
+        range_row.append(&min_exp);
+        range_row.append(&UiFactory::create_label("to", Align::Start, false, &[]));
+        range_row.append(&max_exp);
+        range_row.append(&UiFactory::create_label("CR:", Align::Start, false, &[]));
+        range_row.append(&min_cr);
+        range_row.append(&UiFactory::create_label("to", Align::Start, false, &[]));
+        range_row.append(&max_cr);
+        range_row.append(&UiFactory::create_label("AC:", Align::Start, false, &[]));
+        range_row.append(&min_ac);
+        range_row.append(&UiFactory::create_label("to", Align::Start, false, &[]));
+        range_row.append(&max_ac);
+        range_row.append(&UiFactory::create_label("Sort:", Align::Start, false, &[]));
+        range_row.append(&sort_dropdown);
+        container.append(&range_row);
+
+        MonsterFilterBar {
+            container,
+            search_entry,
+            folder_dropdown,
+            tag_dropdown,
+            resistance_dropdown,
+            type_dropdown,
+            min_exp,
+            max_exp,
+            min_cr,
+            max_cr,
+            min_ac,
+            max_ac,
+            sort_dropdown,
+            exp_bounds,
+            ac_bounds,
+        }
+    }
+
+    /// Reads the current criteria out of the controls.
+    pub fn filter(&self) -> monster_manager::MonsterFilter {
+        let choice = |dropdown: &gtk::DropDown| {
+            Some(UiFactory::get_dropdown_text(dropdown)).filter(|text| text != ANY_FILTER_LABEL)
+        };
+        let range = |min: &gtk::SpinButton, max: &gtk::SpinButton, bounds: (i32, i32)| {
+            (
+                Some(min.value() as i32).filter(|v| *v != bounds.0),
+                Some(max.value() as i32).filter(|v| *v != bounds.1),
+            )
+        };
+
+        monster_manager::MonsterFilter {
+            search: self.search_entry.text().to_string(),
+            folder: choice(&self.folder_dropdown),
+            tag: choice(&self.tag_dropdown),
+            resistance: choice(&self.resistance_dropdown),
+            creature_type: choice(&self.type_dropdown),
+            exp_range: range(&self.min_exp, &self.max_exp, self.exp_bounds),
+            cr_range: (
+                choice(&self.min_cr).and_then(|cr| monster_manager::cr_value(&cr)),
+                choice(&self.max_cr).and_then(|cr| monster_manager::cr_value(&cr)),
+            ),
+            ac_range: range(&self.min_ac, &self.max_ac, self.ac_bounds),
+        }
+    }
+
+    /// The selected sort order.
+    pub fn sort(&self) -> monster_manager::MonsterSort {
+        monster_manager::MonsterSort::ALL
+            .get(self.sort_dropdown.selected() as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Calls `on_change` whenever any of the controls changes.
+    pub fn connect_changed<F: Fn() + 'static>(&self, on_change: F) {
+        let on_change = Rc::new(on_change);
+
+        let callback = Rc::clone(&on_change);
+        self.search_entry.connect_search_changed(move |_| callback());
+        for dropdown in [
+            &self.folder_dropdown,
+            &self.tag_dropdown,
+            &self.resistance_dropdown,
+            &self.type_dropdown,
+            &self.min_cr,
+            &self.max_cr,
+            &self.sort_dropdown,
+        ] {
+            let callback = Rc::clone(&on_change);
+            dropdown.connect_selected_notify(move |_| callback());
+        }
+        for spin_button in [&self.min_exp, &self.max_exp, &self.min_ac, &self.max_ac] {
+            let callback = Rc::clone(&on_change);
+            spin_button.connect_value_changed(move |_| callback());
+        }
+    }
+}
+
+/// Builds a dropdown from owned strings, selecting the first entry.
+fn create_string_dropdown(options: &[String]) -> gtk::DropDown {
+    let option_refs: Vec<&str> = options.iter().map(|o| o.as_str()).collect();
+    UiFactory::create_dropdown(&option_refs, Some(0), None)
+}
+
 // =========================================================================
 // Attack Creation / Removal Forms
 // =========================================================================
 
 /// Displays the form used to create attacks
-pub fn show_attack_creation_menu(app: &AdwApplication, parent_window: &AdwWindow, monster_name: &str) {
+pub fn show_attack_creation_menu(app: &AdwApplication, parent_window: &AdwWindow, monster: &Monster) {
     let window = AdwWindow::builder()
         .application(app)
-        .title(&format!("Add Attack to {}", monster_name))
+        .title(format!("Add Attack to {}", monster.name))
         .transient_for(parent_window)
         .default_width(400)
         .default_height(350)
@@ -772,7 +1666,7 @@ pub fn show_attack_creation_menu(app: &AdwApplication, parent_window: &AdwWindow
 
     let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
     let title_label = UiFactory::create_label(
-        &format!("Add Attack to {}", monster_name),
+        &format!("Add Attack to {}", monster.name),
         Align::Center,
         false,
         &["title-1"]
@@ -802,6 +1696,41 @@ pub fn show_attack_creation_menu(app: &AdwApplication, parent_window: &AdwWindow
     );
     let (saving_throw_label, saving_throw_checkbox) =
         UiFactory::create_label_checkbox_pair("Is this a saving throw?");
+    let (save_ability_label, save_ability_dropdown) = UiFactory::create_label_dropdown_pair(
+        "Targets Save With:",
+        &monster_manager::ABILITY_KEYS
+    );
+    save_ability_dropdown.set_selected(1);
+    let on_save_options: Vec<&str> = monster_manager::SaveSuccess::ALL.iter().map(|s| s.label()).collect();
+    let (on_save_label, on_save_dropdown) = UiFactory::create_label_dropdown_pair(
+        "On a Successful Save:",
+        &on_save_options
+    );
+    let (add_mod_label, add_mod_checkbox) =
+        UiFactory::create_label_checkbox_pair("Add Ability Mod to Damage?");
+    save_ability_dropdown.set_sensitive(false);
+    on_save_dropdown.set_sensitive(false);
+    add_mod_checkbox.set_sensitive(false);
+    let save_ability_dropdown_clone = save_ability_dropdown.clone();
+    let on_save_dropdown_clone = on_save_dropdown.clone();
+    let add_mod_checkbox_clone = add_mod_checkbox.clone();
+    saving_throw_checkbox.connect_toggled(move |check| {
+        save_ability_dropdown_clone.set_sensitive(check.is_active());
+        on_save_dropdown_clone.set_sensitive(check.is_active());
+        add_mod_checkbox_clone.set_sensitive(check.is_active());
+    });
+    let registered_damage_types = monster_manager::damage_types();
+    let mut damage_type_options = vec![UNSPECIFIED_DAMAGE_TYPE];
+    damage_type_options.extend(registered_damage_types.iter().map(String::as_str));
+    let (damage_type_label, damage_type_dropdown) = UiFactory::create_label_dropdown_pair(
+        "Damage Type:",
+        &damage_type_options
+    );
+    let category_options: Vec<&str> = monster_manager::ActionCategory::ALL.iter().map(|c| c.label()).collect();
+    let (category_label, category_dropdown) = UiFactory::create_label_dropdown_pair(
+        "Used As:",
+        &category_options
+    );
 
     input_grid.attach(&attack_name_label, 0, 0, 1, 1);
     input_grid.attach_next_to(
@@ -851,6 +1780,114 @@ pub fn show_attack_creation_menu(app: &AdwApplication, parent_window: &AdwWindow
         1,
         1
     );
+    input_grid.attach(&save_ability_label, 0, 6, 1, 1);
+    input_grid.attach_next_to(
+        &save_ability_dropdown,
+        Some(&save_ability_label),
+        gtk::PositionType::Right,
+        1,
+        1
+    );
+    input_grid.attach(&on_save_label, 0, 7, 1, 1);
+    input_grid.attach_next_to(
+        &on_save_dropdown,
+        Some(&on_save_label),
+        gtk::PositionType::Right,
+        1,
+        1
+    );
+    input_grid.attach(&add_mod_label, 0, 8, 1, 1);
+    input_grid.attach_next_to(
+        &add_mod_checkbox,
+        Some(&add_mod_label),
+        gtk::PositionType::Right,
+        1,
+        1
+    );
+    input_grid.attach(&damage_type_label, 0, 9, 1, 1);
+    input_grid.attach_next_to(
+        &damage_type_dropdown,
+        Some(&damage_type_label),
+        gtk::PositionType::Right,
+        1,
+        1
+    );
+    input_grid.attach(&category_label, 0, 10, 1, 1);
+    input_grid.attach_next_to(
+        &category_dropdown,
+        Some(&category_label),
+        gtk::PositionType::Right,
+        1,
+        1
+    );
+
+    // --- Range and Area ---
+    let reach_spin = UiFactory::create_spin_button(0.0, 100.0, 5.0, 0.0);
+    let range_spin = UiFactory::create_spin_button(0.0, 1000.0, 5.0, 0.0);
+    let long_range_spin = UiFactory::create_spin_button(0.0, 2000.0, 5.0, 0.0);
+    let mut area_options = vec![UNSET_LABEL];
+    area_options.extend(monster_manager::AreaShape::ALL.iter().map(|s| s.label()));
+    let area_dropdown = UiFactory::create_dropdown(&area_options, Some(0), None);
+    let area_size_spin = UiFactory::create_spin_button(0.0, 500.0, 5.0, 0.0);
+    area_size_spin.set_sensitive(false);
+    let material_options: Vec<&str> = monster_manager::WeaponMaterial::ALL.iter().map(|m| m.label()).collect();
+    let material_dropdown = UiFactory::create_dropdown(&material_options, Some(0), None);
+    material_dropdown.set_tooltip_text(Some("What the attack counts as against resistances to nonmagical attacks"));
+    let area_size_spin_clone = area_size_spin.clone();
+    area_dropdown.connect_selected_notify(move |dropdown| {
+        area_size_spin_clone.set_sensitive(dropdown.selected() > 0);
+    });
+
+    for (i, (label_text, widget)) in [
+        ("Reach (ft.):", reach_spin.clone().upcast::<gtk::Widget>()),
+        ("Range (ft.):", range_spin.clone().upcast()),
+        ("Long Range (ft.):", long_range_spin.clone().upcast()),
+        ("Area:", area_dropdown.clone().upcast()),
+        ("Area Size (ft.):", area_size_spin.clone().upcast()),
+        ("Counts As:", material_dropdown.clone().upcast()),
+    ].iter().enumerate() {
+        let label = UiFactory::create_label(label_text, Align::Start, false, &[]);
+        input_grid.attach(&label, 0, 11 + i as i32, 1, 1);
+        input_grid.attach(widget, 1, 11 + i as i32, 1, 1);
+    }
+
+    // --- On-Hit Rider ---
+    let (rider_label, rider_checkbox) =
+        UiFactory::create_label_checkbox_pair("On a hit, the target makes a save?");
+    input_grid.attach(&rider_label, 0, 17, 1, 1);
+    input_grid.attach_next_to(&rider_checkbox, Some(&rider_label), gtk::PositionType::Right, 1, 1);
+
+    let rider_grid = UiFactory::create_grid(6, 12, Align::Center);
+    let rider_save_dropdown = UiFactory::create_dropdown(&monster_manager::ABILITY_KEYS, Some(2), None);
+    let rider_dc_spin = UiFactory::create_spin_button(1.0, 30.0, 1.0, 13.0);
+    let mut condition_options = vec![UNSET_LABEL];
+    condition_options.extend(monster_manager::CONDITIONS);
+    let rider_condition_dropdown = UiFactory::create_dropdown(&condition_options, Some(0), None);
+    let rider_damage_entry = UiFactory::create_entry(None, Some("e.g., 2d6"), 8);
+    let rider_damage_type_dropdown = UiFactory::create_dropdown(&damage_type_options, Some(0), None);
+    let rider_half_checkbox = UiFactory::create_check_button(false);
+    let rider_duration_entry = UiFactory::create_entry(None, Some("e.g., 1 minute"), 12);
+
+    for (i, (label_text, widget)) in [
+        ("Save:", rider_save_dropdown.clone().upcast::<gtk::Widget>()),
+        ("DC:", rider_dc_spin.clone().upcast()),
+        ("Condition on a Fail:", rider_condition_dropdown.clone().upcast()),
+        ("Extra Damage:", rider_damage_entry.clone().upcast()),
+        ("Extra Damage Type:", rider_damage_type_dropdown.clone().upcast()),
+        ("Half on a Success:", rider_half_checkbox.clone().upcast()),
+        ("Duration:", rider_duration_entry.clone().upcast()),
+    ].iter().enumerate() {
+        let label = UiFactory::create_label(label_text, Align::Start, false, &[]);
+        let (row, column) = ((i / 2) as i32, (i % 2) as i32 * 2);
+        rider_grid.attach(&label, column, row, 1, 1);
+        rider_grid.attach(widget, column + 1, row, 1, 1);
+    }
+    rider_grid.set_sensitive(false);
+
+    let rider_grid_clone = rider_grid.clone();
+    rider_checkbox.connect_toggled(move |check| {
+        rider_grid_clone.set_sensitive(check.is_active());
+    });
 
     let error_label = UiFactory::create_label("", Align::Center, false, &[]);
 
@@ -863,13 +1900,14 @@ pub fn show_attack_creation_menu(app: &AdwApplication, parent_window: &AdwWindow
 
     main_vbox.append(&title_label);
     main_vbox.append(&input_grid);
+    main_vbox.append(&rider_grid);
     main_vbox.append(&error_label);
     main_vbox.append(&button_box);
 
     let window_clone = window.clone();
     let parent_window_clone = parent_window.clone();
     let app_clone = app.clone();
-    let monster_name_clone = monster_name.to_string();
+    let monster_id_clone = monster.id.clone();
     let error_label_clone = error_label.clone();
     let attack_name_entry_clone = attack_name_entry.clone();
     let ability_dropdown_clone = ability_dropdown.clone();
@@ -877,6 +1915,8 @@ pub fn show_attack_creation_menu(app: &AdwApplication, parent_window: &AdwWindow
     let num_dice_entry_clone = num_dice_entry.clone();
     let num_attacks_entry_clone = num_attacks_entry.clone();
     let saving_throw_checkbox_clone = saving_throw_checkbox.clone();
+    let damage_type_dropdown_clone = damage_type_dropdown.clone();
+    let category_dropdown_clone = category_dropdown.clone();
 
     save_button.connect_clicked(move |_| {
         error_label_clone.set_text("");
@@ -911,6 +1951,65 @@ pub fn show_attack_creation_menu(app: &AdwApplication, parent_window: &AdwWindow
         }
 
         let saving_throw = saving_throw_checkbox_clone.is_active();
+        let (save_ability, on_save) = if saving_throw {
+            (
+                UiFactory::get_dropdown_text(&save_ability_dropdown),
+                monster_manager::SaveSuccess::ALL
+                    .get(on_save_dropdown.selected() as usize)
+                    .copied()
+                    .unwrap_or_default(),
+            )
+        } else {
+            (String::new(), monster_manager::SaveSuccess::default())
+        };
+        let damage_type = match UiFactory::get_dropdown_text(&damage_type_dropdown_clone) {
+            t if t == UNSPECIFIED_DAMAGE_TYPE => String::new(),
+            t => t,
+        };
+        let category = monster_manager::ActionCategory::ALL
+            .get(category_dropdown_clone.selected() as usize)
+            .copied()
+            .unwrap_or_default();
+
+        if long_range_spin.value() > 0.0 && long_range_spin.value() < range_spin.value() {
+            error_label_clone.set_text("Long Range can't be shorter than Range.");
+            return;
+        }
+        let area = match area_dropdown.selected() {
+            0 => None,
+            n => {
+                if area_size_spin.value() <= 0.0 {
+                    error_label_clone.set_text("Give the area a size.");
+                    return;
+                }
+                Some(monster_manager::AreaTemplate {
+                    shape: monster_manager::AreaShape::ALL[n as usize - 1],
+                    size: area_size_spin.value() as i32,
+                })
+            }
+        };
+
+        let rider = if rider_checkbox.is_active() {
+            let extra_damage = rider_damage_entry.text().trim().to_lowercase();
+            if !extra_damage.is_empty() && monster_manager::parse_dice_expression(&extra_damage).is_none() {
+                error_label_clone.set_text("Extra Damage must be a dice expression such as 2d6.");
+                return;
+            }
+            let unset_to_empty = |text: String| {
+                if text == UNSET_LABEL || text == UNSPECIFIED_DAMAGE_TYPE { String::new() } else { text }
+            };
+            Some(monster_manager::AttackRider {
+                save_ability: UiFactory::get_dropdown_text(&rider_save_dropdown),
+                dc: rider_dc_spin.value() as i32,
+                condition: unset_to_empty(UiFactory::get_dropdown_text(&rider_condition_dropdown)),
+                extra_damage,
+                damage_type: unset_to_empty(UiFactory::get_dropdown_text(&rider_damage_type_dropdown)),
+                half_on_success: rider_half_checkbox.is_active(),
+                duration: rider_duration_entry.text().trim().to_string(),
+            })
+        } else {
+            None
+        };
 
         let new_attack = monster_manager::Attack {
             attack_name,
@@ -919,9 +2018,23 @@ pub fn show_attack_creation_menu(app: &AdwApplication, parent_window: &AdwWindow
             num_dice,
             num_attacks,
             saving_throw,
+            save_ability,
+            on_save,
+            add_mod_to_damage: saving_throw && add_mod_checkbox.is_active(),
+            damage_type,
+            material: monster_manager::WeaponMaterial::ALL
+                .get(material_dropdown.selected() as usize)
+                .copied()
+                .unwrap_or_default(),
+            category,
+            reach: reach_spin.value() as i32,
+            range: range_spin.value() as i32,
+            long_range: long_range_spin.value() as i32,
+            area,
+            rider,
         };
 
-        if let Err(e) = monster_manager::add_attack_to_monster(&monster_name_clone, new_attack) {
+        if let Err(e) = monster_manager::add_attack_to_monster(&monster_id_clone, new_attack) {
             error_label_clone.set_text(&format!("Failed to save attack: {}", e));
             return;
         }
@@ -940,7 +2053,7 @@ pub fn show_attack_creation_menu(app: &AdwApplication, parent_window: &AdwWindow
 }
 
 /// Displays the form used to remove attacks
-fn show_remove_attack_menu(app: &AdwApplication, parent_window: &AdwWindow, monster_name: &str) {
+fn show_remove_attack_menu(app: &AdwApplication, parent_window: &AdwWindow, monster_id: &str) {
     let window = AdwWindow::builder()
         .application(app)
         .title("Remove Attack")
@@ -966,7 +2079,7 @@ fn show_remove_attack_menu(app: &AdwApplication, parent_window: &AdwWindow, mons
     let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
     list_box.add_css_class("boxed-list");
 
-    let monster_data = monster_manager::read_monster(monster_name);
+    let monster_data = monster_manager::read_monster(monster_id);
     if let Some(monster) = monster_data {
         if monster.attacks.is_empty() {
             list_box.append(&Label::new(Some("This monster has no attacks to remove.")));
@@ -974,7 +2087,7 @@ fn show_remove_attack_menu(app: &AdwApplication, parent_window: &AdwWindow, mons
             for attack in &monster.attacks {
                 let row = create_remove_attack_row(
                     attack,
-                    &monster.name,
+                    &monster.id,
                     window.clone(),
                     parent_window.clone(),
                     app.clone()
@@ -1001,7 +2114,7 @@ fn show_remove_attack_menu(app: &AdwApplication, parent_window: &AdwWindow, mons
 }
 
 /// Helper function for the attack removal display
-fn create_remove_attack_row(attack: &monster_manager::Attack, monster_name: &str, modal_window: AdwWindow, parent_window: AdwWindow, app: AdwApplication) -> Box {
+fn create_remove_attack_row(attack: &monster_manager::Attack, monster_id: &str, modal_window: AdwWindow, parent_window: AdwWindow, app: AdwApplication) -> Box {
     let hbox = UiFactory::create_box(Orientation::Horizontal, 12, (6, 6, 12, 12));
     let attack_name = UiFactory::create_label(&attack.attack_name, Align::Start, false, &[]);
     attack_name.set_hexpand(true);
@@ -1013,12 +2126,12 @@ fn create_remove_attack_row(attack: &monster_manager::Attack, monster_name: &str
     );
 
     let attack_name_clone = attack.attack_name.clone();
-    let monster_name_clone = monster_name.to_string();
+    let monster_id_clone = monster_id.to_string();
 
     remove_button.connect_clicked(move |_| {
         if
             let Err(e) = monster_manager::delete_attack_from_monster(
-                &monster_name_clone,
+                &monster_id_clone,
                 &attack_name_clone
             )
         {
@@ -1033,3 +2146,497 @@ fn create_remove_attack_row(attack: &monster_manager::Attack, monster_name: &str
     hbox.append(&remove_button);
     hbox
 }
+
+// =========================================================================
+// Multiattack Form
+// =========================================================================
+
+/// Displays the form used to build a monster's multiattack routines from its attacks.
+fn show_multiattack_menu(app: &AdwApplication, parent_window: &AdwWindow, monster: &Monster) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title(format!("Multiattack for {}", monster.name))
+        .transient_for(parent_window)
+        .default_width(550)
+        .default_height(500)
+        .modal(true)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let title_label = UiFactory::create_label(
+        &format!("Multiattack for {}", monster.name),
+        Align::Center,
+        false,
+        &["title-1"]
+    );
+    main_vbox.append(&title_label);
+
+    if monster.attacks.is_empty() {
+        let empty_label = UiFactory::create_label(
+            "Add some attacks to this monster before building a multiattack.",
+            Align::Center,
+            false,
+            &[]
+        );
+        let close_button = UiFactory::create_button("Close", Align::End, None);
+        let window_clone = window.clone();
+        close_button.connect_clicked(move |_| window_clone.close());
+        main_vbox.append(&empty_label);
+        main_vbox.append(&close_button);
+        window.set_child(Some(&main_vbox));
+        window.present();
+        return;
+    }
+
+    // --- Routine List ---
+    let routines = Rc::new(RefCell::new(monster.multiattacks.clone()));
+    let routines_title = UiFactory::create_label("Routines", Align::Start, false, &["title-3"]);
+    let routine_list = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
+    routine_list.add_css_class("boxed-list");
+    fill_multiattack_list(&routine_list, &routines);
+    let scrolled_window = UiFactory::create_scrolled_window(true, true, Some(150));
+    scrolled_window.set_child(Some(&routine_list));
+
+    // --- New Routine Inputs ---
+    let steps: Rc<RefCell<Vec<monster_manager::MultiattackStep>>> = Rc::new(RefCell::new(Vec::new()));
+    let attack_names: Vec<&str> = monster.attacks.iter().map(|a| a.attack_name.as_str()).collect();
+
+    let routine_grid = UiFactory::create_grid(6, 12, Align::Center);
+    let (name_label, name_entry) = UiFactory::create_label_entry_pair(
+        "Routine Name:",
+        "e.g., Multiattack"
+    );
+    let attack_label = UiFactory::create_label("Attack:", Align::Start, false, &[]);
+    let attack_dropdown = UiFactory::create_dropdown(&attack_names, Some(0), None);
+    let count_label = UiFactory::create_label("Times:", Align::Start, false, &[]);
+    let count_spin = UiFactory::create_spin_button(1.0, 10.0, 1.0, 1.0);
+    let add_step_button = UiFactory::create_button("Add Step", Align::Center, None);
+    let steps_label = UiFactory::create_label("No steps yet.", Align::Start, false, &[]);
+    let clear_steps_button = UiFactory::create_button("Clear Steps", Align::Center, None);
+    let add_routine_button = UiFactory::create_button("Add Routine", Align::Center, Some("suggested-action"));
+
+    routine_grid.attach(&name_label, 0, 0, 1, 1);
+    routine_grid.attach(&name_entry, 1, 0, 3, 1);
+    routine_grid.attach(&attack_label, 0, 1, 1, 1);
+    routine_grid.attach(&attack_dropdown, 1, 1, 1, 1);
+    routine_grid.attach(&count_label, 2, 1, 1, 1);
+    routine_grid.attach(&count_spin, 3, 1, 1, 1);
+    routine_grid.attach(&add_step_button, 4, 1, 1, 1);
+    routine_grid.attach(&steps_label, 0, 2, 4, 1);
+    routine_grid.attach(&clear_steps_button, 4, 2, 1, 1);
+    routine_grid.attach(&add_routine_button, 4, 3, 1, 1);
+
+    let error_label = UiFactory::create_label("", Align::Center, false, &[]);
+
+    let describe_steps = |steps: &[monster_manager::MultiattackStep]| {
+        if steps.is_empty() {
+            "No steps yet.".to_string()
+        } else {
+            monster_manager::Multiattack { name: String::new(), steps: steps.to_vec() }.describe_steps()
+        }
+    };
+
+    let steps_clone = Rc::clone(&steps);
+    let steps_label_clone = steps_label.clone();
+    add_step_button.connect_clicked(move |_| {
+        let attack_name = UiFactory::get_dropdown_text(&attack_dropdown);
+        let count = count_spin.value() as i32;
+        {
+            let mut steps = steps_clone.borrow_mut();
+            // Adding the same attack twice in a row just raises its count
+            match steps.last_mut() {
+                Some(last) if last.attack_name == attack_name => last.count += count,
+                _ => steps.push(monster_manager::MultiattackStep { attack_name, count }),
+            }
+        }
+        steps_label_clone.set_text(&describe_steps(&steps_clone.borrow()));
+    });
+
+    let steps_clone = Rc::clone(&steps);
+    let steps_label_clone = steps_label.clone();
+    clear_steps_button.connect_clicked(move |_| {
+        steps_clone.borrow_mut().clear();
+        steps_label_clone.set_text(&describe_steps(&[]));
+    });
+
+    let routines_clone = Rc::clone(&routines);
+    let routine_list_clone = routine_list.clone();
+    let error_label_clone = error_label.clone();
+    add_routine_button.connect_clicked(move |_| {
+        error_label_clone.set_text("");
+
+        let name = name_entry.text().trim().to_string();
+        if name.is_empty() {
+            error_label_clone.set_text("The routine needs a name.");
+            return;
+        }
+        if steps.borrow().is_empty() {
+            error_label_clone.set_text("Add at least one attack to the routine.");
+            return;
+        }
+        if routines_clone.borrow().iter().any(|r| r.name.eq_ignore_ascii_case(&name)) {
+            error_label_clone.set_text("A routine with this name is already listed.");
+            return;
+        }
+
+        routines_clone.borrow_mut().push(monster_manager::Multiattack {
+            name,
+            steps: steps.borrow_mut().drain(..).collect(),
+        });
+        fill_multiattack_list(&routine_list_clone, &routines_clone);
+        name_entry.set_text("");
+        steps_label.set_text(&describe_steps(&[]));
+    });
+
+    // --- Save / Cancel ---
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let save_button = Button::with_label("Save Multiattack");
+    let cancel_button = Button::with_label("Cancel");
+    button_box.append(&save_button);
+    button_box.append(&cancel_button);
+
+    main_vbox.append(&routines_title);
+    main_vbox.append(&scrolled_window);
+    main_vbox.append(&routine_grid);
+    main_vbox.append(&error_label);
+    main_vbox.append(&button_box);
+
+    let window_clone = window.clone();
+    let parent_window_clone = parent_window.clone();
+    let app_clone = app.clone();
+    let monster_id_clone = monster.id.clone();
+    let error_label_clone = error_label.clone();
+    save_button.connect_clicked(move |_| {
+        if let Err(e) = monster_manager::set_multiattacks(&monster_id_clone, routines.borrow().clone()) {
+            error_label_clone.set_text(&format!("Failed to save multiattack: {}", e));
+            return;
+        }
+
+        window_clone.close();
+        switch_to_monster_list(&app_clone, &parent_window_clone);
+    });
+
+    let window_clone_cancel = window.clone();
+    cancel_button.connect_clicked(move |_| {
+        window_clone_cancel.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// Rebuilds the routine rows of the multiattack form, each with a remove button.
+fn fill_multiattack_list(list_box: &ListBox, routines: &Rc<RefCell<Vec<monster_manager::Multiattack>>>) {
+    while let Some(child) = list_box.first_child() {
+        list_box.remove(&child);
+    }
+    if routines.borrow().is_empty() {
+        list_box.append(&Label::new(Some("No routines yet.")));
+        return;
+    }
+
+    for (index, routine) in routines.borrow().iter().enumerate() {
+        let hbox = UiFactory::create_box(Orientation::Horizontal, 12, (6, 6, 12, 12));
+        let routine_label = UiFactory::create_label(
+            &format!("{}: {}", routine.name, routine.describe_steps()),
+            Align::Start,
+            false,
+            &[]
+        );
+        routine_label.set_hexpand(true);
+        let remove_button = UiFactory::create_button("Remove", Align::Center, Some("destructive-action"));
+
+        let list_box_clone = list_box.clone();
+        let routines_clone = Rc::clone(routines);
+        remove_button.connect_clicked(move |_| {
+            routines_clone.borrow_mut().remove(index);
+            fill_multiattack_list(&list_box_clone, &routines_clone);
+        });
+
+        hbox.append(&routine_label);
+        hbox.append(&remove_button);
+        list_box.append(&hbox);
+    }
+}
+
+// =========================================================================
+// Spellcasting Form
+// =========================================================================
+
+/// Spell roll dropdown entries; everything after "Spell attack" is a save in `ABILITY_KEYS` order.
+const SPELL_ROLL_OPTIONS: [&str; 8] =
+    ["No roll", "Spell attack", "Str save", "Dex save", "Con save", "Int save", "Wis save", "Cha save"];
+
+/// Usage dropdown entries, in `SpellUsage` order.
+const SPELL_USAGE_OPTIONS: [&str; 3] = ["Slot", "At will", "Per day"];
+
+/// Spellcasting ability dropdown entry for monsters that don't cast spells.
+const NO_SPELLCASTING_LABEL: &str = "None";
+
+/// Displays the form used to edit a monster's spellcasting ability, slots and spells.
+fn show_spellcasting_menu(app: &AdwApplication, parent_window: &AdwWindow, monster: &Monster) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title(format!("Spellcasting for {}", monster.name))
+        .transient_for(parent_window)
+        .default_width(600)
+        .default_height(550)
+        .modal(true)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let title_label = UiFactory::create_label(
+        &format!("Spellcasting for {}", monster.name),
+        Align::Center,
+        false,
+        &["title-1"]
+    );
+
+    let spellcasting = monster.spellcasting.clone().unwrap_or_default();
+
+    // --- Ability, DC and Slots ---
+    let mut ability_options = vec![NO_SPELLCASTING_LABEL];
+    ability_options.extend(monster_manager::ABILITY_KEYS);
+    let ability_idx = ability_options
+        .iter()
+        .position(|a| monster.spellcasting.is_some() && *a == spellcasting.ability)
+        .unwrap_or(0) as u32;
+    let (ability_label, ability_dropdown) = UiFactory::create_label_dropdown_pair(
+        "Spellcasting Ability:",
+        &ability_options
+    );
+    ability_dropdown.set_selected(ability_idx);
+    let dc_label = UiFactory::create_label("", Align::Start, false, &[]);
+
+    let ability_grid = UiFactory::create_grid(12, 12, Align::Center);
+    ability_grid.attach(&ability_label, 0, 0, 1, 1);
+    ability_grid.attach(&ability_dropdown, 1, 0, 1, 1);
+    ability_grid.attach(&dc_label, 2, 0, 1, 1);
+
+    let update_dc_label = {
+        let monster = monster.clone();
+        let dc_label = dc_label.clone();
+        move |dropdown: &gtk::DropDown| {
+            let ability = UiFactory::get_dropdown_text(dropdown);
+            let check = Monster {
+                spellcasting: Some(monster_manager::Spellcasting { ability, ..Default::default() }),
+                ..monster.clone()
+            };
+            let text = match (check.spell_save_dc(), check.spell_attack_bonus()) {
+                (Some(dc), Some(bonus)) => format!("Spell save DC {}, {:+} to hit", dc, bonus),
+                _ => String::new(),
+            };
+            dc_label.set_text(&text);
+        }
+    };
+    update_dc_label(&ability_dropdown);
+    ability_dropdown.connect_selected_notify(update_dc_label);
+
+    let slots_grid = UiFactory::create_grid(6, 12, Align::Center);
+    let mut slot_spins = Vec::new();
+    for (i, &slots) in spellcasting.slots.iter().enumerate() {
+        let label = UiFactory::create_label(
+            &format!("{}:", monster_manager::spell_level_label(i as i32 + 1)),
+            Align::Start,
+            false,
+            &[]
+        );
+        let spin = UiFactory::create_spin_button(0.0, 9.0, 1.0, slots as f64);
+        let (row, column) = ((i / 5) as i32, (i % 5) as i32 * 2);
+        slots_grid.attach(&label, column, row, 1, 1);
+        slots_grid.attach(&spin, column + 1, row, 1, 1);
+        slot_spins.push(spin);
+    }
+    let slots_title = UiFactory::create_label("Spell Slots", Align::Start, false, &["title-3"]);
+
+    // --- Spell List ---
+    let spells = Rc::new(RefCell::new(spellcasting.spells.clone()));
+    let spells_title = UiFactory::create_label("Spells", Align::Start, false, &["title-3"]);
+    let spell_list = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
+    spell_list.add_css_class("boxed-list");
+    fill_spell_list(&spell_list, &spells);
+    let scrolled_window = UiFactory::create_scrolled_window(true, true, Some(150));
+    scrolled_window.set_child(Some(&spell_list));
+
+    // --- New Spell Inputs ---
+    let spell_grid = UiFactory::create_grid(6, 12, Align::Center);
+    let spell_name_entry = UiFactory::create_entry(None, Some("e.g., Fireball"), 15);
+    let level_spin = UiFactory::create_spin_button(0.0, 9.0, 1.0, 1.0);
+    let usage_dropdown = UiFactory::create_dropdown(&SPELL_USAGE_OPTIONS, Some(0), None);
+    let per_day_spin = UiFactory::create_spin_button(1.0, 9.0, 1.0, 1.0);
+    let roll_dropdown = UiFactory::create_dropdown(&SPELL_ROLL_OPTIONS, Some(0), None);
+    let damage_entry = UiFactory::create_entry(None, Some("e.g., 8d6"), 8);
+    let registered_damage_types = monster_manager::damage_types();
+    let mut damage_type_options = vec![UNSPECIFIED_DAMAGE_TYPE];
+    damage_type_options.extend(registered_damage_types.iter().map(String::as_str));
+    let damage_type_dropdown = UiFactory::create_dropdown(&damage_type_options, Some(0), None);
+    let add_spell_button = UiFactory::create_button("Add Spell", Align::Center, None);
+
+    for (i, (label_text, widget)) in [
+        ("Name:", spell_name_entry.clone().upcast::<gtk::Widget>()),
+        ("Level (0 = cantrip):", level_spin.clone().upcast()),
+        ("Uses:", usage_dropdown.clone().upcast()),
+        ("Per Day:", per_day_spin.clone().upcast()),
+        ("Roll:", roll_dropdown.clone().upcast()),
+        ("Damage:", damage_entry.clone().upcast()),
+        ("Damage Type:", damage_type_dropdown.clone().upcast()),
+    ].iter().enumerate() {
+        let label = UiFactory::create_label(label_text, Align::Start, false, &[]);
+        let (row, column) = ((i / 2) as i32, (i % 2) as i32 * 2);
+        spell_grid.attach(&label, column, row, 1, 1);
+        spell_grid.attach(widget, column + 1, row, 1, 1);
+    }
+    spell_grid.attach(&add_spell_button, 3, 3, 1, 1);
+
+    let per_day_spin_clone = per_day_spin.clone();
+    usage_dropdown.connect_selected_notify(move |dropdown| {
+        per_day_spin_clone.set_sensitive(dropdown.selected() == 2);
+    });
+    per_day_spin.set_sensitive(false);
+
+    let error_label = UiFactory::create_label("", Align::Center, false, &[]);
+
+    let spells_clone = Rc::clone(&spells);
+    let spell_list_clone = spell_list.clone();
+    let error_label_clone = error_label.clone();
+    let spell_name_entry_clone = spell_name_entry.clone();
+    let damage_entry_clone = damage_entry.clone();
+    add_spell_button.connect_clicked(move |_| {
+        error_label_clone.set_text("");
+
+        let name = spell_name_entry_clone.text().trim().to_string();
+        if name.is_empty() {
+            error_label_clone.set_text("The spell needs a name.");
+            return;
+        }
+        if spells_clone.borrow().iter().any(|s| s.name.eq_ignore_ascii_case(&name)) {
+            error_label_clone.set_text("A spell with this name is already listed.");
+            return;
+        }
+        let damage = damage_entry_clone.text().trim().to_string();
+        if !damage.is_empty() && monster_manager::parse_dice_expression(&damage).is_none() {
+            error_label_clone.set_text("Damage must be a dice expression such as 8d6 or 1d10 + 3.");
+            return;
+        }
+
+        let usage = match usage_dropdown.selected() {
+            1 => monster_manager::SpellUsage::AtWill,
+            2 => monster_manager::SpellUsage::PerDay(per_day_spin.value() as i32),
+            _ => monster_manager::SpellUsage::Slot,
+        };
+        let roll = match roll_dropdown.selected() {
+            0 => monster_manager::SpellRoll::None,
+            1 => monster_manager::SpellRoll::Attack,
+            n => monster_manager::SpellRoll::Save(monster_manager::ABILITY_KEYS[n as usize - 2].to_string()),
+        };
+        let damage_type = match UiFactory::get_dropdown_text(&damage_type_dropdown) {
+            t if t == UNSPECIFIED_DAMAGE_TYPE => String::new(),
+            t => t,
+        };
+
+        spells_clone.borrow_mut().push(monster_manager::Spell {
+            name,
+            level: level_spin.value() as i32,
+            usage,
+            roll,
+            damage,
+            damage_type,
+        });
+        spells_clone.borrow_mut().sort_by(|a, b| a.level.cmp(&b.level).then(a.name.cmp(&b.name)));
+        fill_spell_list(&spell_list_clone, &spells_clone);
+        spell_name_entry_clone.set_text("");
+        damage_entry_clone.set_text("");
+    });
+
+    // --- Save / Cancel ---
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let save_button = Button::with_label("Save Spellcasting");
+    let cancel_button = Button::with_label("Cancel");
+    button_box.append(&save_button);
+    button_box.append(&cancel_button);
+
+    main_vbox.append(&title_label);
+    main_vbox.append(&ability_grid);
+    main_vbox.append(&slots_title);
+    main_vbox.append(&slots_grid);
+    main_vbox.append(&spells_title);
+    main_vbox.append(&scrolled_window);
+    main_vbox.append(&spell_grid);
+    main_vbox.append(&error_label);
+    main_vbox.append(&button_box);
+
+    let window_clone = window.clone();
+    let parent_window_clone = parent_window.clone();
+    let app_clone = app.clone();
+    let monster_id_clone = monster.id.clone();
+    let error_label_clone = error_label.clone();
+    save_button.connect_clicked(move |_| {
+        let ability = UiFactory::get_dropdown_text(&ability_dropdown);
+        let spellcasting = if ability == NO_SPELLCASTING_LABEL {
+            None
+        } else {
+            let mut slots = [0; 9];
+            for (slot, spin) in slots.iter_mut().zip(slot_spins.iter()) {
+                *slot = spin.value() as i32;
+            }
+            Some(monster_manager::Spellcasting {
+                ability,
+                slots,
+                spells: spells.borrow().clone(),
+            })
+        };
+
+        if let Err(e) = monster_manager::set_spellcasting(&monster_id_clone, spellcasting) {
+            error_label_clone.set_text(&format!("Failed to save spellcasting: {}", e));
+            return;
+        }
+
+        window_clone.close();
+        switch_to_monster_list(&app_clone, &parent_window_clone);
+    });
+
+    let window_clone_cancel = window.clone();
+    cancel_button.connect_clicked(move |_| {
+        window_clone_cancel.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// Rebuilds the spell rows of the spellcasting form, each with a remove button.
+fn fill_spell_list(list_box: &ListBox, spells: &Rc<RefCell<Vec<monster_manager::Spell>>>) {
+    while let Some(child) = list_box.first_child() {
+        list_box.remove(&child);
+    }
+    if spells.borrow().is_empty() {
+        list_box.append(&Label::new(Some("No spells yet.")));
+        return;
+    }
+
+    for (index, spell) in spells.borrow().iter().enumerate() {
+        let hbox = UiFactory::create_box(Orientation::Horizontal, 12, (6, 6, 12, 12));
+        let spell_label = UiFactory::create_label(&spell.describe(), Align::Start, false, &[]);
+        spell_label.set_hexpand(true);
+        let remove_button = UiFactory::create_button("Remove", Align::Center, Some("destructive-action"));
+
+        let list_box_clone = list_box.clone();
+        let spells_clone = Rc::clone(spells);
+        remove_button.connect_clicked(move |_| {
+            spells_clone.borrow_mut().remove(index);
+            fill_spell_list(&list_box_clone, &spells_clone);
+        });
+
+        hbox.append(&spell_label);
+        hbox.append(&remove_button);
+        list_box.append(&hbox);
+    }
+}
@@ -4,13 +4,30 @@ use gtk::ApplicationWindow as AdwWindow;
 
 // import local script
 mod monster_manager;
+mod encounter_manager;
 mod interface;
+mod import_interface;
+mod export_interface;
+mod monster_import;
+mod monster_bundle;
+mod monster_csv;
+mod statblock_export;
 mod simulation;
 mod ui_factory;
 
 const APP_ID: &str = "com.mass.combat.decider";
 
 fn main() {
+    // Command line exports run without opening a window
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "export-statblock") {
+        if let Err(e) = statblock_export::run_cli(&args[1..]) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     if std::env::var("GTK_CSD").is_err() {
         unsafe { std::env::set_var("GTK_CSD", "0") };
     }
@@ -20,6 +37,9 @@ fn main() {
         .application_id(APP_ID)
         .build();
 
+    // Pull a simulation saved by older versions into the encounter library
+    encounter_manager::import_legacy_simulation();
+
     // Check for monsters and activate appropriate UI
     if !monster_manager::check_for_monsters() {
         app.connect_activate(first_start);
@@ -57,4 +77,9 @@ fn monster_list(app: &AdwApplication) {
     let header_bar = libadwaita::HeaderBar::new();
     window.set_titlebar(Some(&header_bar));
     interface::switch_to_monster_list(app, &window);
+
+    // Offer to bring back a simulation that was still running when the app last closed
+    if encounter_manager::check_for_autosave() {
+        simulation::show_autosave_recovery_menu(app, &window);
+    }
 }
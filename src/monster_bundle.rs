@@ -0,0 +1,190 @@
+// monster_bundle.rs
+//
+// This file handles monster bundles: a single JSON file holding a set of monsters and their
+// folders, used to share homebrew libraries. It writes bundles, reads them back and works out
+// which incoming monsters clash with ones already in the library.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+use super::monster_manager::{self, Monster};
+
+/// Current version of the bundle layout. Monsters inside carry their own schema version.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+// A shareable set of monsters with some information about where it came from.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct MonsterBundle {
+    pub format_version: u32,
+    pub monster_schema_version: u32,
+    pub title: String,
+    pub description: String,
+    pub created_at: String,
+    pub app_version: String,
+    // every exported folder, so empty ones are recreated too
+    pub folders: Vec<String>,
+    pub monsters: Vec<BundledMonster>,
+}
+
+// Monsters are kept as raw JSON so bundles from older versions go through the usual migrations.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct BundledMonster {
+    pub folder: String,
+    pub monster: Value,
+}
+
+/// How an incoming monster relates to the library.
+#[derive(Debug, Clone)]
+pub enum BundleConflict {
+    None,
+    // same id or name with the same stats
+    Identical(Monster),
+    // same id or name but different stats
+    Differs(Monster),
+}
+
+/// What to do with an incoming monster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleResolution {
+    Skip,
+    Import,
+    Overwrite,
+    Rename,
+}
+
+/// Writes the given monsters and folders to a bundle file.
+pub fn export_bundle(path: &Path, title: &str, description: &str, monsters: &[Monster], folders: &[String]) -> io::Result<()> {
+    let bundle = MonsterBundle {
+        format_version: BUNDLE_FORMAT_VERSION,
+        monster_schema_version: monster_manager::MONSTER_SCHEMA_VERSION,
+        title: title.trim().to_string(),
+        description: description.trim().to_string(),
+        created_at: chrono::Local::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        folders: folders.to_vec(),
+        monsters: monsters
+            .iter()
+            .map(|monster| {
+                Ok(BundledMonster {
+                    folder: monster.folder.clone(),
+                    monster: serde_json::to_value(monster)?,
+                })
+            })
+            .collect::<serde_json::Result<Vec<BundledMonster>>>()?,
+    };
+
+    let json_data = serde_json::to_string_pretty(&bundle)?;
+    let mut file = File::create(path)?;
+    file.write_all(json_data.as_bytes())?;
+
+    println!("Exported {} monsters to bundle: {:?}", monsters.len(), path);
+    Ok(())
+}
+
+/// Reads a bundle file, refusing ones written by a newer version of the app.
+pub fn read_bundle(path: &Path) -> io::Result<MonsterBundle> {
+    let contents = fs::read_to_string(path)?;
+    let bundle: MonsterBundle = serde_json::from_str(&contents)?;
+
+    if bundle.format_version == 0 || bundle.monsters.is_empty() && bundle.folders.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "This file is not a monster bundle"));
+    }
+    if bundle.format_version > BUNDLE_FORMAT_VERSION || bundle.monster_schema_version > monster_manager::MONSTER_SCHEMA_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("This bundle was made by a newer version of the app ({})", bundle.app_version)
+        ));
+    }
+    Ok(bundle)
+}
+
+/// Converts the bundled monsters to the current schema. Monsters that can't be read are reported by name.
+pub fn bundle_monsters(bundle: &MonsterBundle) -> (Vec<Monster>, Vec<String>) {
+    let mut monsters = Vec::new();
+    let mut failures = Vec::new();
+    for entry in &bundle.monsters {
+        match monster_manager::monster_from_value(entry.monster.clone()) {
+            Ok(mut monster) => {
+                monster.folder = entry.folder.clone();
+                monsters.push(monster);
+            }
+            Err(e) => {
+                let name = entry.monster.get("name").and_then(Value::as_str).unwrap_or("Unnamed monster");
+                failures.push(format!("{}: {}", name, e));
+            }
+        }
+    }
+    (monsters, failures)
+}
+
+/// Looks for a library monster with the same id, or failing that the same name.
+pub fn find_conflict(incoming: &Monster, library: &[Monster]) -> BundleConflict {
+    let existing = library
+        .iter()
+        .find(|m| !incoming.id.is_empty() && m.id == incoming.id)
+        .or_else(|| library.iter().find(|m| m.name.trim().eq_ignore_ascii_case(incoming.name.trim())));
+
+    match existing {
+        Some(existing) if same_stats(existing, incoming) => BundleConflict::Identical(existing.clone()),
+        Some(existing) => BundleConflict::Differs(existing.clone()),
+        None => BundleConflict::None,
+    }
+}
+
+/// Compares everything except bookkeeping (id, schema version and folder).
+fn same_stats(a: &Monster, b: &Monster) -> bool {
+    let stats = |monster: &Monster| {
+        let mut value = serde_json::to_value(monster).unwrap_or(Value::Null);
+        if let Some(map) = value.as_object_mut() {
+            map.remove("id");
+            map.remove("schema_version");
+        }
+        value
+    };
+    stats(a) == stats(b)
+}
+
+/// Saves one incoming monster according to the chosen resolution. `taken_names` holds the
+/// library's names plus those given out earlier in the same import, and gains the saved name.
+pub fn import_bundled_monster(mut monster: Monster, conflict: &BundleConflict, resolution: BundleResolution, taken_names: &mut Vec<String>) -> io::Result<()> {
+    let existing = match conflict {
+        BundleConflict::Identical(existing) | BundleConflict::Differs(existing) => Some(existing),
+        BundleConflict::None => None,
+    };
+
+    match (resolution, existing) {
+        (BundleResolution::Skip, _) => return Ok(()),
+        // Overwriting keeps the library's record and location, only the stats change
+        (BundleResolution::Overwrite, Some(existing)) => {
+            monster.id = existing.id.clone();
+            monster.folder = existing.folder.clone();
+        }
+        (BundleResolution::Rename, Some(_)) => {
+            monster.id = String::new();
+            monster.name = unique_monster_name(&monster.name, taken_names);
+        }
+        _ => {}
+    }
+
+    let name = monster.name.clone();
+    monster_manager::save_monster(monster)?;
+    taken_names.push(name);
+    Ok(())
+}
+
+/// Appends " (2)", " (3)", ... until the name isn't taken.
+fn unique_monster_name(name: &str, taken_names: &[String]) -> String {
+    let taken = |candidate: &str| taken_names.iter().any(|taken| taken.eq_ignore_ascii_case(candidate));
+    let mut counter = 2;
+    let mut candidate = format!("{} ({})", name, counter);
+    while taken(&candidate) {
+        counter += 1;
+        candidate = format!("{} ({})", name, counter);
+    }
+    candidate
+}
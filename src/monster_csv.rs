@@ -0,0 +1,895 @@
+// monster_csv.rs
+//
+// This file handles CSV spreadsheets of the monster library, for tuning stats in bulk.
+// Monsters go one per row in one file and their attacks one per row in a second file
+// next to it ("monsters.csv" and "monsters_attacks.csv"). Edited files are read back,
+// checked row by row and matched against the library before anything is saved.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::monster_manager::{self, ActionCategory, AreaShape, AreaTemplate, Attack, AttackRider, DamageModifier, Monster, SaveSuccess, Sense, SkillProficiency, WeaponMaterial};
+
+/// Columns of the monster file. "str" to "cha" are ability scores and speeds are in feet.
+/// Lists inside a cell (tags, skills, senses, damage types) are separated by ";",
+/// with expertise written as "Stealth (expertise)", senses as "Darkvision 60" and
+/// conditional resistances as "Slashing from nonmagical attacks".
+pub const MONSTER_COLUMNS: [&str; 40] = [
+    "id", "name", "folder", "tags", "hp", "ac", "cr", "exp", "pb", "hitdie",
+    "size", "type", "subtypes", "alignment",
+    "walk_speed", "fly_speed", "swim_speed", "climb_speed", "burrow_speed", "hover",
+    "str", "dex", "con", "int", "wis", "cha",
+    "save_str", "save_dex", "save_con", "save_int", "save_wis", "save_cha", "skills",
+    "senses", "languages", "vulnerabilities", "resistances", "immunities",
+    "condition_immunities", "abilities",
+];
+
+/// Columns of the attack file. Attacks find their monster by id, or by name when the id is empty.
+/// "category" is Action, Bonus Action, Reaction, Legendary or Lair; empty means Action.
+/// "material" is Nonmagical, Silvered, Adamantine or Magical; empty means Nonmagical.
+pub const ATTACK_COLUMNS: [&str; 26] = [
+    "monster_id", "monster_name", "attack_name", "ability_used", "dice_used",
+    "num_dice", "num_attacks", "saving_throw", "save_ability", "on_save", "add_mod_to_damage",
+    "damage_type", "material", "category",
+    "reach", "range", "long_range", "area_shape", "area_size",
+    "rider_save", "rider_dc", "rider_condition", "rider_damage", "rider_damage_type",
+    "rider_half_on_success", "rider_duration",
+];
+
+const ABILITIES: [&str; 6] = ["str", "dex", "con", "int", "wis", "cha"];
+const DICE: [&str; 6] = ["d4", "d6", "d8", "d10", "d12", "d20"];
+
+/// A problem with one row of a CSV file.
+#[derive(Debug, Clone)]
+pub struct CsvRowError {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+/// A valid monster row, with the library monster it will replace if there is one.
+#[derive(Debug, Clone)]
+pub struct CsvImportRow {
+    pub line: usize,
+    pub monster: Monster,
+    pub existing: Option<Monster>,
+}
+
+/// Everything read from a monster CSV and its attack file.
+#[derive(Debug, Clone, Default)]
+pub struct CsvImport {
+    pub rows: Vec<CsvImportRow>,
+    pub errors: Vec<CsvRowError>,
+    // None when there was no attack file, in which case attacks are left untouched
+    pub attacks_path: Option<PathBuf>,
+}
+
+// ==================================================================================
+// Export
+// ==================================================================================
+
+/// Path of the attack file that belongs to a monster file.
+pub fn attacks_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("monsters");
+    path.with_file_name(format!("{}_attacks.csv", stem))
+}
+
+/// Writes the monsters and their attacks to two CSV files. Returns the attack file's path.
+pub fn export_csv(path: &Path, monsters: &[Monster]) -> io::Result<PathBuf> {
+    let mut monster_rows = vec![MONSTER_COLUMNS.iter().map(|c| c.to_string()).collect::<Vec<String>>()];
+    let mut attack_rows = vec![ATTACK_COLUMNS.iter().map(|c| c.to_string()).collect::<Vec<String>>()];
+
+    for monster in monsters {
+        let mut row = vec![
+            monster.id.clone(),
+            monster.name.clone(),
+            monster.folder.clone(),
+            monster.tags.join("; "),
+            monster.hp.to_string(),
+            monster.ac.to_string(),
+            monster.cr.clone(),
+            monster.exp.to_string(),
+            monster.pb.to_string(),
+            monster.hitdie.clone(),
+            monster.size.clone(),
+            monster.creature_type.clone(),
+            monster.subtypes.join("; "),
+            monster.alignment.clone(),
+            monster.speeds.walk.to_string(),
+            monster.speeds.fly.to_string(),
+            monster.speeds.swim.to_string(),
+            monster.speeds.climb.to_string(),
+            monster.speeds.burrow.to_string(),
+            monster.speeds.hover.to_string(),
+        ];
+        row.extend(monster.scores.iter().map(|s| s.to_string()));
+        row.extend(monster.saves.iter().map(|s| s.to_string()));
+        row.push(
+            monster.skills
+                .iter()
+                .map(skill_text)
+                .collect::<Vec<String>>()
+                .join("; ")
+        );
+        row.push(
+            monster.senses
+                .iter()
+                .map(sense_text)
+                .collect::<Vec<String>>()
+                .join("; ")
+        );
+        row.push(monster.languages.join("; "));
+        for list in [&monster.vulnerabilities, &monster.resistances, &monster.immunities] {
+            row.push(describe_damage_list(list, "; "));
+        }
+        row.push(monster.condition_immunities.join("; "));
+        row.push(monster.abilities.clone());
+        monster_rows.push(row);
+
+        for attack in &monster.attacks {
+            let rider = attack.rider.clone().unwrap_or_default();
+            let has_rider = attack.rider.is_some();
+            attack_rows.push(vec![
+                monster.id.clone(),
+                monster.name.clone(),
+                attack.attack_name.clone(),
+                attack.ability_used.clone(),
+                attack.dice_used.clone(),
+                attack.num_dice.to_string(),
+                attack.num_attacks.to_string(),
+                attack.saving_throw.to_string(),
+                attack.save_ability.clone(),
+                if attack.saving_throw { attack.on_save.label().to_string() } else { String::new() },
+                if attack.saving_throw { attack.add_mod_to_damage.to_string() } else { String::new() },
+                attack.damage_type.clone(),
+                attack.material.label().to_string(),
+                attack.category.label().to_string(),
+                attack.reach.to_string(),
+                attack.range.to_string(),
+                attack.long_range.to_string(),
+                attack.area.as_ref().map(|a| a.shape.label().to_string()).unwrap_or_default(),
+                attack.area.as_ref().map(|a| a.size.to_string()).unwrap_or_default(),
+                rider.save_ability,
+                if has_rider { rider.dc.to_string() } else { String::new() },
+                rider.condition,
+                rider.extra_damage,
+                rider.damage_type,
+                if has_rider { rider.half_on_success.to_string() } else { String::new() },
+                rider.duration,
+            ]);
+        }
+    }
+
+    let attack_file = attacks_path(path);
+    fs::write(path, write_csv(&monster_rows))?;
+    fs::write(&attack_file, write_csv(&attack_rows))?;
+
+    println!("Exported {} monsters to CSV: {:?}", monsters.len(), path);
+    Ok(attack_file)
+}
+
+/// Joins rows into CSV text, quoting cells that need it.
+fn write_csv(rows: &[Vec<String>]) -> String {
+    let mut text = String::new();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| {
+                if cell.contains([',', '"', '\n', '\r']) || cell.trim() != cell {
+                    format!("\"{}\"", cell.replace('"', "\"\""))
+                } else {
+                    cell.clone()
+                }
+            })
+            .collect();
+        text.push_str(&cells.join(","));
+        text.push_str("\r\n");
+    }
+    text
+}
+
+// ==================================================================================
+// Import
+// ==================================================================================
+
+/// Reads an edited monster CSV (and its attack file when it exists) and checks every row.
+/// Only a missing or unreadable monster file is an error; bad rows are reported in the result.
+pub fn read_csv(path: &Path, library: &[Monster]) -> io::Result<CsvImport> {
+    let text = fs::read_to_string(path)?;
+    let file_name = display_name(path);
+    let records = parse_csv(&text);
+    let (header, records) = match records.split_first() {
+        Some(((_, header), records)) => (header_map(header), records),
+        None => return Err(io::Error::new(io::ErrorKind::InvalidData, "The file is empty")),
+    };
+    if !header.contains_key("name") {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "The file has no \"name\" column"));
+    }
+
+    let mut import = CsvImport::default();
+    let mut seen: Vec<String> = Vec::new();
+    for (line, record) in records {
+        if record.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        match parse_monster_row(&header, record, library) {
+            Ok((monster, existing)) => {
+                let key = existing.as_ref().map(|m| m.id.clone()).unwrap_or_else(|| monster.name.to_lowercase());
+                if seen.contains(&key) {
+                    import.errors.push(row_error(&file_name, *line, format!("\"{}\" appears more than once", monster.name)));
+                    continue;
+                }
+                seen.push(key);
+                import.rows.push(CsvImportRow { line: *line, monster, existing });
+            }
+            Err(message) => import.errors.push(row_error(&file_name, *line, message)),
+        }
+    }
+
+    let attack_file = attacks_path(path);
+    if attack_file.exists() {
+        apply_attack_file(&attack_file, &mut import)?;
+        import.attacks_path = Some(attack_file);
+    }
+    Ok(import)
+}
+
+/// Saves every valid row through the monster manager. Returns the rows that failed.
+pub fn apply_csv_import(import: &CsvImport) -> Vec<String> {
+    let mut failures = Vec::new();
+    for row in &import.rows {
+        if let Err(e) = monster_manager::save_monster(row.monster.clone()) {
+            failures.push(format!("Line {} ({}): {}", row.line, row.monster.name, e));
+        }
+    }
+    failures
+}
+
+/// Lists what a row changes compared to the library monster it replaces.
+pub fn describe_changes(existing: &Monster, updated: &Monster) -> Vec<String> {
+    let mut changes = Vec::new();
+    let mut compare = |label: &str, before: String, after: String| {
+        if before != after {
+            changes.push(format!("{}: {} → {}", label, before, after));
+        }
+    };
+    compare("Name", existing.name.clone(), updated.name.clone());
+    compare("Folder", existing.folder.clone(), updated.folder.clone());
+    compare("Tags", existing.tags.join(", "), updated.tags.join(", "));
+    compare("HP", existing.hp.to_string(), updated.hp.to_string());
+    compare("AC", existing.ac.to_string(), updated.ac.to_string());
+    compare("CR", existing.cr.clone(), updated.cr.clone());
+    compare("EXP", existing.exp.to_string(), updated.exp.to_string());
+    compare("PB", existing.pb.to_string(), updated.pb.to_string());
+    compare("Hit Die", existing.hitdie.clone(), updated.hitdie.clone());
+    compare("Type", existing.describe_type(), updated.describe_type());
+    compare("Speed", existing.speeds.describe(), updated.speeds.describe());
+    for (i, ability) in ABILITIES.iter().enumerate() {
+        compare(&ability.to_uppercase(), existing.scores[i].to_string(), updated.scores[i].to_string());
+        compare(&format!("{} save", ability.to_uppercase()), existing.saves[i].to_string(), updated.saves[i].to_string());
+    }
+    let skill_names = |monster: &Monster| {
+        monster.skills
+            .iter()
+            .map(skill_text)
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    compare("Skills", skill_names(existing), skill_names(updated));
+    let sense_names = |monster: &Monster| {
+        monster.senses
+            .iter()
+            .map(sense_text)
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    compare("Senses", sense_names(existing), sense_names(updated));
+    compare("Languages", existing.languages.join(", "), updated.languages.join(", "));
+    compare("Vulnerabilities", describe_damage_list(&existing.vulnerabilities, ", "), describe_damage_list(&updated.vulnerabilities, ", "));
+    compare("Resistances", describe_damage_list(&existing.resistances, ", "), describe_damage_list(&updated.resistances, ", "));
+    compare("Immunities", describe_damage_list(&existing.immunities, ", "), describe_damage_list(&updated.immunities, ", "));
+    compare("Condition immunities", existing.condition_immunities.join(", "), updated.condition_immunities.join(", "));
+    if existing.abilities != updated.abilities {
+        changes.push("Abilities text changed".to_string());
+    }
+    let attack_names = |monster: &Monster| monster.attacks.iter().map(|a| a.attack_name.clone()).collect::<Vec<String>>().join(", ");
+    let attack_rows = |monster: &Monster| serde_json::to_string(&monster.attacks).unwrap_or_default();
+    if attack_rows(existing) != attack_rows(updated) {
+        changes.push(format!("Attacks: {}", if updated.attacks.is_empty() { "None".to_string() } else { attack_names(updated) }));
+    }
+    changes
+}
+
+/// Replaces the attacks of the imported monsters with the rows of the attack file.
+/// A monster with a bad attack row is dropped so it isn't saved with half its attacks.
+fn apply_attack_file(path: &Path, import: &mut CsvImport) -> io::Result<()> {
+    let text = fs::read_to_string(path)?;
+    let file_name = display_name(path);
+    let records = parse_csv(&text);
+    let Some(((_, header), records)) = records.split_first() else {
+        return Ok(());
+    };
+    let header = header_map(header);
+
+    let mut attacks: HashMap<usize, Vec<Attack>> = HashMap::new();
+    let mut broken: Vec<usize> = Vec::new();
+    for (line, record) in records {
+        if record.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        let cell = |column: &str| header.get(column).and_then(|&i| record.get(i)).map(|c| c.trim()).unwrap_or("");
+
+        let monster_id = cell("monster_id");
+        let monster_name = cell("monster_name");
+        let owner = import.rows.iter().position(|row| {
+            if monster_id.is_empty() {
+                row.monster.name.eq_ignore_ascii_case(monster_name)
+            } else {
+                row.monster.id == monster_id
+            }
+        });
+        let Some(owner) = owner else {
+            let who = if monster_id.is_empty() { monster_name } else { monster_id };
+            import.errors.push(row_error(&file_name, *line, format!("No valid monster row for \"{}\"", who)));
+            continue;
+        };
+
+        match parse_attack_row(&cell) {
+            Ok(attack) => attacks.entry(owner).or_default().push(attack),
+            Err(message) => {
+                import.errors.push(row_error(
+                    &file_name,
+                    *line,
+                    format!("{} (\"{}\" won't be updated)", message, import.rows[owner].monster.name)
+                ));
+                broken.push(owner);
+            }
+        }
+    }
+
+    import.rows = std::mem::take(&mut import.rows)
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| !broken.contains(index))
+        .map(|(index, mut row)| {
+            // Monsters without rows in the attack file keep the attacks they have
+            if let Some(list) = attacks.remove(&index) {
+                row.monster.attacks = list;
+            }
+            row
+        })
+        .collect();
+    Ok(())
+}
+
+/// Builds a monster from one row. Columns missing from the file keep the library's values.
+fn parse_monster_row(header: &HashMap<String, usize>, record: &[String], library: &[Monster]) -> Result<(Monster, Option<Monster>), String> {
+    let cell = |column: &str| header.get(column).and_then(|&i| record.get(i)).map(|c| c.trim());
+
+    let name = cell("name").unwrap_or("");
+    if name.is_empty() {
+        return Err("The name is empty".to_string());
+    }
+    let id = cell("id").unwrap_or("");
+    let existing = if id.is_empty() {
+        library.iter().find(|m| m.name.eq_ignore_ascii_case(name)).cloned()
+    } else {
+        match library.iter().find(|m| m.id == id) {
+            Some(existing) => Some(existing.clone()),
+            None => return Err(format!("No monster with id \"{}\"; clear the id to add it as a new monster", id)),
+        }
+    };
+
+    let mut monster = existing.clone().unwrap_or_else(|| Monster {
+        scores: [10; 6],
+        hitdie: "d8".to_string(),
+        ..Monster::default()
+    });
+    monster.name = name.to_string();
+    if let Some(folder) = cell("folder") {
+        monster.folder = monster_manager::normalize_folder(folder).map_err(|e| format!("folder: {}", e))?;
+    }
+    if let Some(tags) = cell("tags") {
+        monster.tags = split_list(tags);
+    }
+
+    for (column, field) in [
+        ("hp", &mut monster.hp),
+        ("ac", &mut monster.ac),
+        ("exp", &mut monster.exp),
+        ("pb", &mut monster.pb),
+        ("walk_speed", &mut monster.speeds.walk),
+        ("fly_speed", &mut monster.speeds.fly),
+        ("swim_speed", &mut monster.speeds.swim),
+        ("climb_speed", &mut monster.speeds.climb),
+        ("burrow_speed", &mut monster.speeds.burrow),
+    ] {
+        if let Some(value) = cell(column) {
+            *field = parse_number(column, value)?;
+        }
+    }
+    // Files exported before there were several speeds have a single "speed" column
+    if let Some(value) = cell("speed").filter(|_| cell("walk_speed").is_none()) {
+        monster.speeds.walk = parse_number("speed", value)?;
+    }
+    if let Some(value) = cell("hover") {
+        monster.speeds.hover = parse_bool("hover", value)?;
+    }
+    if let Some(cr) = cell("cr") {
+        if !cr.is_empty() && monster_manager::cr_stats(cr).is_none() {
+            return Err(format!("cr: \"{}\" is not a challenge rating", cr));
+        }
+        monster.cr = cr.to_string();
+    }
+    if let Some(hitdie) = cell("hitdie") {
+        if !DICE.contains(&hitdie) {
+            return Err(format!("hitdie: \"{}\" should be one of {}", hitdie, DICE.join(", ")));
+        }
+        monster.hitdie = hitdie.to_string();
+    }
+
+    if let Some(size) = cell("size") {
+        monster.size = match size {
+            "" => String::new(),
+            size => monster_manager::SIZES
+                .iter()
+                .find(|s| s.eq_ignore_ascii_case(size))
+                .map(|s| s.to_string())
+                .ok_or_else(|| format!("size: \"{}\" should be one of {}", size, monster_manager::SIZES.join(", ")))?,
+        };
+    }
+    // Types outside the SRD list are allowed for homebrew, but known ones get their usual spelling
+    if let Some(creature_type) = cell("type") {
+        monster.creature_type = monster_manager::CREATURE_TYPES
+            .iter()
+            .find(|t| t.eq_ignore_ascii_case(creature_type))
+            .map_or(creature_type.to_string(), |t| t.to_string());
+    }
+    if let Some(subtypes) = cell("subtypes") {
+        monster.subtypes = split_list(subtypes);
+    }
+    if let Some(alignment) = cell("alignment") {
+        monster.alignment = alignment.to_string();
+    }
+    if let Some(senses) = cell("senses") {
+        monster.senses = split_list(senses)
+            .iter()
+            .map(|sense| parse_sense(sense))
+            .collect::<Result<Vec<Sense>, String>>()?;
+    }
+    if let Some(languages) = cell("languages") {
+        monster.languages = split_list(languages);
+    }
+
+    for (i, ability) in ABILITIES.iter().enumerate() {
+        if let Some(value) = cell(ability) {
+            monster.scores[i] = parse_number(ability, value)?;
+        }
+        let save_column = format!("save_{}", ability);
+        if let Some(value) = cell(&save_column) {
+            monster.saves[i] = parse_bool(&save_column, value)?;
+        }
+    }
+
+    if let Some(skills) = cell("skills") {
+        monster.skills = split_list(skills)
+            .iter()
+            .map(|skill| parse_skill(skill))
+            .collect::<Result<Vec<SkillProficiency>, String>>()?;
+    }
+
+    for (column, list) in [
+        ("vulnerabilities", &mut monster.vulnerabilities),
+        ("resistances", &mut monster.resistances),
+        ("immunities", &mut monster.immunities),
+    ] {
+        if let Some(value) = cell(column) {
+            *list = split_list(value)
+                .iter()
+                .map(|entry| parse_damage_modifier(column, entry))
+                .collect::<Result<Vec<DamageModifier>, String>>()?;
+        }
+    }
+    if let Some(value) = cell("condition_immunities") {
+        monster.condition_immunities = split_list(value)
+            .iter()
+            .map(|name| {
+                monster_manager::CONDITIONS
+                    .iter()
+                    .find(|c| c.eq_ignore_ascii_case(name))
+                    .map(|c| c.to_string())
+                    .ok_or_else(|| format!("condition_immunities: \"{}\" is not a condition", name))
+            })
+            .collect::<Result<Vec<String>, String>>()?;
+    }
+    if let Some(abilities) = cell("abilities") {
+        monster.abilities = abilities.to_string();
+    }
+
+    if monster.hp <= 0 {
+        return Err("hp: must be more than 0".to_string());
+    }
+    Ok((monster, existing))
+}
+
+/// Builds an attack from one row of the attack file.
+fn parse_attack_row<'a>(cell: &dyn Fn(&str) -> &'a str) -> Result<Attack, String> {
+    let attack_name = cell("attack_name");
+    if attack_name.is_empty() {
+        return Err("The attack name is empty".to_string());
+    }
+
+    let ability_used = cell("ability_used").to_lowercase();
+    if !ABILITIES.contains(&ability_used.as_str()) {
+        return Err(format!("ability_used: \"{}\" should be one of {}", ability_used, ABILITIES.join(", ")));
+    }
+    let dice_used = cell("dice_used").to_lowercase();
+    if !DICE.contains(&dice_used.as_str()) {
+        return Err(format!("dice_used: \"{}\" should be one of {}", dice_used, DICE.join(", ")));
+    }
+    let damage_type = match cell("damage_type") {
+        "" => String::new(),
+        value => parse_damage_type("damage_type", value)?,
+    };
+    let material = match cell("material") {
+        "" => WeaponMaterial::default(),
+        value => WeaponMaterial::ALL
+            .iter()
+            .find(|m| m.label().eq_ignore_ascii_case(value))
+            .copied()
+            .ok_or_else(|| format!("material: \"{}\" should be one of Nonmagical, Silvered, Adamantine, Magical", value))?,
+    };
+    let category = match cell("category") {
+        "" => ActionCategory::default(),
+        value => ActionCategory::ALL
+            .iter()
+            .find(|c| c.label().eq_ignore_ascii_case(value) || c.heading().eq_ignore_ascii_case(value))
+            .copied()
+            .ok_or_else(|| format!("category: \"{}\" should be one of Action, Bonus Action, Reaction, Legendary, Lair", value))?,
+    };
+
+    let save_ability = cell("save_ability").to_lowercase();
+    if !save_ability.is_empty() && !ABILITIES.contains(&save_ability.as_str()) {
+        return Err(format!("save_ability: \"{}\" should be one of {}", save_ability, ABILITIES.join(", ")));
+    }
+    let on_save = match cell("on_save") {
+        "" => SaveSuccess::default(),
+        value => SaveSuccess::ALL
+            .iter()
+            .find(|s| s.label().eq_ignore_ascii_case(value))
+            .copied()
+            .ok_or_else(|| format!("on_save: \"{}\" should be Half damage or No damage", value))?,
+    };
+
+    // The same limits as the attack form
+    let num_dice = parse_number("num_dice", cell("num_dice"))?;
+    let num_attacks = parse_number("num_attacks", cell("num_attacks"))?;
+    for (column, value) in [("num_dice", num_dice), ("num_attacks", num_attacks)] {
+        if value <= 0 {
+            return Err(format!("{}: must be more than 0", column));
+        }
+    }
+
+    let distance = |column: &str| match cell(column) {
+        "" => Ok(0),
+        value => match parse_number(column, value)? {
+            feet if feet < 0 => Err(format!("{}: can't be negative", column)),
+            feet => Ok(feet),
+        },
+    };
+    let (reach, range, long_range) = (distance("reach")?, distance("range")?, distance("long_range")?);
+    if long_range > 0 && long_range < range {
+        return Err("long_range: can't be shorter than range".to_string());
+    }
+    let area = match cell("area_shape") {
+        "" => None,
+        value => Some(AreaTemplate {
+            shape: AreaShape::ALL
+                .iter()
+                .find(|s| s.label().eq_ignore_ascii_case(value))
+                .copied()
+                .ok_or_else(|| format!("area_shape: \"{}\" should be one of Cone, Line, Sphere, Cube, Cylinder", value))?,
+            size: match parse_number("area_size", cell("area_size"))? {
+                size if size <= 0 => return Err("area_size: give the area a size".to_string()),
+                size => size,
+            },
+        }),
+    };
+
+    // A rider needs at least its save ability; the other columns are optional
+    let rider = match cell("rider_save").to_lowercase() {
+        save if save.is_empty() => None,
+        save if !ABILITIES.contains(&save.as_str()) => {
+            return Err(format!("rider_save: \"{}\" should be one of {}", save, ABILITIES.join(", ")));
+        }
+        save_ability => {
+            let condition = match cell("rider_condition") {
+                "" => String::new(),
+                value => monster_manager::CONDITIONS
+                    .iter()
+                    .find(|c| c.eq_ignore_ascii_case(value))
+                    .map(|c| c.to_string())
+                    .ok_or_else(|| format!("rider_condition: \"{}\" is not a condition", value))?,
+            };
+            let extra_damage = cell("rider_damage").to_lowercase();
+            if !extra_damage.is_empty() && monster_manager::parse_dice_expression(&extra_damage).is_none() {
+                return Err(format!("rider_damage: \"{}\" is not a dice expression", extra_damage));
+            }
+            let dc = parse_number("rider_dc", cell("rider_dc"))?;
+            if !(1..=30).contains(&dc) {
+                return Err(format!("rider_dc: {} should be between 1 and 30", dc));
+            }
+            Some(AttackRider {
+                save_ability,
+                dc,
+                condition,
+                extra_damage,
+                damage_type: match cell("rider_damage_type") {
+                    "" => String::new(),
+                    value => parse_damage_type("rider_damage_type", value)?,
+                },
+                half_on_success: parse_bool("rider_half_on_success", cell("rider_half_on_success"))?,
+                duration: cell("rider_duration").to_string(),
+            })
+        }
+    };
+
+    Ok(Attack {
+        attack_name: attack_name.to_string(),
+        ability_used,
+        dice_used,
+        num_dice,
+        num_attacks,
+        saving_throw: parse_bool("saving_throw", cell("saving_throw"))?,
+        save_ability,
+        on_save,
+        add_mod_to_damage: parse_bool("add_mod_to_damage", cell("add_mod_to_damage"))?,
+        damage_type,
+        material,
+        category,
+        reach,
+        range,
+        long_range,
+        area,
+        rider,
+    })
+}
+
+fn parse_number(column: &str, value: &str) -> Result<i32, String> {
+    value
+        .trim_start_matches('+')
+        .parse::<i32>()
+        .map_err(|_| format!("{}: \"{}\" is not a whole number", column, value))
+}
+
+/// Accepts what spreadsheets tend to write for a checkbox. An empty cell is false.
+fn parse_bool(column: &str, value: &str) -> Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "y" | "x" | "1" => Ok(true),
+        "false" | "no" | "n" | "0" | "" => Ok(false),
+        _ => Err(format!("{}: \"{}\" should be true or false", column, value)),
+    }
+}
+
+/// Writes a skill as "Perception" or "Stealth (expertise)".
+fn skill_text(skill: &SkillProficiency) -> String {
+    if skill.expertise {
+        format!("{} (expertise)", skill.skill)
+    } else {
+        skill.skill.clone()
+    }
+}
+
+/// Reads a skill written by `skill_text`.
+fn parse_skill(value: &str) -> Result<SkillProficiency, String> {
+    let (name, expertise) = match value.strip_suffix("(expertise)") {
+        Some(name) => (name.trim(), true),
+        None => (value, false),
+    };
+    monster_manager::SKILLS
+        .iter()
+        .find(|(skill, _)| skill.eq_ignore_ascii_case(name))
+        .map(|(skill, _)| SkillProficiency { skill: skill.to_string(), expertise })
+        .ok_or_else(|| format!("skills: \"{}\" is not a skill", value))
+}
+
+/// Writes a sense as "Darkvision 60".
+fn sense_text(sense: &Sense) -> String {
+    format!("{} {}", sense.sense, sense.range)
+}
+
+/// Reads a sense written by `sense_text`, also accepting "darkvision 60 ft.".
+fn parse_sense(value: &str) -> Result<Sense, String> {
+    let text = value.trim_end_matches('.').trim_end_matches("ft").trim();
+    let (name, range) = text
+        .rsplit_once(' ')
+        .ok_or_else(|| format!("senses: \"{}\" should be a sense and a range, like \"Darkvision 60\"", value))?;
+    let sense = monster_manager::SENSES
+        .iter()
+        .find(|s| s.eq_ignore_ascii_case(name.trim()))
+        .map_or(name.trim().to_string(), |s| s.to_string());
+    Ok(Sense { sense, range: parse_number("senses", range)? })
+}
+
+/// Matches a damage type case-insensitively against the SRD and user-defined ones.
+fn parse_damage_type(column: &str, value: &str) -> Result<String, String> {
+    monster_manager::find_damage_type(value)
+        .ok_or_else(|| format!("{}: \"{}\" is not a damage type", column, value))
+}
+
+/// Reads "Fire" or "Slashing from nonmagical attacks".
+fn parse_damage_modifier(column: &str, value: &str) -> Result<DamageModifier, String> {
+    let modifier = DamageModifier::parse(value)
+        .ok_or_else(|| format!("{}: \"{}\" is not a damage type", column, value))?;
+    Ok(DamageModifier {
+        damage_type: parse_damage_type(column, &modifier.damage_type)?,
+        ..modifier
+    })
+}
+
+fn describe_damage_list(list: &[DamageModifier], separator: &str) -> String {
+    list.iter().map(DamageModifier::describe).collect::<Vec<String>>().join(separator)
+}
+
+/// Splits a ";" separated cell, dropping empty entries and duplicates.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items: Vec<String> = Vec::new();
+    for item in value.split(';').map(str::trim) {
+        if !item.is_empty() && !items.iter().any(|i| i == item) {
+            items.push(item.to_string());
+        }
+    }
+    items
+}
+
+fn header_map(header: &[String]) -> HashMap<String, usize> {
+    header
+        .iter()
+        .enumerate()
+        .map(|(i, column)| (column.trim().to_lowercase(), i))
+        .collect()
+}
+
+fn row_error(file: &str, line: usize, message: String) -> CsvRowError {
+    CsvRowError { file: file.to_string(), line, message }
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+/// Splits CSV text into records, each with the line it starts on. Handles quoted cells
+/// with commas, doubled quotes and line breaks, and the byte order mark some spreadsheets add.
+fn parse_csv(text: &str) -> Vec<(usize, Vec<String>)> {
+    let text = text.trim_start_matches('\u{feff}');
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    cell.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => cell.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut cell)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut cell));
+                records.push((record_line, std::mem::take(&mut record)));
+                record_line = line;
+            }
+            _ => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !record.is_empty() {
+        record.push(cell);
+        records.push((record_line, record));
+    }
+    records
+}
+
+// ==================================================================================
+// Tests
+// ==================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_csv_quoted_cells() {
+        let text = "\u{feff}name,abilities\r\nGoblin,\"Nimble Escape. Disengage, or \"\"Hide\"\".\n\nSecond line\"\r\nOgre,\r\n";
+        let records = parse_csv(text);
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0], (1, vec!["name".to_string(), "abilities".to_string()]));
+        assert_eq!(records[1].0, 2);
+        assert_eq!(records[1].1[1], "Nimble Escape. Disengage, or \"Hide\".\n\nSecond line");
+        // Line numbers count the line breaks inside quoted cells
+        assert_eq!(records[2], (5, vec!["Ogre".to_string(), String::new()]));
+    }
+
+    #[test]
+    fn export_and_read_round_trip() {
+        // An old v0 file, so the library monster goes through every migration first
+        let old_goblin = serde_json::json!({
+            "name": "Goblin",
+            "hp": 7,
+            "ac": 15,
+            "exp": 50,
+            "pb": 2,
+            "speed": 30,
+            "hitdie": "d6",
+            "mods": [-1, 2, 0, 0, -1, -1],
+            "saves": [false, true, false, false, false, false],
+            "restistances": ["Fire"],
+            "abilities": "Nimble Escape. The goblin can take the Disengage, or \"Hide\", action.\n\nSecond paragraph.",
+            "attacks": [{
+                "attack_name": "Scimitar",
+                "ability_used": "dex",
+                "dice_used": "d6",
+                "num_dice": 1,
+                "num_attacks": 1,
+                "saving_throw": false,
+                "damage_type": "Slashing"
+            }]
+        });
+        let mut goblin = monster_manager::monster_from_value(old_goblin).unwrap();
+        assert!(!goblin.id.is_empty());
+        assert_eq!(goblin.cr, "1/4");
+        assert_eq!(goblin.scores, [8, 14, 10, 10, 8, 8]);
+        assert_eq!(goblin.speeds.walk, 30);
+        assert_eq!(describe_damage_list(&goblin.resistances, "; "), "Fire");
+
+        goblin.tags = vec!["camp".to_string(), "small, sneaky".to_string()];
+        goblin.attacks.push(Attack {
+            attack_name: "Stink Cloud".to_string(),
+            ability_used: "con".to_string(),
+            dice_used: "d6".to_string(),
+            num_dice: 2,
+            num_attacks: 1,
+            saving_throw: true,
+            save_ability: "con".to_string(),
+            on_save: SaveSuccess::HalfDamage,
+            add_mod_to_damage: true,
+            damage_type: "Poison".to_string(),
+            area: Some(AreaTemplate { shape: AreaShape::Sphere, size: 10 }),
+            ..Default::default()
+        });
+
+        let dir = std::env::temp_dir().join(format!("monster_csv_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("monsters.csv");
+        let attack_file = export_csv(&path, std::slice::from_ref(&goblin)).unwrap();
+        assert_eq!(attack_file, dir.join("monsters_attacks.csv"));
+
+        let import = read_csv(&path, std::slice::from_ref(&goblin)).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(import.errors.is_empty(), "{:?}", import.errors);
+        assert_eq!(import.rows.len(), 1);
+        let row = &import.rows[0];
+        assert_eq!(row.line, 2);
+        assert_eq!(row.existing.as_ref().map(|m| m.id.as_str()), Some(goblin.id.as_str()));
+        assert!(describe_changes(&goblin, &row.monster).is_empty(), "{:?}", describe_changes(&goblin, &row.monster));
+        assert_eq!(serde_json::to_value(&row.monster).unwrap(), serde_json::to_value(&goblin).unwrap());
+    }
+}
@@ -0,0 +1,1487 @@
+// monster_import.rs
+//
+// This file converts monster JSON exported by other tools into our own `Monster` type.
+// It understands the 5e.tools bestiary format and the Open5e monster format, works only on
+// local files and never writes anything; saving is left to the caller after the user confirms.
+
+use serde_json::{Map, Value};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::monster_manager::{self, ActionCategory, AreaShape, AreaTemplate, Attack, AttackRider, DamageModifier, DamageQualifier, Monster, SaveSuccess, WeaponMaterial};
+
+/// Ability names in `scores`/`saves` order, with their short keys.
+const ABILITIES: [(&str, &str); 6] = [
+    ("strength", "str"),
+    ("dexterity", "dex"),
+    ("constitution", "con"),
+    ("intelligence", "int"),
+    ("wisdom", "wis"),
+    ("charisma", "cha"),
+];
+
+/// A converted monster along with everything that couldn't be carried over.
+pub struct ImportedMonster {
+    pub monster: Monster,
+    pub notes: Vec<String>,
+}
+
+/// Reads a local JSON file and converts every monster found in it.
+pub fn import_json_file(path: &Path) -> io::Result<Vec<ImportedMonster>> {
+    let contents = fs::read_to_string(path)?;
+    import_json_str(&contents)
+}
+
+/// Converts every monster in a JSON document. Accepts a 5e.tools bestiary file (`{"monster": [...]}`),
+/// an Open5e API page (`{"results": [...]}`), a bare array or a single monster object.
+pub fn import_json_str(contents: &str) -> io::Result<Vec<ImportedMonster>> {
+    let value: Value = serde_json::from_str(contents)?;
+
+    let entries: Vec<&Value> = match &value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(map) => match (map.get("monster"), map.get("results")) {
+            (Some(Value::Array(items)), _) | (_, Some(Value::Array(items))) => items.iter().collect(),
+            _ => vec![&value],
+        },
+        _ => Vec::new(),
+    };
+
+    let imported: Vec<ImportedMonster> = entries
+        .into_iter()
+        .filter_map(|entry| entry.as_object())
+        .filter(|map| map.get("name").and_then(Value::as_str).is_some())
+        .map(|map| {
+            if map.contains_key("strength") || map.contains_key("armor_class") {
+                import_open5e(map)
+            } else {
+                import_5etools(map)
+            }
+        })
+        .collect();
+
+    if imported.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "No monsters found in this file"));
+    }
+    Ok(imported)
+}
+
+// =========================================================================
+// 5e.tools Bestiary Format
+// =========================================================================
+
+fn import_5etools(map: &Map<String, Value>) -> ImportedMonster {
+    let mut monster = new_monster(map);
+    let mut notes = Vec::new();
+
+    if map.contains_key("_copy") {
+        notes.push("This entry copies another monster; only the fields written out in the file were imported.".to_string());
+    }
+
+    for (i, (_, short)) in ABILITIES.iter().enumerate() {
+        if let Some(score) = map.get(*short).and_then(Value::as_i64) {
+            monster.scores[i] = score as i32;
+        }
+    }
+
+    match map.get("cr") {
+        Some(Value::String(cr)) => apply_cr(&mut monster, cr, &mut notes),
+        Some(Value::Object(cr)) => {
+            if let Some(cr) = cr.get("cr").and_then(Value::as_str) {
+                apply_cr(&mut monster, cr, &mut notes);
+            }
+            if cr.contains_key("lair") || cr.contains_key("coven") {
+                notes.push("Alternate challenge ratings (lair/coven) were not imported.".to_string());
+            }
+        }
+        _ => notes.push("No challenge rating found; XP and PB left at 0.".to_string()),
+    }
+
+    // "ac" is a list of plain numbers or {"ac": 15, "from": [...]}; the first one is the default
+    monster.ac = match map.get("ac").and_then(Value::as_array).and_then(|list| list.first()) {
+        Some(Value::Object(ac)) => ac.get("ac").and_then(Value::as_i64).unwrap_or(0) as i32,
+        Some(ac) => ac.as_i64().unwrap_or(0) as i32,
+        None => 0,
+    };
+
+    if let Some(hp) = map.get("hp").and_then(Value::as_object) {
+        monster.hp = hp.get("average").and_then(Value::as_i64).unwrap_or(0) as i32;
+        apply_hit_dice(&mut monster, hp.get("formula").and_then(Value::as_str), &mut notes);
+        if let Some(special) = hp.get("special").and_then(Value::as_str) {
+            notes.push(format!("Special hit points were not imported: {}", special));
+        }
+    }
+
+    if let Some(speed) = map.get("speed").and_then(Value::as_object) {
+        apply_speeds(&mut monster, speed, &mut notes);
+    }
+
+    // Sizes are one-letter codes; a creature listed with several sizes gets the first
+    if let Some(code) = map.get("size").and_then(Value::as_array).and_then(|s| s.first()).and_then(Value::as_str) {
+        match monster_manager::SIZES.iter().find(|size| size.starts_with(code)) {
+            Some(size) => monster.size = size.to_string(),
+            None => notes.push(format!("Unknown size \"{}\".", code)),
+        }
+    }
+    // "type" is a plain string or {"type": "humanoid", "tags": ["goblinoid", {"tag": "elf", ...}]}
+    match map.get("type") {
+        Some(Value::String(creature_type)) => apply_creature_type(&mut monster, creature_type),
+        Some(Value::Object(creature_type)) => {
+            match creature_type.get("type").and_then(Value::as_str) {
+                Some(name) => apply_creature_type(&mut monster, name),
+                None => notes.push("Creature types with a choice were not imported.".to_string()),
+            }
+            monster.subtypes = creature_type
+                .get("tags")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|tag| tag.as_str().or_else(|| tag.get("tag").and_then(Value::as_str)))
+                .map(str::to_string)
+                .collect();
+        }
+        _ => {}
+    }
+    if let Some(alignment) = map.get("alignment").and_then(Value::as_array) {
+        let codes: Vec<&str> = alignment.iter().filter_map(Value::as_str).collect();
+        match alignment_5etools(&codes) {
+            Some(text) => monster.alignment = text,
+            None => notes.push("Mixed or conditional alignments were not imported.".to_string()),
+        }
+    }
+    for sense in map.get("senses").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str) {
+        apply_senses_text(&mut monster, &strip_5etools_tags(sense), &mut notes);
+    }
+    monster.languages = map
+        .get("languages")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(strip_5etools_tags)
+        .collect();
+
+    if let Some(saves) = map.get("save").and_then(Value::as_object) {
+        for (i, (name, short)) in ABILITIES.iter().enumerate() {
+            if let Some(bonus) = saves.get(*short).and_then(Value::as_str) {
+                apply_save(&mut monster, i, name, parse_signed(bonus), &mut notes);
+            }
+        }
+    }
+
+    monster.vulnerabilities = damage_types_5etools(map.get("vulnerable"), "vulnerable", "vulnerabilities", &mut notes);
+    monster.resistances = damage_types_5etools(map.get("resist"), "resist", "resistances", &mut notes);
+    monster.immunities = damage_types_5etools(map.get("immune"), "immune", "immunities", &mut notes);
+    for entry in map.get("conditionImmune").and_then(Value::as_array).into_iter().flatten() {
+        match entry {
+            Value::String(name) => push_condition(&mut monster.condition_immunities, name, &mut notes),
+            Value::Object(group) => {
+                for name in group.get("conditionImmune").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str) {
+                    push_condition(&mut monster.condition_immunities, name, &mut notes);
+                }
+                if let Some(condition) = group.get("note").or(group.get("preNote")).and_then(Value::as_str) {
+                    notes.push(format!("Conditional condition immunities were imported without their condition: {}", condition));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut abilities = Vec::new();
+    for key in ["trait", "spellcasting"] {
+        for entry in map.get(key).and_then(Value::as_array).into_iter().flatten() {
+            abilities.push(named_entry_text(entry));
+        }
+    }
+    if map.contains_key("spellcasting") {
+        notes.push("Spell lists were added to the ability text only.".to_string());
+    }
+
+    for entry in map.get("action").and_then(Value::as_array).into_iter().flatten() {
+        let name = entry.get("name").and_then(Value::as_str).map(strip_5etools_tags).unwrap_or_default();
+        let text = strip_5etools_tags(&flatten_entries(entry.get("entries").unwrap_or(&Value::Null)));
+        add_action(&mut monster, &mut abilities, &name, &text, ActionCategory::Action, &mut notes);
+    }
+
+    for (key, category) in [
+        ("bonus", ActionCategory::BonusAction),
+        ("reaction", ActionCategory::Reaction),
+        ("legendary", ActionCategory::Legendary),
+    ] {
+        let mut texts = Vec::new();
+        for entry in map.get(key).and_then(Value::as_array).into_iter().flatten() {
+            let name = entry.get("name").and_then(Value::as_str).map(strip_5etools_tags).unwrap_or_default();
+            let text = strip_5etools_tags(&flatten_entries(entry.get("entries").unwrap_or(&Value::Null)));
+            add_action(&mut monster, &mut texts, &name, &text, category, &mut notes);
+        }
+        push_section_text(&mut abilities, category, texts);
+    }
+
+    if let Some(skills) = map.get("skill").and_then(Value::as_object) {
+        for (skill, bonus) in skills {
+            apply_skill(&mut monster, skill, bonus.as_str().and_then(parse_signed), &mut notes);
+        }
+    }
+
+    monster.abilities = abilities.join("\n\n");
+    apply_magic_weapons(&mut monster);
+
+    ImportedMonster { monster, notes }
+}
+
+/// Turns 5e.tools alignment codes such as ["N", "E"] into "neutral evil".
+/// Returns None for entries we can't express, like "any non-good alignment".
+fn alignment_5etools(codes: &[&str]) -> Option<String> {
+    let words: Vec<&str> = codes
+        .iter()
+        .map(|code| match *code {
+            "L" => Some("lawful"),
+            "N" => Some("neutral"),
+            "C" => Some("chaotic"),
+            "G" => Some("good"),
+            "E" => Some("evil"),
+            "U" => Some("unaligned"),
+            "A" => Some("any alignment"),
+            _ => None,
+        })
+        .collect::<Option<Vec<&str>>>()?;
+    match words.as_slice() {
+        [] => None,
+        ["neutral", "neutral"] => Some("neutral".to_string()),
+        [_] | [_, _] => Some(words.join(" ")),
+        _ => None,
+    }
+}
+
+/// Collects damage types from 5e.tools "resist"/"immune"/"vulnerable" lists. Nested groups carry
+/// a note such as "from nonmagical attacks"; the ones we can't represent get noted.
+fn damage_types_5etools(value: Option<&Value>, key: &str, label: &str, notes: &mut Vec<String>) -> Vec<DamageModifier> {
+    let mut types = Vec::new();
+    for entry in value.and_then(Value::as_array).into_iter().flatten() {
+        match entry {
+            Value::String(name) => push_damage_type(&mut types, name, DamageQualifier::Always, label, notes),
+            Value::Object(group) => {
+                let condition = group.get("note").or(group.get("preNote")).and_then(Value::as_str);
+                let qualifier = condition.and_then(damage_qualifier).unwrap_or_default();
+                for name in group.get(key).and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str) {
+                    push_damage_type(&mut types, name, qualifier, label, notes);
+                }
+                if let Some(condition) = condition
+                    && damage_qualifier(condition).is_none()
+                {
+                    notes.push(format!("Conditional {} were imported without their condition: {}", label, condition));
+                }
+                if let Some(special) = group.get("special").and_then(Value::as_str) {
+                    notes.push(format!("Special {} were not imported: {}", label, special));
+                }
+            }
+            _ => {}
+        }
+    }
+    types
+}
+
+/// Builds "Name. text" out of a named 5e.tools entry.
+fn named_entry_text(entry: &Value) -> String {
+    let text = strip_5etools_tags(&flatten_entries(entry.get("headerEntries").or(entry.get("entries")).unwrap_or(&Value::Null)));
+    let mut extra = Vec::new();
+    if let Some(will) = entry.get("will") {
+        extra.push(format!("At will: {}", strip_5etools_tags(&flatten_entries(will))));
+    }
+    if let Some(daily) = entry.get("daily").and_then(Value::as_object) {
+        for (uses, spells) in daily {
+            extra.push(format!("{}/day: {}", uses.trim_end_matches('e'), strip_5etools_tags(&flatten_entries(spells))));
+        }
+    }
+    if let Some(spells) = entry.get("spells").and_then(Value::as_object) {
+        for (level, spells) in spells {
+            let list = strip_5etools_tags(&flatten_entries(spells.get("spells").unwrap_or(&Value::Null)));
+            let slots = spells.get("slots").and_then(Value::as_i64).map(|s| format!(" ({} slots)", s)).unwrap_or_default();
+            extra.push(format!("Level {}{}: {}", level, slots, list));
+        }
+    }
+
+    let body = std::iter::once(text).chain(extra).filter(|t| !t.is_empty()).collect::<Vec<_>>().join("\n");
+    match entry.get("name").and_then(Value::as_str) {
+        Some(name) => format!("{}. {}", strip_5etools_tags(name), body),
+        None => body,
+    }
+}
+
+/// Turns nested entry lists (strings, lists, sub-entries) into plain text.
+fn flatten_entries(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(flatten_entries).filter(|t| !t.is_empty()).collect::<Vec<_>>().join("\n"),
+        Value::Object(map) => {
+            let body = ["entries", "items", "entry"]
+                .iter()
+                .find_map(|key| map.get(*key))
+                .map(flatten_entries)
+                .unwrap_or_default();
+            match map.get("name").and_then(Value::as_str) {
+                Some(name) => format!("{}. {}", name, body),
+                None => body,
+            }
+        }
+        Value::Number(number) => number.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Replaces 5e.tools inline tags like `{@hit 4}` or `{@damage 1d6 + 2}` with their plain text.
+fn strip_5etools_tags(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{@") {
+        result.push_str(&rest[..start]);
+        let Some(length) = rest[start..].find('}') else {
+            break;
+        };
+        let tag = &rest[start + 2..start + length];
+        rest = &rest[start + length + 1..];
+
+        let (name, content) = tag.split_once(' ').unwrap_or((tag, ""));
+        let shown = content.split('|').next().unwrap_or("");
+        let replacement = match name {
+            "h" => "Hit: ".to_string(),
+            "atk" | "atkr" => attack_kind_label(content),
+            "hit" if shown.starts_with('-') => shown.to_string(),
+            "hit" => format!("+{}", shown),
+            "dc" => format!("DC {}", shown),
+            "recharge" if shown.is_empty() => "(Recharge 6)".to_string(),
+            "recharge" => format!("(Recharge {}-6)", shown),
+            "actSave" => {
+                let ability = ABILITIES.iter().find(|(_, short)| *short == shown).map(|(n, _)| *n).unwrap_or(shown);
+                format!("{} Saving Throw:", capitalize(ability))
+            }
+            "actSaveFail" => "Failure:".to_string(),
+            "actSaveSuccess" => "Success:".to_string(),
+            _ => shown.to_string(),
+        };
+        result.push_str(&replacement);
+    }
+
+    result.push_str(rest);
+    result
+}
+
+fn attack_kind_label(kind: &str) -> String {
+    let melee = kind.contains('m');
+    let ranged = kind.contains('r');
+    let range = match (melee, ranged) {
+        (true, true) => "Melee or Ranged",
+        (false, true) => "Ranged",
+        _ => "Melee",
+    };
+    let source = if kind.contains('s') { "Spell" } else { "Weapon" };
+    format!("{} {} Attack:", range, source)
+}
+
+// =========================================================================
+// Open5e Format
+// =========================================================================
+
+fn import_open5e(map: &Map<String, Value>) -> ImportedMonster {
+    let mut monster = new_monster(map);
+    let mut notes = Vec::new();
+
+    for (i, (name, _)) in ABILITIES.iter().enumerate() {
+        if let Some(score) = map.get(*name).and_then(Value::as_i64) {
+            monster.scores[i] = score as i32;
+        }
+    }
+
+    match map.get("challenge_rating").and_then(Value::as_str) {
+        Some(cr) => apply_cr(&mut monster, cr, &mut notes),
+        None => notes.push("No challenge rating found; XP and PB left at 0.".to_string()),
+    }
+
+    monster.ac = map.get("armor_class").and_then(Value::as_i64).unwrap_or(0) as i32;
+    monster.hp = map.get("hit_points").and_then(Value::as_i64).unwrap_or(0) as i32;
+    apply_hit_dice(&mut monster, map.get("hit_dice").and_then(Value::as_str), &mut notes);
+
+    if let Some(speed) = map.get("speed").and_then(Value::as_object) {
+        apply_speeds(&mut monster, speed, &mut notes);
+    }
+
+    let text = |key: &str| map.get(key).and_then(Value::as_str).unwrap_or("").trim();
+    if !text("size").is_empty() {
+        apply_size(&mut monster, text("size"), &mut notes);
+    }
+    apply_creature_type(&mut monster, text("type"));
+    monster.subtypes = split_text_list(text("subtype"));
+    monster.alignment = text("alignment").to_string();
+    apply_senses_text(&mut monster, text("senses"), &mut notes);
+    monster.languages = split_text_list(text("languages"));
+
+    for (i, (name, _)) in ABILITIES.iter().enumerate() {
+        if let Some(bonus) = map.get(&format!("{}_save", name)).and_then(Value::as_i64) {
+            apply_save(&mut monster, i, name, Some(bonus as i32), &mut notes);
+        }
+    }
+
+    let damage_text = |key: &str| map.get(key).and_then(Value::as_str).unwrap_or("").to_string();
+    let mut conditions = Vec::new();
+    monster.vulnerabilities = damage_types_from_text(&damage_text("damage_vulnerabilities"), "vulnerabilities", &mut conditions, &mut notes);
+    monster.resistances = damage_types_from_text(&damage_text("damage_resistances"), "resistances", &mut conditions, &mut notes);
+    monster.immunities = damage_types_from_text(&damage_text("damage_immunities"), "immunities", &mut conditions, &mut notes);
+    for name in damage_text("condition_immunities").split(',') {
+        push_condition(&mut conditions, name, &mut notes);
+    }
+    monster.condition_immunities = conditions;
+
+    let mut abilities: Vec<String> = open5e_named_list(map.get("special_abilities"))
+        .into_iter()
+        .map(|(name, desc)| format!("{}. {}", name, desc))
+        .collect();
+
+    for (name, desc) in open5e_named_list(map.get("actions")) {
+        add_action(&mut monster, &mut abilities, &name, &desc, ActionCategory::Action, &mut notes);
+    }
+
+    for (key, category) in [
+        ("bonus_actions", ActionCategory::BonusAction),
+        ("reactions", ActionCategory::Reaction),
+        ("legendary_actions", ActionCategory::Legendary),
+    ] {
+        let mut texts = Vec::new();
+        for (name, desc) in open5e_named_list(map.get(key)) {
+            add_action(&mut monster, &mut texts, &name, &desc, category, &mut notes);
+        }
+        push_section_text(&mut abilities, category, texts);
+    }
+
+    if let Some(skills) = map.get("skills").and_then(Value::as_object) {
+        for (skill, bonus) in skills {
+            apply_skill(&mut monster, skill, bonus.as_i64().map(|b| b as i32), &mut notes);
+        }
+    }
+
+    monster.abilities = abilities.join("\n\n");
+    apply_magic_weapons(&mut monster);
+
+    ImportedMonster { monster, notes }
+}
+
+/// Reads Open5e's lists of `{"name": ..., "desc": ...}` objects.
+fn open5e_named_list(value: Option<&Value>) -> Vec<(String, String)> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.get("name")?.as_str()?.to_string();
+            let desc = entry.get("desc").and_then(Value::as_str).unwrap_or("").to_string();
+            Some((name, desc))
+        })
+        .collect()
+}
+
+// =========================================================================
+// Plain Text Stat Blocks
+// =========================================================================
+
+/// Header lines of a stat block, matched at the start of a line (longest first where they overlap).
+const HEADER_KEYS: [&str; 20] = [
+    "Armor Class",
+    "AC",
+    "Hit Points",
+    "HP",
+    "Speed",
+    "Saving Throws",
+    "Skills",
+    "Damage Vulnerabilities",
+    "Damage Resistances",
+    "Damage Immunities",
+    "Condition Immunities",
+    "Vulnerabilities",
+    "Resistances",
+    "Immunities",
+    "Senses",
+    "Languages",
+    "Challenge",
+    "CR",
+    "Proficiency Bonus",
+    "Initiative",
+];
+
+/// Section headings that split traits from the different kinds of actions.
+const SECTION_HEADINGS: [&str; 7] = [
+    "Traits",
+    "Actions",
+    "Bonus Actions",
+    "Reactions",
+    "Legendary Actions",
+    "Mythic Actions",
+    "Lair Actions",
+];
+
+/// Parses a stat block pasted as plain text (e.g. copied out of a PDF). The first line is taken as the name.
+pub fn import_stat_block_text(text: &str) -> io::Result<ImportedMonster> {
+    let lines = join_wrapped_lines(text);
+    let Some(name) = lines.first() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "The stat block is empty"));
+    };
+
+    let mut monster = Monster {
+        schema_version: monster_manager::MONSTER_SCHEMA_VERSION,
+        name: name.clone(),
+        hitdie: "d8".to_string(),
+        ..Default::default()
+    };
+    let mut notes = Vec::new();
+
+    // Header fields are collected first because saves need the PB from the challenge line
+    let mut header: Vec<(&str, String)> = Vec::new();
+    let mut body_start = lines.len();
+    let mut ability_tokens: Vec<String> = Vec::new();
+    let mut in_abilities = false;
+
+    for (i, line) in lines.iter().enumerate().skip(1) {
+        if SECTION_HEADINGS.iter().any(|h| line.eq_ignore_ascii_case(h)) {
+            body_start = i;
+            break;
+        }
+        if let Some(key) = header_key(line) {
+            in_abilities = false;
+            let value = line[key.len()..].trim().to_string();
+            let is_challenge = key == "Challenge" || key == "CR";
+            header.push((key, value));
+            if is_challenge {
+                body_start = i + 1;
+                break;
+            }
+            continue;
+        }
+        // Without a challenge line the traits start right after the ability scores
+        let has_scores = ability_tokens.iter().filter(|t| is_ability_score(t)).count() >= 6;
+        if has_scores && entry_name(line).is_some() {
+            body_start = i;
+            break;
+        }
+        if line.split_whitespace().next().is_some_and(|w| w.eq_ignore_ascii_case("str")) {
+            in_abilities = true;
+        }
+        if in_abilities {
+            ability_tokens.extend(line.split_whitespace().map(str::to_string));
+        } else if i == 1 && monster_manager::SIZES.iter().any(|size| line.starts_with(size)) {
+            apply_type_line(&mut monster, line, &mut notes);
+        }
+    }
+
+    let header_value = |key: &str| header.iter().find(|(k, _)| *k == key).map(|(_, v)| v.as_str());
+
+    if let Some(challenge) = header_value("Challenge").or(header_value("CR")) {
+        let cr = challenge.split_whitespace().next().unwrap_or("");
+        apply_cr(&mut monster, cr, &mut notes);
+        // The XP in the block wins over the table, e.g. for monsters fought in their lair.
+        // The rating itself is skipped so "CR 2 (XP 450)" doesn't read as 2 XP.
+        let words: Vec<&str> = challenge
+            .split(|c: char| c.is_whitespace() || "();".contains(c))
+            .filter(|w| !w.is_empty())
+            .skip(1)
+            .collect();
+        if let Some(exp) = words.windows(2).find_map(|pair| match pair {
+            [n, "XP"] | ["XP", n] => n.replace(',', "").parse::<i32>().ok(),
+            _ => None,
+        }) {
+            monster.exp = exp;
+        }
+    } else {
+        notes.push("No challenge rating found; XP and PB left at 0.".to_string());
+    }
+    if let Some(pb) = header_value("Proficiency Bonus").and_then(parse_signed).or_else(|| {
+        header_value("CR").and_then(|cr| cr.find("PB").and_then(|i| parse_signed(&cr[i + 2..])))
+    }) {
+        monster.pb = pb;
+    }
+
+    monster.ac = header_value("Armor Class").or(header_value("AC")).and_then(parse_signed).unwrap_or(0);
+    if let Some(hp) = header_value("Hit Points").or(header_value("HP")) {
+        monster.hp = parse_signed(hp).unwrap_or(0);
+        let formula = hp.split_once('(').and_then(|(_, rest)| rest.split_once(')')).map(|(dice, _)| dice);
+        apply_hit_dice(&mut monster, formula, &mut notes);
+    }
+    if let Some(speed) = header_value("Speed") {
+        apply_speed_text(&mut monster, speed, &mut notes);
+    }
+    if let Some(senses) = header_value("Senses") {
+        apply_senses_text(&mut monster, senses, &mut notes);
+    }
+    if let Some(languages) = header_value("Languages") {
+        monster.languages = split_text_list(languages);
+    }
+
+    apply_ability_tokens(&mut monster, &ability_tokens, &mut notes);
+
+    if let Some(saves) = header_value("Saving Throws") {
+        for part in saves.split(',') {
+            let mut words = part.split_whitespace();
+            let ability = words.next().unwrap_or("").to_lowercase();
+            if let Some(prefix) = ability.get(..3)
+                && let Some(index) = ABILITIES.iter().position(|(name, _)| name.starts_with(prefix))
+            {
+                apply_save(&mut monster, index, ABILITIES[index].0, parse_signed(words.next().unwrap_or("")), &mut notes);
+            }
+        }
+    }
+
+    for (keys, label, list) in [
+        (["Damage Vulnerabilities", "Vulnerabilities"], "vulnerabilities", &mut monster.vulnerabilities),
+        (["Damage Resistances", "Resistances"], "resistances", &mut monster.resistances),
+        (["Damage Immunities", "Immunities"], "immunities", &mut monster.immunities),
+    ] {
+        if let Some(text) = header_value(keys[0]).or(header_value(keys[1])) {
+            *list = damage_types_from_text(text, label, &mut monster.condition_immunities, &mut notes);
+        }
+    }
+    if let Some(text) = header_value("Condition Immunities") {
+        for name in text.split(',') {
+            push_condition(&mut monster.condition_immunities, name, &mut notes);
+        }
+    }
+
+    if let Some(skills) = header_value("Skills") {
+        for entry in skills.split(',') {
+            let entry = entry.trim();
+            match entry.rsplit_once(' ') {
+                Some((skill, bonus)) => apply_skill(&mut monster, skill, parse_signed(bonus), &mut notes),
+                None => apply_skill(&mut monster, entry, None, &mut notes),
+            }
+        }
+    }
+
+    let ignored: Vec<&str> = ["Initiative"]
+        .into_iter()
+        .filter(|key| header_value(key).is_some())
+        .collect();
+    if !ignored.is_empty() {
+        notes.push(format!("Not imported: {}.", ignored.join(", ").to_lowercase()));
+    }
+
+    // Everything after the header is a list of "Name. Description" entries split into sections
+    let mut abilities = Vec::new();
+    let mut section = "Traits";
+    let mut entries: Vec<(&str, String, String)> = Vec::new();
+    for line in &lines[body_start.min(lines.len())..] {
+        if let Some(heading) = SECTION_HEADINGS.iter().find(|h| line.eq_ignore_ascii_case(h)) {
+            section = heading;
+            continue;
+        }
+        match (entry_name(line), entries.last_mut()) {
+            (Some(name), _) => {
+                let description = line[name.len()..].trim_start_matches('.').trim().to_string();
+                entries.push((section, name.to_string(), description));
+            }
+            (None, Some((_, _, description))) => {
+                description.push(' ');
+                description.push_str(line);
+            }
+            (None, None) => abilities.push(line.clone()),
+        }
+    }
+
+    // Entries that aren't attacks stay in the ability text under their section heading
+    let mut noted_sections: Vec<&str> = Vec::new();
+    let mut section_texts: Vec<(ActionCategory, Vec<String>)> = Vec::new();
+    for (entry_section, name, description) in &entries {
+        let category = ActionCategory::ALL.iter().copied().find(|c| c.heading() == *entry_section);
+        match (*entry_section, category) {
+            ("Traits", _) => abilities.push(format!("{}. {}", name, description)),
+            (_, Some(ActionCategory::Action)) => {
+                add_action(&mut monster, &mut abilities, name, description, ActionCategory::Action, &mut notes);
+            }
+            (_, Some(category)) => {
+                if !section_texts.iter().any(|(c, _)| *c == category) {
+                    section_texts.push((category, Vec::new()));
+                }
+                if let Some((_, texts)) = section_texts.iter_mut().find(|(c, _)| *c == category) {
+                    add_action(&mut monster, texts, name, description, category, &mut notes);
+                }
+            }
+            (other, None) => {
+                if !noted_sections.contains(&other) {
+                    noted_sections.push(other);
+                    abilities.push(format!("{}:", other));
+                    notes.push(format!("{} were added to the ability text only.", other));
+                }
+                abilities.push(format!("{}. {}", name, description));
+            }
+        }
+    }
+    for (category, texts) in section_texts {
+        push_section_text(&mut abilities, category, texts);
+    }
+    monster.abilities = abilities.join("\n\n");
+    apply_magic_weapons(&mut monster);
+
+    Ok(ImportedMonster { monster, notes })
+}
+
+/// Cleans up PDF copy: unifies dashes, drops blank lines and glues wrapped lines back together.
+/// A line continues the previous one when it starts in lowercase or the previous one ends mid-list.
+fn join_wrapped_lines(text: &str) -> Vec<String> {
+    let cleaned = text.replace(['\u{2212}', '\u{2013}', '\u{2014}'], "-").replace('\u{2019}', "'");
+    let mut lines: Vec<String> = Vec::new();
+    for line in cleaned.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let continues = line.chars().next().is_some_and(char::is_lowercase)
+            || lines.last().is_some_and(|prev| prev.ends_with(',') || prev.ends_with(" and") || prev.ends_with(" or"));
+        match lines.last_mut() {
+            Some(prev) if continues => {
+                prev.push(' ');
+                prev.push_str(line);
+            }
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Returns the header key a line starts with, if any.
+fn header_key(line: &str) -> Option<&'static str> {
+    HEADER_KEYS.iter().copied().find(|key| {
+        line.len() > key.len()
+            && line.is_char_boundary(key.len())
+            && line[..key.len()].eq_ignore_ascii_case(key)
+            && line[key.len()..].starts_with(' ')
+    })
+}
+
+/// Returns the name of a "Name. Description" entry, e.g. "Fire Breath (Recharge 5-6)".
+fn entry_name(line: &str) -> Option<&str> {
+    let name = &line[..line.find(". ").or_else(|| line.strip_suffix('.').map(str::len))?];
+    let words: Vec<&str> = name.split_whitespace().collect();
+    let connectors = ["of", "the", "and", "or", "with", "in", "a", "an", "to", "from", "per"];
+    let looks_like_name = !words.is_empty()
+        && words.len() <= 8
+        && !name.contains(':')
+        && header_key(line).is_none()
+        && words.iter().all(|word| {
+            word.starts_with(|c: char| c.is_uppercase() || c.is_ascii_digit() || c == '(')
+                || connectors.contains(&word.to_lowercase().as_str())
+        });
+    looks_like_name.then_some(name)
+}
+
+/// Scores are the bare numbers of the ability block; modifiers and saves carry a sign or parentheses.
+fn is_ability_score(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Reads the ability score block. Handles "STR DEX ... / 8 (-1) 14 (+2) ...", one score per line,
+/// and the newer "Str 8 -1 -1" layout where a save different from the modifier means proficiency.
+fn apply_ability_tokens(monster: &mut Monster, tokens: &[String], notes: &mut Vec<String>) {
+    let scores: Vec<i32> = tokens.iter().filter(|t| is_ability_score(t)).filter_map(|t| t.parse().ok()).take(6).collect();
+    if scores.len() < 6 {
+        notes.push("Couldn't find all six ability scores; they were left at 10.".to_string());
+        return;
+    }
+    for (i, score) in scores.iter().enumerate() {
+        monster.scores[i] = *score;
+    }
+
+    for (i, (_, short)) in ABILITIES.iter().enumerate() {
+        let Some(position) = tokens.iter().position(|t| t.eq_ignore_ascii_case(short)) else {
+            continue;
+        };
+        let following: Vec<Option<i32>> = tokens.iter().skip(position + 1).take(3).map(|t| parse_signed(t)).collect();
+        if let [Some(_), Some(modifier), Some(save)] = following[..]
+            && tokens[position + 2].starts_with(['+', '-'])
+            && tokens[position + 3].starts_with(['+', '-'])
+            && save != modifier
+        {
+            monster.saves[i] = true;
+        }
+    }
+}
+
+// =========================================================================
+// Shared Conversion Helpers
+// =========================================================================
+
+fn new_monster(map: &Map<String, Value>) -> Monster {
+    Monster {
+        schema_version: monster_manager::MONSTER_SCHEMA_VERSION,
+        name: map.get("name").and_then(Value::as_str).unwrap_or_default().trim().to_string(),
+        hitdie: "d8".to_string(),
+        scores: [10; 6],
+        ..Default::default()
+    }
+}
+
+/// Parses "+5" or "-1" (ignoring anything after the number).
+fn parse_signed(text: &str) -> Option<i32> {
+    let text = text.trim();
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, text.trim_start_matches('+')),
+    };
+    let digits: String = digits.trim_start().chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse::<i32>().ok().map(|n| n * sign)
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
+
+fn push_damage_type(
+    types: &mut Vec<DamageModifier>,
+    name: &str,
+    qualifier: DamageQualifier,
+    label: &str,
+    notes: &mut Vec<String>
+) {
+    let name = capitalize(name.trim().trim_end_matches(|c: char| !c.is_alphabetic()));
+    if name.is_empty() || types.iter().any(|t| t.damage_type == name && t.qualifier == qualifier) {
+        return;
+    }
+    let damage_type = match monster_manager::find_damage_type(&name) {
+        Some(known) => known,
+        None => {
+            notes.push(format!("Unknown damage type in {}: {}", label, name));
+            name
+        }
+    };
+    types.push(DamageModifier { damage_type, qualifier });
+}
+
+/// Adds a condition immunity, keeping the SRD spelling when the condition is a known one.
+fn push_condition(conditions: &mut Vec<String>, name: &str, notes: &mut Vec<String>) {
+    let name = capitalize(name.trim().trim_end_matches(|c: char| !c.is_alphabetic()));
+    if name.is_empty() || conditions.contains(&name) {
+        return;
+    }
+    if !monster_manager::CONDITIONS.contains(&name.as_str()) {
+        notes.push(format!("Unknown condition in condition immunities: {}", name));
+    }
+    conditions.push(name);
+}
+
+/// Recognises "from nonmagical attacks (that aren't silvered/adamantine)" style conditions.
+fn damage_qualifier(text: &str) -> Option<DamageQualifier> {
+    let lower = text.to_lowercase();
+    if !lower.contains("nonmagical") {
+        None
+    } else if lower.contains("silvered") {
+        Some(DamageQualifier::NonmagicalNotSilvered)
+    } else if lower.contains("adamantine") {
+        Some(DamageQualifier::NonmagicalNotAdamantine)
+    } else {
+        Some(DamageQualifier::Nonmagical)
+    }
+}
+
+/// Parses comma separated damage lists as written in stat blocks, e.g.
+/// "fire; bludgeoning, piercing, and slashing from nonmagical attacks". Groups made only of
+/// conditions, which newer stat blocks put after a ";" in the immunities line, go to `conditions`.
+fn damage_types_from_text(
+    text: &str,
+    label: &str,
+    conditions: &mut Vec<String>,
+    notes: &mut Vec<String>
+) -> Vec<DamageModifier> {
+    let mut types = Vec::new();
+
+    for group in text.split(';').map(str::trim).filter(|g| !g.is_empty()) {
+        let clean = |word: &str| capitalize(word.trim_end_matches(','));
+        let is_damage_type = |word: &str| monster_manager::SRD_DAMAGE_TYPES.contains(&clean(word).as_str());
+        if !group.split_whitespace().any(is_damage_type) {
+            let names: Vec<&str> = group.split(',').map(|n| n.trim().trim_start_matches("and ")).collect();
+            if names.iter().all(|n| monster_manager::CONDITIONS.contains(&capitalize(n).as_str())) {
+                for name in names {
+                    push_condition(conditions, name, notes);
+                }
+            } else {
+                notes.push(format!("Not imported from {}: {}", label, group));
+            }
+            continue;
+        }
+
+        let qualifier = damage_qualifier(group);
+        let mut conditional = false;
+        for part in group.split(',') {
+            let words: Vec<&str> = part.split_whitespace().filter(|w| *w != "and").collect();
+            if let Some(first) = words.first() {
+                push_damage_type(&mut types, first, qualifier.unwrap_or_default(), label, notes);
+                conditional |= words.len() > 1;
+            }
+        }
+        if conditional && qualifier.is_none() {
+            notes.push(format!("Conditional {} were imported without their condition: {}", label, group));
+        }
+    }
+    types
+}
+
+fn apply_cr(monster: &mut Monster, cr: &str, notes: &mut Vec<String>) {
+    match monster_manager::cr_stats(cr) {
+        Some((exp, pb)) => {
+            monster.cr = cr.trim().to_string();
+            monster.exp = exp;
+            monster.pb = pb;
+        }
+        None => notes.push(format!("Unknown challenge rating \"{}\"; XP and PB left at 0.", cr)),
+    }
+}
+
+fn apply_hit_dice(monster: &mut Monster, formula: Option<&str>, notes: &mut Vec<String>) {
+    let Some(formula) = formula else {
+        return;
+    };
+    match monster_manager::parse_dice_expression(formula) {
+        Some((_, die, _)) if ["d4", "d6", "d8", "d10", "d12", "d20"].contains(&die.as_str()) => monster.hitdie = die,
+        _ => notes.push(format!("Couldn't read the hit dice \"{}\".", formula)),
+    }
+}
+
+fn apply_speeds(monster: &mut Monster, speeds: &Map<String, Value>, notes: &mut Vec<String>) {
+    let speed_value = |value: &Value| match value {
+        Value::Object(speed) => speed.get("number").and_then(Value::as_i64),
+        other => other.as_i64(),
+    };
+
+    // 5e.tools marks hovering with "canHover" or a "(hover)" condition, Open5e with "hover": true
+    let mut others = Vec::new();
+    for (kind, value) in speeds {
+        if kind == "canHover" || kind == "hover" {
+            monster.speeds.hover |= value.as_bool().unwrap_or(false);
+            continue;
+        }
+        let Some(feet) = speed_value(value) else {
+            continue;
+        };
+        if value.get("condition").and_then(Value::as_str).is_some_and(|c| c.contains("hover")) {
+            monster.speeds.hover = true;
+        }
+        match speed_field(monster, kind) {
+            Some(field) => *field = feet as i32,
+            None if feet > 0 => others.push(format!("{} {} ft", kind, feet)),
+            None => {}
+        }
+    }
+    if !others.is_empty() {
+        notes.push(format!("Not imported: {} speed.", others.join(", ")));
+    }
+}
+
+/// The speed field for a movement kind such as "fly", if we store it.
+fn speed_field<'a>(monster: &'a mut Monster, kind: &str) -> Option<&'a mut i32> {
+    match kind.to_lowercase().as_str() {
+        "walk" => Some(&mut monster.speeds.walk),
+        "fly" => Some(&mut monster.speeds.fly),
+        "swim" => Some(&mut monster.speeds.swim),
+        "climb" => Some(&mut monster.speeds.climb),
+        "burrow" => Some(&mut monster.speeds.burrow),
+        _ => None,
+    }
+}
+
+/// Reads a stat block speed line, e.g. "30 ft., fly 60 ft. (hover), swim 30 ft.".
+fn apply_speed_text(monster: &mut Monster, text: &str, notes: &mut Vec<String>) {
+    for part in text.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let kind = part.split_whitespace().next().filter(|w| !w.starts_with(|c: char| c.is_ascii_digit())).unwrap_or("walk");
+        let feet = part.split_whitespace().find_map(parse_signed);
+        match (speed_field(monster, kind), feet) {
+            (Some(field), Some(feet)) => *field = feet,
+            _ => notes.push(format!("Not imported from speed: {}", part)),
+        }
+        if part.contains("hover") {
+            monster.speeds.hover = true;
+        }
+    }
+}
+
+/// Reads a size, matching it against the known sizes.
+fn apply_size(monster: &mut Monster, size: &str, notes: &mut Vec<String>) {
+    match monster_manager::SIZES.iter().find(|s| s.eq_ignore_ascii_case(size)) {
+        Some(size) => monster.size = size.to_string(),
+        None => notes.push(format!("Unknown size \"{}\".", size)),
+    }
+}
+
+/// Known creature types get their usual spelling; homebrew ones are kept as written.
+fn apply_creature_type(monster: &mut Monster, creature_type: &str) {
+    let creature_type = creature_type.trim();
+    monster.creature_type = monster_manager::CREATURE_TYPES
+        .iter()
+        .find(|t| t.eq_ignore_ascii_case(creature_type))
+        .map_or_else(|| capitalize(creature_type), |t| t.to_string());
+}
+
+/// Reads the line under a stat block's name, e.g. "Medium humanoid (goblinoid), neutral evil".
+fn apply_type_line(monster: &mut Monster, line: &str, notes: &mut Vec<String>) {
+    let (description, alignment) = line.split_once(',').unwrap_or((line, ""));
+    monster.alignment = alignment.trim().to_string();
+
+    let (description, subtypes) = match description.split_once('(') {
+        Some((description, rest)) => (description, rest.trim_end_matches(')')),
+        None => (description, ""),
+    };
+    monster.subtypes = split_text_list(subtypes);
+
+    // "Medium or Small humanoid" keeps the first size
+    let mut words = description.split_whitespace().peekable();
+    apply_size(monster, words.next().unwrap_or(""), notes);
+    if words.peek() == Some(&"or") {
+        words.nth(1);
+    }
+    let creature_type: Vec<&str> = words.collect();
+    apply_creature_type(monster, &creature_type.join(" "));
+}
+
+/// Reads senses as written in stat blocks, e.g. "darkvision 60 ft., passive Perception 9".
+/// Passive Perception is skipped since it comes from the Perception skill.
+fn apply_senses_text(monster: &mut Monster, text: &str, notes: &mut Vec<String>) {
+    for part in text.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        if part.to_lowercase().starts_with("passive perception") {
+            continue;
+        }
+        let name = part.split_whitespace().next().unwrap_or("");
+        let range = part.split_whitespace().find_map(parse_signed);
+        match (monster_manager::SENSES.iter().find(|s| s.eq_ignore_ascii_case(name)), range) {
+            (Some(sense), Some(range)) => {
+                monster.senses.retain(|s| s.sense != *sense);
+                monster.senses.push(monster_manager::Sense { sense: sense.to_string(), range });
+                if part.contains('(') {
+                    notes.push(format!("Imported without its condition: {}", part));
+                }
+            }
+            _ => notes.push(format!("Not imported from senses: {}", part)),
+        }
+    }
+}
+
+/// Splits a comma separated stat block list such as languages. A dash means none.
+fn split_text_list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty() && !["-", "—", "–"].contains(item))
+        .map(str::to_string)
+        .collect()
+}
+
+fn apply_save(monster: &mut Monster, index: usize, ability: &str, bonus: Option<i32>, notes: &mut Vec<String>) {
+    monster.saves[index] = true;
+    let expected = monster.ability_mod(index) + monster.pb;
+    if let Some(bonus) = bonus
+        && bonus != expected
+    {
+        notes.push(format!(
+            "{} save is {:+} in the source but will roll as {:+} (modifier + PB).",
+            capitalize(ability),
+            bonus,
+            expected
+        ));
+    }
+}
+
+/// Marks a skill as proficient, or expertise when the bonus includes PB twice.
+fn apply_skill(monster: &mut Monster, skill: &str, bonus: Option<i32>, notes: &mut Vec<String>) {
+    let skill = skill.trim().replace('_', " ");
+    let Some(&(name, ability)) = monster_manager::SKILLS.iter().find(|(name, _)| name.eq_ignore_ascii_case(&skill)) else {
+        notes.push(format!("Unknown skill \"{}\" was not imported.", skill));
+        return;
+    };
+    let expertise = monster.pb > 0 && bonus.is_some_and(|b| b >= monster.ability_mod(ability) + 2 * monster.pb);
+    monster.skills.push(monster_manager::SkillProficiency { skill: name.to_string(), expertise });
+
+    let expected = monster.skill_bonus(name);
+    if let Some(bonus) = bonus
+        && bonus != expected
+    {
+        notes.push(format!("{} is {:+} in the source but will roll as {:+}.", name, bonus, expected));
+    }
+}
+
+/// Turns an action into an `Attack` when it has a to-hit bonus or save DC plus damage dice.
+/// Anything else is kept as ability text.
+fn add_action(
+    monster: &mut Monster,
+    abilities: &mut Vec<String>,
+    name: &str,
+    text: &str,
+    category: ActionCategory,
+    notes: &mut Vec<String>
+) {
+    match parse_action(monster, name, text) {
+        Some((attack, attack_notes)) => {
+            notes.extend(attack_notes);
+            monster.attacks.push(Attack { category, ..attack });
+        }
+        None => {
+            if name.eq_ignore_ascii_case("multiattack") {
+                notes.push("Multiattack was added to the ability text only.".to_string());
+            }
+            abilities.push(format!("{}. {}", name, text));
+        }
+    }
+}
+
+/// Reads an action like "Melee Weapon Attack: +5 to hit, reach 5 ft., one target. Hit: 7 (1d8 + 3) slashing damage."
+/// or "... DC 13 Dexterity saving throw, taking 10 (3d6) fire damage on a failed save".
+fn parse_action(monster: &Monster, name: &str, text: &str) -> Option<(Attack, Vec<String>)> {
+    let lower = text.to_lowercase();
+    let mut notes = Vec::new();
+
+    let to_hit = ["attack roll:", "attack:"]
+        .iter()
+        .find_map(|marker| lower.find(marker).map(|i| &lower[i + marker.len()..]))
+        .and_then(parse_signed);
+    let save_dc = lower.find("dc ").and_then(|i| parse_signed(&lower[i + 3..]));
+    if to_hit.is_none() && save_dc.is_none() {
+        return None;
+    }
+
+    // The damage dice are the first "(XdY + Z)" of the text
+    let (dice_start, dice_end) = lower
+        .match_indices('(')
+        .filter_map(|(i, _)| lower[i..].find(')').map(|end| (i + 1, i + end)))
+        .find(|&(start, end)| monster_manager::parse_dice_expression(&lower[start..end]).is_some())?;
+    let (num_dice, dice_used, damage_bonus) = monster_manager::parse_dice_expression(&lower[dice_start..dice_end])?;
+
+    let after_dice = &lower[dice_end + 1..];
+    let damage_type = after_dice
+        .split_whitespace()
+        .next()
+        .map(|word| capitalize(word.trim_end_matches(|c: char| !c.is_alphabetic())))
+        .filter(|word| monster_manager::SRD_DAMAGE_TYPES.contains(&word.as_str()))
+        .unwrap_or_default();
+    if after_dice.contains("plus") && after_dice.contains("damage") {
+        notes.push(format!("{}: extra damage after the first damage roll was not imported.", name));
+    }
+
+    let is_save = to_hit.is_none();
+    let mut save_ability = String::new();
+    let ability_index = if is_save {
+        let dc = save_dc.unwrap_or(0);
+        let index = [2, 0, 1, 4, 3, 5].into_iter().find(|&i| 8 + monster.ability_mod(i) + monster.pb == dc);
+        if index.is_none() {
+            notes.push(format!("{}: no ability gives DC {} with this PB; using Con.", name, dc));
+        }
+        match ABILITIES.iter().find(|(ability, _)| lower.contains(&format!("{} saving throw", ability))) {
+            Some((_, short)) => save_ability = short.to_string(),
+            None => notes.push(format!("{}: the saving throw ability wasn't found; targets will save with Dex.", name)),
+        }
+        let index = index.unwrap_or(2);
+        // Breath weapons and spell-like saves have no "+ Z"; a bonus that isn't the modifier can't be kept
+        if damage_bonus != 0 && damage_bonus != monster.ability_mod(index) {
+            notes.push(format!("{}: damage bonus {:+} in the source will roll as {:+}.", name, damage_bonus, monster.ability_mod(index)));
+        }
+        index
+    } else {
+        let to_hit = to_hit.unwrap_or(0);
+        let ranged = lower.contains("ranged") && !lower.contains("melee");
+        let order = if ranged { [1, 0, 2, 3, 4, 5] } else { [0, 1, 2, 3, 4, 5] };
+        let matches_hit = |i: usize| monster.ability_mod(i) + monster.pb == to_hit;
+        let matches_damage = |i: usize| monster.ability_mod(i) == damage_bonus;
+
+        if let Some(i) = order.into_iter().find(|&i| matches_hit(i) && matches_damage(i)) {
+            i
+        } else if let Some(i) = order.into_iter().find(|&i| matches_damage(i)) {
+            notes.push(format!("{}: to-hit {:+} in the source will roll as {:+}.", name, to_hit, monster.ability_mod(i) + monster.pb));
+            i
+        } else if let Some(i) = order.into_iter().find(|&i| matches_hit(i)) {
+            notes.push(format!("{}: damage bonus {:+} in the source will roll as {:+}.", name, damage_bonus, monster.ability_mod(i)));
+            i
+        } else {
+            notes.push(format!("{}: to-hit and damage bonus don't match any ability; using {}.", name, ABILITIES[order[0]].1));
+            order[0]
+        }
+    };
+
+    let attack = Attack {
+        attack_name: name.to_string(),
+        ability_used: ABILITIES[ability_index].1.to_string(),
+        dice_used,
+        num_dice,
+        num_attacks: 1,
+        saving_throw: is_save,
+        save_ability,
+        on_save: if !is_save || lower.contains("half as much") {
+            SaveSuccess::HalfDamage
+        } else {
+            SaveSuccess::NoDamage
+        },
+        add_mod_to_damage: is_save && damage_bonus != 0,
+        material: WeaponMaterial::default(),
+        damage_type,
+        category: ActionCategory::Action,
+        reach: lower.find("reach ").and_then(|i| parse_signed(&lower[i + 6..])).unwrap_or(0),
+        range: lower.find("range ").and_then(|i| parse_signed(&lower[i + 6..])).unwrap_or(0),
+        long_range: lower
+            .find("range ")
+            .and_then(|i| lower[i..].split_whitespace().nth(1))
+            .and_then(|ranges| ranges.split_once('/'))
+            .and_then(|(_, long)| parse_signed(long))
+            .unwrap_or(0),
+        area: parse_area(&lower),
+        rider: if is_save { None } else { parse_rider(&lower[dice_end..]) },
+    };
+    Some((attack, notes))
+}
+
+/// Reads an area such as "60-foot cone", "20-foot-radius sphere" or "line that is 30 feet long".
+fn parse_area(text: &str) -> Option<AreaTemplate> {
+    if let Some(i) = text.find("line that is ") {
+        let size = parse_signed(&text[i + 13..])?;
+        return Some(AreaTemplate { shape: AreaShape::Line, size });
+    }
+    AreaShape::ALL.iter().find_map(|shape| {
+        let i = text.find(&shape.label().to_lowercase())?;
+        let before = text[..i].trim_end();
+        let before = before.strip_suffix("-radius").unwrap_or(before);
+        let before = before
+            .strip_suffix("-foot")
+            .or_else(|| before.strip_suffix(" feet"))
+            .or_else(|| before.strip_suffix(" foot"))?;
+        let digits: String = before.chars().rev().take_while(|c| c.is_ascii_digit()).collect();
+        let size = digits.chars().rev().collect::<String>().parse().ok()?;
+        Some(AreaTemplate { shape: *shape, size })
+    })
+}
+
+/// Reads an on-hit save after the damage, such as "... the target must succeed on a
+/// DC 13 Constitution saving throw or be poisoned for 1 minute."
+fn parse_rider(text: &str) -> Option<AttackRider> {
+    let dc_start = text.find("dc ")?;
+    let dc = parse_signed(&text[dc_start + 3..])?;
+    let rest = &text[dc_start..];
+    let sentence = rest.split_once(". ").map_or(rest, |(first, _)| first);
+    let (_, save_ability) = ABILITIES
+        .iter()
+        .find(|(ability, _)| sentence.contains(&format!("{} saving throw", ability)))?;
+
+    let condition = monster_manager::CONDITIONS
+        .iter()
+        .find(|condition| sentence.contains(&condition.to_lowercase()))
+        .map(|condition| condition.to_string())
+        .unwrap_or_default();
+
+    let (extra_damage, damage_type) = sentence
+        .match_indices('(')
+        .filter_map(|(i, _)| sentence[i..].find(')').map(|end| (i + 1, i + end)))
+        .find(|&(start, end)| monster_manager::parse_dice_expression(&sentence[start..end]).is_some())
+        .map(|(start, end)| {
+            let damage_type = sentence[end + 1..]
+                .split_whitespace()
+                .next()
+                .map(|word| capitalize(word.trim_end_matches(|c: char| !c.is_alphabetic())))
+                .filter(|word| monster_manager::SRD_DAMAGE_TYPES.contains(&word.as_str()))
+                .unwrap_or_default();
+            (sentence[start..end].replace(' ', ""), damage_type)
+        })
+        .unwrap_or_default();
+
+    let duration = [" for ", " until "]
+        .iter()
+        .find_map(|marker| {
+            sentence.rfind(marker).map(|i| {
+                let phrase = sentence[i + 1..].trim_end_matches('.');
+                phrase.strip_prefix("for ").unwrap_or(phrase).to_string()
+            })
+        })
+        .unwrap_or_default();
+
+    Some(AttackRider {
+        save_ability: save_ability.to_string(),
+        dc,
+        condition,
+        extra_damage,
+        damage_type,
+        half_on_success: sentence.contains("half as much"),
+        duration,
+    })
+}
+
+/// Marks the weapon attacks magical when a trait such as "Magic Weapons" says they are.
+fn apply_magic_weapons(monster: &mut Monster) {
+    if monster.abilities.to_lowercase().contains("weapon attacks are magical") {
+        for attack in monster.attacks.iter_mut().filter(|a| !a.saving_throw) {
+            attack.material = WeaponMaterial::Magical;
+        }
+    }
+}
+
+/// Adds the bonus action, reaction or legendary entries that didn't become attacks under their heading.
+fn push_section_text(abilities: &mut Vec<String>, category: ActionCategory, texts: Vec<String>) {
+    if !texts.is_empty() {
+        abilities.push(format!("{}:", category.heading()));
+        abilities.extend(texts);
+    }
+}
+
+// =========================================================================
+// Tests
+// =========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOBLIN: &str = "Goblin
+Small humanoid (goblinoid), neutral evil
+Armor Class 15 (leather armor, shield)
+Hit Points 7 (2d6)
+Speed 30 ft.
+STR DEX CON INT WIS CHA
+8 (-1) 14 (+2) 10 (+0) 10 (+0) 8 (-1) 8 (-1)
+Skills Stealth +6
+Senses darkvision 60 ft., passive Perception 9
+Languages Common, Goblin
+Challenge 1/4 (50 XP)
+Nimble Escape. The goblin can take the Disengage or Hide action as a bonus action on each of its
+turns.
+Actions
+Scimitar. Melee Weapon Attack: +4 to hit, reach 5 ft., one target. Hit: 5 (1d6 + 2) slashing damage.
+Shortbow. Ranged Weapon Attack: +4 to hit, range 80/320 ft., one target. Hit: 5 (1d6 + 2) piercing damage.";
+
+    const BANDIT_CAPTAIN_2024: &str = "Bandit Captain
+Medium Humanoid, Neutral
+AC 15
+HP 52 (8d8 + 16)
+Speed 30 ft.
+Str 15 +2 +4
+Dex 16 +3 +5
+Con 14 +2 +2
+Int 14 +2 +2
+Wis 11 +0 +2
+Cha 14 +2 +2
+Skills Athletics +4, Deception +4
+Senses Passive Perception 10
+Languages Common, Thieves' Cant
+CR 2 (XP 450; PB +2)
+Actions
+Scimitar. Melee Attack Roll: +5, reach 5 ft. Hit: 6 (1d6 + 3) Slashing damage.";
+
+    /// A monster with the given scores and a PB of 2, for parsing single actions.
+    fn monster_with_scores(scores: [i32; 6]) -> Monster {
+        Monster { scores, pb: 2, ..Default::default() }
+    }
+
+    #[test]
+    fn classic_stat_block() {
+        let imported = import_stat_block_text(GOBLIN).unwrap();
+        let goblin = imported.monster;
+
+        assert_eq!(goblin.name, "Goblin");
+        assert_eq!(goblin.size, "Small");
+        assert_eq!(goblin.subtypes, ["goblinoid"]);
+        assert_eq!((goblin.ac, goblin.hp, goblin.hitdie.as_str()), (15, 7, "d6"));
+        assert_eq!(goblin.speeds.walk, 30);
+        assert_eq!(goblin.scores, [8, 14, 10, 10, 8, 8]);
+        assert_eq!((goblin.cr.as_str(), goblin.exp, goblin.pb), ("1/4", 50, 2));
+        assert_eq!(goblin.languages, ["Common", "Goblin"]);
+        assert!(goblin.abilities.contains("Nimble Escape. The goblin can take the Disengage or Hide action as a bonus action on each of its turns."));
+
+        assert_eq!(goblin.attacks.len(), 2);
+        let scimitar = &goblin.attacks[0];
+        assert_eq!(scimitar.attack_name, "Scimitar");
+        assert_eq!(scimitar.ability_used, "dex");
+        assert_eq!((scimitar.num_dice, scimitar.dice_used.as_str()), (1, "d6"));
+        assert_eq!(scimitar.damage_type, "Slashing");
+        assert_eq!(scimitar.reach, 5);
+        let shortbow = &goblin.attacks[1];
+        assert_eq!((shortbow.range, shortbow.long_range), (80, 320));
+        assert_eq!(shortbow.damage_type, "Piercing");
+    }
+
+    #[test]
+    fn stat_block_2024_layout() {
+        let captain = import_stat_block_text(BANDIT_CAPTAIN_2024).unwrap().monster;
+
+        assert_eq!(captain.scores, [15, 16, 14, 14, 11, 14]);
+        // The save column only differs from the modifier where the monster is proficient
+        assert_eq!(captain.saves, [true, true, false, false, true, false]);
+        assert_eq!((captain.ac, captain.hp), (15, 52));
+        assert_eq!((captain.cr.as_str(), captain.exp, captain.pb), ("2", 450, 2));
+
+        let scimitar = &captain.attacks[0];
+        assert_eq!(scimitar.ability_used, "dex");
+        assert_eq!(scimitar.damage_type, "Slashing");
+    }
+
+    #[test]
+    fn breath_weapon_becomes_save_attack() {
+        let dragon = monster_with_scores([19, 10, 17, 12, 11, 15]);
+        let text = "The dragon exhales fire in a 60-foot cone. Each creature in that area must make a DC 13 Dexterity \
+            saving throw, taking 24 (7d6) fire damage on a failed save, or half as much damage on a successful one.";
+        let (breath, notes) = parse_action(&dragon, "Fire Breath (Recharge 5-6)", text).unwrap();
+
+        assert!(breath.saving_throw);
+        assert_eq!(breath.save_ability, "dex");
+        // DC 13 is 8 + PB 2 + Con 3
+        assert_eq!(breath.ability_used, "con");
+        assert_eq!((breath.num_dice, breath.dice_used.as_str()), (7, "d6"));
+        assert_eq!(breath.damage_type, "Fire");
+        assert_eq!(breath.on_save, SaveSuccess::HalfDamage);
+        assert!(!breath.add_mod_to_damage);
+        let area = breath.area.unwrap();
+        assert_eq!((area.shape, area.size), (AreaShape::Cone, 60));
+        assert!(notes.is_empty(), "{:?}", notes);
+    }
+
+    #[test]
+    fn bite_with_poison_rider() {
+        let spider = monster_with_scores([14, 16, 12, 2, 11, 4]);
+        let text = "Melee Weapon Attack: +5 to hit, reach 5 ft., one creature. Hit: 7 (1d8 + 3) piercing damage, and \
+            the target must make a DC 11 Constitution saving throw, taking 9 (2d8) poison damage on a failed save, \
+            or half as much damage on a successful one.";
+        let (bite, _) = parse_action(&spider, "Bite", text).unwrap();
+
+        assert!(!bite.saving_throw);
+        assert_eq!(bite.ability_used, "dex");
+        assert_eq!(bite.damage_type, "Piercing");
+        let rider = bite.rider.unwrap();
+        assert_eq!((rider.save_ability.as_str(), rider.dc), ("con", 11));
+        assert_eq!((rider.extra_damage.as_str(), rider.damage_type.as_str()), ("2d8", "Poison"));
+        assert!(rider.half_on_success);
+
+        let text = "Melee Weapon Attack: +4 to hit, reach 5 ft., one target. Hit: 6 (1d8 + 2) piercing damage, and \
+            the target must succeed on a DC 11 Constitution saving throw or be poisoned for 1 minute.";
+        let (bite, _) = parse_action(&monster_with_scores([10, 14, 11, 2, 10, 3]), "Bite", text).unwrap();
+        let rider = bite.rider.unwrap();
+        assert_eq!(rider.condition, "Poisoned");
+        assert_eq!(rider.duration, "1 minute");
+        assert!(rider.extra_damage.is_empty());
+    }
+
+    #[test]
+    fn tagged_5etools_entry() {
+        let json = r#"{"monster": [{
+            "name": "Goblin",
+            "size": ["S"],
+            "type": {"type": "humanoid", "tags": ["goblinoid"]},
+            "alignment": ["N", "E"],
+            "ac": [{"ac": 15, "from": ["{@item leather armor|phb}", "{@item shield|phb}"]}],
+            "hp": {"average": 7, "formula": "2d6"},
+            "speed": {"walk": 30},
+            "str": 8, "dex": 14, "con": 10, "int": 10, "wis": 8, "cha": 8,
+            "skill": {"stealth": "+6"},
+            "languages": ["Common", "Goblin"],
+            "cr": "1/4",
+            "trait": [{"name": "Nimble Escape", "entries": ["The goblin can take the {@action Disengage} or {@action Hide} action as a bonus action on each of its turns."]}],
+            "action": [{"name": "Scimitar", "entries": ["{@atk mw} {@hit 4} to hit, reach 5 ft., one target. {@h}5 ({@damage 1d6 + 2}) slashing damage."]}]
+        }]}"#;
+        let imported = import_json_str(json).unwrap();
+        assert_eq!(imported.len(), 1);
+        let goblin = &imported[0].monster;
+
+        assert_eq!((goblin.ac, goblin.hp, goblin.hitdie.as_str()), (15, 7, "d6"));
+        assert_eq!(goblin.alignment, "neutral evil");
+        assert_eq!((goblin.cr.as_str(), goblin.exp, goblin.pb), ("1/4", 50, 2));
+        assert!(goblin.abilities.contains("take the Disengage or Hide action"));
+        assert!(!goblin.abilities.contains("{@"));
+
+        let scimitar = &goblin.attacks[0];
+        assert_eq!(scimitar.attack_name, "Scimitar");
+        assert_eq!(scimitar.ability_used, "dex");
+        assert_eq!((scimitar.num_dice, scimitar.dice_used.as_str()), (1, "d6"));
+        assert_eq!((scimitar.damage_type.as_str(), scimitar.reach), ("Slashing", 5));
+    }
+}
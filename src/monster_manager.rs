@@ -4,33 +4,259 @@
 // It handles reading from and writing to the "Monsters" directory.
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs::{self, File};
 use std::io::{self, Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Current version of the monster file schema. Bump it and add a step to `MIGRATIONS`
+/// whenever stored data has to be transformed rather than just defaulted.
+pub const MONSTER_SCHEMA_VERSION: u32 = 6;
+
+/// Migration steps; entry `i` upgrades a file from version `i` to version `i + 1`.
+const MIGRATIONS: &[fn(&mut Value)] = &[
+    migrate_v0_fix_resistances_name,
+    migrate_v1_assign_id,
+    migrate_v2_derive_cr,
+    migrate_v3_mods_to_scores,
+    migrate_v4_speed_to_speeds,
+    migrate_v5_damage_lists,
+];
 
 // Represents the data structure for a monster.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// Missing fields fall back to their defaults so adding fields never drops old monsters.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct Monster {
+    pub schema_version: u32,
+    // stable id that also names the file, so renaming a monster keeps the same record
+    pub id: String,
     pub name: String,
     pub hp: i32,
     pub ac: i32,
+    // challenge rating as written in stat blocks ("1/4", "5"); empty when not set
+    pub cr: String,
     pub exp: i32,
     pub pb: i32,
-    pub speed: i32,
+    pub speeds: Speeds,
     pub hitdie: String,
-    // mod order: str, dex, con, int, wis, cha
-    pub mods: [i32;6],
+    // "" when unknown, otherwise one of `SIZES`
+    pub size: String,
+    // usually one of `CREATURE_TYPES`; subtypes are the tags in brackets, e.g. "goblinoid"
+    pub creature_type: String,
+    pub subtypes: Vec<String>,
+    pub alignment: String,
+    pub senses: Vec<Sense>,
+    pub languages: Vec<String>,
+    // ability scores, order: str, dex, con, int, wis, cha. Modifiers are derived from them.
+    pub scores: [i32;6],
     // save order: str, dex, con, int, wis, cha
     pub saves: [bool;6],
-    pub vulnerabilities: Vec<String>,
-    pub restistances: Vec<String>,
-    pub immunities: Vec<String>,
+    // skills the monster is proficient in; everything else uses the plain ability modifier
+    pub skills: Vec<SkillProficiency>,
+    pub vulnerabilities: Vec<DamageModifier>,
+    // alias keeps monster copies inside older saved encounters readable
+    #[serde(alias = "restistances")]
+    pub resistances: Vec<DamageModifier>,
+    pub immunities: Vec<DamageModifier>,
+    // e.g. "Poisoned"; usually one of `CONDITIONS`
+    pub condition_immunities: Vec<String>,
     pub abilities: String,
     pub attacks: Vec<Attack>,
+    // routines such as "one bite and two claws" made of the attacks above
+    pub multiattacks: Vec<Multiattack>,
+    // None for monsters that don't cast spells
+    pub spellcasting: Option<Spellcasting>,
+    pub tags: Vec<String>,
+    // folder path inside "Monsters" ("" for the top level, "Campaign 2/Goblinoids" when nested).
+    // It comes from where the file lives, so it isn't stored in the file itself.
+    #[serde(skip)]
+    pub folder: String,
+}
+
+impl Monster {
+    /// Modifier of the ability at `index` (same order as `scores`).
+    pub fn ability_mod(&self, index: usize) -> i32 {
+        score_to_mod(self.scores[index])
+    }
+
+    /// Check bonus for a skill: the ability modifier plus PB when proficient, or twice PB with expertise.
+    pub fn skill_bonus(&self, skill: &str) -> i32 {
+        let ability = SKILLS.iter().find(|(name, _)| *name == skill).map_or(0, |&(_, ability)| ability);
+        let proficiency = match self.skills.iter().find(|s| s.skill == skill) {
+            Some(s) if s.expertise => 2 * self.pb,
+            Some(_) => self.pb,
+            None => 0,
+        };
+        self.ability_mod(ability) + proficiency
+    }
+
+    /// 10 + the Perception bonus.
+    pub fn passive_perception(&self) -> i32 {
+        10 + self.skill_bonus("Perception")
+    }
+
+    /// Stat block subtitle, e.g. "Medium humanoid (goblinoid), neutral evil". Empty when nothing is set.
+    pub fn describe_type(&self) -> String {
+        let mut text = [self.size.as_str(), &self.creature_type.to_lowercase()]
+            .iter()
+            .filter(|part| !part.is_empty())
+            .copied()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        if !self.subtypes.is_empty() {
+            text = format!("{} ({})", text, self.subtypes.join(", ")).trim().to_string();
+        }
+        if !self.alignment.is_empty() {
+            text = if text.is_empty() { self.alignment.clone() } else { format!("{}, {}", text, self.alignment) };
+        }
+        text
+    }
+
+    /// 8 + PB + the spellcasting ability modifier, if the monster casts spells.
+    pub fn spell_save_dc(&self) -> Option<i32> {
+        self.spell_attack_bonus().map(|bonus| 8 + bonus)
+    }
+
+    /// PB + the spellcasting ability modifier, if the monster casts spells.
+    pub fn spell_attack_bonus(&self) -> Option<i32> {
+        let spellcasting = self.spellcasting.as_ref()?;
+        Some(self.pb + self.ability_mod(ability_index(&spellcasting.ability)?))
+    }
+
+    /// Senses with ranges followed by passive Perception, e.g. "darkvision 60 ft., passive Perception 9".
+    pub fn describe_senses(&self) -> String {
+        let mut parts: Vec<String> = self.senses
+            .iter()
+            .map(|s| format!("{} {} ft.", s.sense.to_lowercase(), s.range))
+            .collect();
+        parts.push(format!("passive Perception {}", self.passive_perception()));
+        parts.join(", ")
+    }
+}
+
+// Movement speeds in feet. 0 means the monster doesn't have that kind of movement.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Speeds {
+    pub walk: i32,
+    pub fly: i32,
+    pub swim: i32,
+    pub climb: i32,
+    pub burrow: i32,
+    pub hover: bool,
+}
+
+impl Speeds {
+    /// Stat block style text, e.g. "30 ft., fly 60 ft. (hover)".
+    pub fn describe(&self) -> String {
+        let mut parts = vec![format!("{} ft.", self.walk)];
+        for (kind, feet) in [("burrow", self.burrow), ("climb", self.climb), ("fly", self.fly), ("swim", self.swim)] {
+            if feet > 0 {
+                let hover = if kind == "fly" && self.hover { " (hover)" } else { "" };
+                parts.push(format!("{} {} ft.{}", kind, feet, hover));
+            }
+        }
+        parts.join(", ")
+    }
+}
+
+// A special sense such as darkvision, with its range in feet.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Sense {
+    pub sense: String,
+    pub range: i32,
+}
+
+/// Creature sizes from smallest to largest.
+pub const SIZES: [&str; 6] = ["Tiny", "Small", "Medium", "Large", "Huge", "Gargantuan"];
+
+/// Creature types from the SRD.
+pub const CREATURE_TYPES: [&str; 14] = [
+    "Aberration",
+    "Beast",
+    "Celestial",
+    "Construct",
+    "Dragon",
+    "Elemental",
+    "Fey",
+    "Fiend",
+    "Giant",
+    "Humanoid",
+    "Monstrosity",
+    "Ooze",
+    "Plant",
+    "Undead",
+];
+
+/// Conditions from the SRD.
+pub const CONDITIONS: [&str; 14] = [
+    "Blinded",
+    "Charmed",
+    "Deafened",
+    "Frightened",
+    "Grappled",
+    "Incapacitated",
+    "Invisible",
+    "Paralyzed",
+    "Petrified",
+    "Poisoned",
+    "Prone",
+    "Restrained",
+    "Stunned",
+    "Unconscious",
+];
+
+/// Special senses from the SRD.
+pub const SENSES: [&str; 4] = ["Blindsight", "Darkvision", "Tremorsense", "Truesight"];
+
+// A skill proficiency, doubled when the monster has expertise.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct SkillProficiency {
+    pub skill: String,
+    pub expertise: bool,
+}
+
+/// Skills from the SRD with the ability (index into `scores`) they use.
+pub const SKILLS: [(&str, usize); 18] = [
+    ("Acrobatics", 1),
+    ("Animal Handling", 4),
+    ("Arcana", 3),
+    ("Athletics", 0),
+    ("Deception", 5),
+    ("History", 3),
+    ("Insight", 4),
+    ("Intimidation", 5),
+    ("Investigation", 3),
+    ("Medicine", 4),
+    ("Nature", 3),
+    ("Perception", 4),
+    ("Performance", 5),
+    ("Persuasion", 5),
+    ("Religion", 3),
+    ("Sleight of Hand", 1),
+    ("Stealth", 1),
+    ("Survival", 4),
+];
+
+/// Short ability names as stored in attacks and spells, in `scores` order.
+pub const ABILITY_KEYS: [&str; 6] = ["str", "dex", "con", "int", "wis", "cha"];
+
+/// Index into `scores`/`saves` of a short ability name such as "dex".
+pub fn ability_index(key: &str) -> Option<usize> {
+    ABILITY_KEYS.iter().position(|k| k.eq_ignore_ascii_case(key))
+}
+
+/// Ability score to modifier, rounding down.
+pub fn score_to_mod(score: i32) -> i32 {
+    (score - 10).div_euclid(2)
 }
 
 // Represents the data structure for an attack.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct Attack {
     pub attack_name: String,
     pub ability_used: String,
@@ -38,6 +264,659 @@ pub struct Attack {
     pub num_dice: i32,
     pub num_attacks: i32,
     pub saving_throw: bool,
+    // for saving throw attacks: the ability targets save with (empty means Dex) and what a success does
+    pub save_ability: String,
+    pub on_save: SaveSuccess,
+    // for saving throw attacks: whether the damage adds the ability modifier, as weapon attacks always do
+    pub add_mod_to_damage: bool,
+    // e.g. "Slashing"; empty when unknown
+    pub damage_type: String,
+    // what the weapon counts as against resistances to nonmagical attacks
+    pub material: WeaponMaterial,
+    pub category: ActionCategory,
+    // in feet; 0 when the attack has no reach or range
+    pub reach: i32,
+    pub range: i32,
+    pub long_range: i32,
+    pub area: Option<AreaTemplate>,
+    // extra save the target makes when the attack hits
+    pub rider: Option<AttackRider>,
+}
+
+/// What a target that succeeds on a saving throw attack takes.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SaveSuccess {
+    #[default]
+    HalfDamage,
+    NoDamage,
+}
+
+impl SaveSuccess {
+    pub const ALL: [SaveSuccess; 2] = [SaveSuccess::HalfDamage, SaveSuccess::NoDamage];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SaveSuccess::HalfDamage => "Half damage",
+            SaveSuccess::NoDamage => "No damage",
+        }
+    }
+}
+
+impl Attack {
+    /// Short name of the ability targets save with, defaulting to Dex.
+    pub fn target_save_ability(&self) -> &str {
+        if self.save_ability.is_empty() { "dex" } else { &self.save_ability }
+    }
+
+    /// "reach 10 ft.", "range 80/320 ft.", "60-ft. cone", joined; empty when none are set.
+    pub fn describe_range(&self) -> String {
+        let mut parts = Vec::new();
+        if self.reach > 0 {
+            parts.push(format!("reach {} ft.", self.reach));
+        }
+        if self.range > 0 {
+            if self.long_range > self.range {
+                parts.push(format!("range {}/{} ft.", self.range, self.long_range));
+            } else {
+                parts.push(format!("range {} ft.", self.range));
+            }
+        }
+        if let Some(area) = &self.area {
+            parts.push(area.describe());
+        }
+        parts.join(", ")
+    }
+}
+
+/// What an attack's weapon counts as for resistances that only cover nonmagical attacks.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WeaponMaterial {
+    #[default]
+    Nonmagical,
+    Silvered,
+    Adamantine,
+    Magical,
+}
+
+impl WeaponMaterial {
+    pub const ALL: [WeaponMaterial; 4] = [
+        WeaponMaterial::Nonmagical,
+        WeaponMaterial::Silvered,
+        WeaponMaterial::Adamantine,
+        WeaponMaterial::Magical,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WeaponMaterial::Nonmagical => "Nonmagical",
+            WeaponMaterial::Silvered => "Silvered",
+            WeaponMaterial::Adamantine => "Adamantine",
+            WeaponMaterial::Magical => "Magical",
+        }
+    }
+}
+
+/// Shape of an area of effect.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AreaShape {
+    #[default]
+    Cone,
+    Line,
+    Sphere,
+    Cube,
+    Cylinder,
+}
+
+impl AreaShape {
+    pub const ALL: [AreaShape; 5] = [
+        AreaShape::Cone,
+        AreaShape::Line,
+        AreaShape::Sphere,
+        AreaShape::Cube,
+        AreaShape::Cylinder,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AreaShape::Cone => "Cone",
+            AreaShape::Line => "Line",
+            AreaShape::Sphere => "Sphere",
+            AreaShape::Cube => "Cube",
+            AreaShape::Cylinder => "Cylinder",
+        }
+    }
+}
+
+// An area of effect; size is the length of a cone or line, the side of a cube
+// or the radius of a sphere or cylinder, in feet.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AreaTemplate {
+    pub shape: AreaShape,
+    pub size: i32,
+}
+
+impl AreaTemplate {
+    /// "60-ft. cone" or "20-ft.-radius sphere".
+    pub fn describe(&self) -> String {
+        match self.shape {
+            AreaShape::Sphere | AreaShape::Cylinder => {
+                format!("{}-ft.-radius {}", self.size, self.shape.label().to_lowercase())
+            }
+            shape => format!("{}-ft. {}", self.size, shape.label().to_lowercase()),
+        }
+    }
+
+    /// Rough number of creatures caught in the area, from the DMG's targets in areas of effect table.
+    pub fn estimated_targets(&self) -> i32 {
+        let per_target = match self.shape {
+            AreaShape::Cone => 10,
+            AreaShape::Line => 30,
+            AreaShape::Sphere | AreaShape::Cylinder | AreaShape::Cube => 5,
+        };
+        // The table rounds up
+        ((self.size + per_target - 1) / per_target).max(1)
+    }
+}
+
+/// An on-hit effect such as "DC 13 Con save or be poisoned for 1 minute".
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AttackRider {
+    // short ability name, as in `ABILITY_KEYS`
+    pub save_ability: String,
+    pub dc: i32,
+    // applied on a failed save; empty for none
+    pub condition: String,
+    // dice expression such as "2d6"; empty for none
+    pub extra_damage: String,
+    pub damage_type: String,
+    // whether a successful save still takes half the extra damage
+    pub half_on_success: bool,
+    // free text such as "1 minute"; empty when it lasts until removed
+    pub duration: String,
+}
+
+impl AttackRider {
+    /// "DC 13 Con save or poisoned and 2d6 poison (half on success) for 1 minute"
+    pub fn describe(&self) -> String {
+        let mut effects = Vec::new();
+        if !self.condition.is_empty() {
+            effects.push(self.condition.to_lowercase());
+        }
+        if !self.extra_damage.is_empty() {
+            let damage_type = if self.damage_type.is_empty() {
+                String::new()
+            } else {
+                format!(" {}", self.damage_type.to_lowercase())
+            };
+            let half = if self.half_on_success { " (half on success)" } else { "" };
+            effects.push(format!("{}{}{}", self.extra_damage, damage_type, half));
+        }
+        let mut text = format!("DC {} {} save", self.dc, capitalize_key(&self.save_ability));
+        if !effects.is_empty() {
+            text.push_str(&format!(" or {}", effects.join(" and ")));
+        }
+        if !self.duration.is_empty() {
+            text.push_str(&format!(" for {}", self.duration));
+        }
+        text
+    }
+}
+
+/// A named routine of several attacks used together as one action.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Multiattack {
+    pub name: String,
+    pub steps: Vec<MultiattackStep>,
+}
+
+// One attack of a routine, referenced by name, and how many times it is made.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct MultiattackStep {
+    pub attack_name: String,
+    pub count: i32,
+}
+
+impl Multiattack {
+    /// The steps in order, e.g. "1x Bite, 2x Claw".
+    pub fn describe_steps(&self) -> String {
+        self.steps
+            .iter()
+            .map(|step| format!("{}x {}", step.count, step.attack_name))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+/// Where an attack sits in the action economy.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ActionCategory {
+    #[default]
+    Action,
+    BonusAction,
+    Reaction,
+    Legendary,
+    Lair,
+}
+
+impl ActionCategory {
+    /// Every category, in the order they are listed on cards and stat blocks.
+    pub const ALL: [ActionCategory; 5] = [
+        ActionCategory::Action,
+        ActionCategory::BonusAction,
+        ActionCategory::Reaction,
+        ActionCategory::Legendary,
+        ActionCategory::Lair,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ActionCategory::Action => "Action",
+            ActionCategory::BonusAction => "Bonus Action",
+            ActionCategory::Reaction => "Reaction",
+            ActionCategory::Legendary => "Legendary",
+            ActionCategory::Lair => "Lair",
+        }
+    }
+
+    /// Heading for a group of attacks in this category, e.g. "Bonus Actions".
+    pub fn heading(&self) -> &'static str {
+        match self {
+            ActionCategory::Action => "Actions",
+            ActionCategory::BonusAction => "Bonus Actions",
+            ActionCategory::Reaction => "Reactions",
+            ActionCategory::Legendary => "Legendary Actions",
+            ActionCategory::Lair => "Lair Actions",
+        }
+    }
+}
+
+/// How a monster casts spells: its spellcasting ability, slots and known spells.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Spellcasting {
+    // short ability name, e.g. "wis"
+    pub ability: String,
+    // slots per spell level, index 0 is 1st level
+    pub slots: [i32; 9],
+    pub spells: Vec<Spell>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Spell {
+    pub name: String,
+    // 0 for cantrips
+    pub level: i32,
+    pub usage: SpellUsage,
+    pub roll: SpellRoll,
+    // dice expression such as "8d6"; empty for spells that don't deal damage
+    pub damage: String,
+    pub damage_type: String,
+}
+
+/// What casting a spell uses up.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub enum SpellUsage {
+    // a slot of the spell's level or higher; cantrips are free
+    #[default]
+    Slot,
+    // innate, as often as the monster likes
+    AtWill,
+    // innate, a number of times per day
+    PerDay(i32),
+}
+
+/// What a spell rolls when it is cast.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub enum SpellRoll {
+    #[default]
+    None,
+    Attack,
+    // short ability name of the target's save, e.g. "dex"
+    Save(String),
+}
+
+impl Spell {
+    /// Short summary, e.g. "Fireball (3rd, Dex save, 8d6 fire)".
+    pub fn describe(&self) -> String {
+        let mut parts = vec![spell_level_label(self.level)];
+        if self.usage != SpellUsage::Slot {
+            parts.push(self.usage.label());
+        }
+        match &self.roll {
+            SpellRoll::None => {}
+            SpellRoll::Attack => parts.push("spell attack".to_string()),
+            SpellRoll::Save(ability) => parts.push(format!("{} save", capitalize_key(ability))),
+        }
+        if !self.damage.is_empty() {
+            parts.push(format!("{} {}", self.damage, self.damage_type.to_lowercase()).trim().to_string());
+        }
+        format!("{} ({})", self.name, parts.join(", "))
+    }
+}
+
+/// "dex" -> "Dex".
+fn capitalize_key(key: &str) -> String {
+    let mut chars = key.chars();
+    chars.next().map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+}
+
+impl SpellUsage {
+    pub fn label(&self) -> String {
+        match self {
+            SpellUsage::Slot => "Slot".to_string(),
+            SpellUsage::AtWill => "At will".to_string(),
+            SpellUsage::PerDay(uses) => format!("{}/day", uses),
+        }
+    }
+}
+
+/// "Cantrip", "1st", "2nd", "3rd", "4th"...
+pub fn spell_level_label(level: i32) -> String {
+    match level {
+        0 => "Cantrip".to_string(),
+        1 => "1st".to_string(),
+        2 => "2nd".to_string(),
+        3 => "3rd".to_string(),
+        n => format!("{}th", n),
+    }
+}
+
+/// Parses a dice expression such as "2d6 + 4" into (count, "d6", bonus).
+pub fn parse_dice_expression(text: &str) -> Option<(i32, String, i32)> {
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let (count, rest) = compact.split_once('d')?;
+    let count = if count.is_empty() { 1 } else { count.parse().ok()? };
+    let sides: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if sides.is_empty() {
+        return None;
+    }
+    let bonus_text = &rest[sides.len()..];
+    let (sign, digits) = match bonus_text.strip_prefix('-') {
+        Some(digits) => (-1, digits),
+        None => (1, bonus_text.trim_start_matches('+')),
+    };
+    let bonus: String = digits.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let bonus = bonus.parse::<i32>().map_or(0, |n| n * sign);
+    Some((count, format!("d{}", sides), bonus))
+}
+
+/// Damage types from the SRD. User-defined types are added through `add_custom_damage_type`.
+pub const SRD_DAMAGE_TYPES: [&str; 13] = [
+    "Acid",
+    "Bludgeoning",
+    "Cold",
+    "Fire",
+    "Force",
+    "Lightning",
+    "Necrotic",
+    "Piercing",
+    "Poison",
+    "Psychic",
+    "Radiant",
+    "Slashing",
+    "Thunder",
+];
+
+/// When a vulnerability, resistance or immunity applies.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DamageQualifier {
+    #[default]
+    Always,
+    Nonmagical,
+    NonmagicalNotSilvered,
+    NonmagicalNotAdamantine,
+}
+
+impl DamageQualifier {
+    pub const ALL: [DamageQualifier; 4] = [
+        DamageQualifier::Always,
+        DamageQualifier::Nonmagical,
+        DamageQualifier::NonmagicalNotSilvered,
+        DamageQualifier::NonmagicalNotAdamantine,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DamageQualifier::Always => "Always",
+            DamageQualifier::Nonmagical => "Nonmagical",
+            DamageQualifier::NonmagicalNotSilvered => "Nonmagical, not silvered",
+            DamageQualifier::NonmagicalNotAdamantine => "Nonmagical, not adamantine",
+        }
+    }
+
+    /// Stat block wording, e.g. "from nonmagical attacks that aren't silvered"; empty for `Always`.
+    pub fn phrase(&self) -> &'static str {
+        match self {
+            DamageQualifier::Always => "",
+            DamageQualifier::Nonmagical => "from nonmagical attacks",
+            DamageQualifier::NonmagicalNotSilvered => "from nonmagical attacks that aren't silvered",
+            DamageQualifier::NonmagicalNotAdamantine => "from nonmagical attacks that aren't adamantine",
+        }
+    }
+
+    /// Whether the vulnerability, resistance or immunity covers an attack with this weapon.
+    pub fn applies_to(&self, material: WeaponMaterial) -> bool {
+        match self {
+            DamageQualifier::Always => true,
+            DamageQualifier::Nonmagical => material != WeaponMaterial::Magical,
+            DamageQualifier::NonmagicalNotSilvered => {
+                matches!(material, WeaponMaterial::Nonmagical | WeaponMaterial::Adamantine)
+            }
+            DamageQualifier::NonmagicalNotAdamantine => {
+                matches!(material, WeaponMaterial::Nonmagical | WeaponMaterial::Silvered)
+            }
+        }
+    }
+}
+
+// One entry of a monster's vulnerabilities, resistances or immunities.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct DamageModifier {
+    pub damage_type: String,
+    pub qualifier: DamageQualifier,
+}
+
+impl DamageModifier {
+    /// "Slashing" or "Slashing from nonmagical attacks".
+    pub fn describe(&self) -> String {
+        match self.qualifier {
+            DamageQualifier::Always => self.damage_type.clone(),
+            qualifier => format!("{} {}", self.damage_type, qualifier.phrase()),
+        }
+    }
+
+    /// Reads the text written by `describe`, fixing the casing and old misspellings of SRD types.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let lower = text.to_lowercase();
+        let (name, qualifier) = DamageQualifier::ALL
+            .iter()
+            .rev()
+            .filter(|q| **q != DamageQualifier::Always)
+            .find_map(|q| {
+                let name = lower.strip_suffix(q.phrase())?;
+                Some((text.get(..name.len())?.trim(), *q))
+            })
+            .unwrap_or((text, DamageQualifier::Always));
+        if name.is_empty() {
+            return None;
+        }
+        let damage_type = match name.to_lowercase().as_str() {
+            // The old resistance dropdown saved this spelling
+            "bludgening" => "Bludgeoning".to_string(),
+            lower => SRD_DAMAGE_TYPES
+                .iter()
+                .find(|t| t.to_lowercase() == lower)
+                .map_or(name.to_string(), |t| t.to_string()),
+        };
+        Some(DamageModifier { damage_type, qualifier })
+    }
+}
+
+/// Returns the path of the file listing user-defined damage types.
+fn custom_damage_types_path() -> io::Result<PathBuf> {
+    let mut path = get_base_path()?;
+    path.push("damage_types.json");
+    Ok(path)
+}
+
+/// Reads the user-defined damage types; missing or unreadable files mean there are none.
+pub fn read_custom_damage_types() -> Vec<String> {
+    let Ok(path) = custom_damage_types_path() else {
+        return Vec::new();
+    };
+    match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            eprintln!("Failed to read custom damage types: {}", e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Every known damage type: the SRD ones followed by the user-defined ones.
+pub fn damage_types() -> Vec<String> {
+    let mut types: Vec<String> = SRD_DAMAGE_TYPES.iter().map(|t| t.to_string()).collect();
+    types.extend(read_custom_damage_types());
+    types
+}
+
+/// Looks up a damage type by name, ignoring case. Returns the registered spelling.
+pub fn find_damage_type(name: &str) -> Option<String> {
+    damage_types().into_iter().find(|t| t.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Registers a user-defined damage type. Returns the registered spelling, which is the
+/// existing one when the type is already known.
+pub fn add_custom_damage_type(name: &str) -> io::Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Damage type name is empty"));
+    }
+    if let Some(existing) = find_damage_type(name) {
+        return Ok(existing);
+    }
+
+    let mut custom = read_custom_damage_types();
+    custom.push(name.to_string());
+    custom.sort();
+
+    let path = custom_damage_types_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&custom)?)?;
+    Ok(name.to_string())
+}
+
+/// Challenge ratings with their XP and proficiency bonus, from the SRD.
+pub const CR_TABLE: [(&str, i32, i32); 34] = [
+    ("0", 10, 2),
+    ("1/8", 25, 2),
+    ("1/4", 50, 2),
+    ("1/2", 100, 2),
+    ("1", 200, 2),
+    ("2", 450, 2),
+    ("3", 700, 2),
+    ("4", 1100, 2),
+    ("5", 1800, 3),
+    ("6", 2300, 3),
+    ("7", 2900, 3),
+    ("8", 3900, 3),
+    ("9", 5000, 4),
+    ("10", 5900, 4),
+    ("11", 7200, 4),
+    ("12", 8400, 4),
+    ("13", 10000, 5),
+    ("14", 11500, 5),
+    ("15", 13000, 5),
+    ("16", 15000, 5),
+    ("17", 18000, 6),
+    ("18", 20000, 6),
+    ("19", 22000, 6),
+    ("20", 25000, 6),
+    ("21", 33000, 7),
+    ("22", 41000, 7),
+    ("23", 50000, 7),
+    ("24", 62000, 7),
+    ("25", 75000, 8),
+    ("26", 90000, 8),
+    ("27", 105000, 8),
+    ("28", 120000, 8),
+    ("29", 135000, 9),
+    ("30", 155000, 9),
+];
+
+/// Looks up the XP and proficiency bonus for a challenge rating such as "1/4" or "5".
+pub fn cr_stats(cr: &str) -> Option<(i32, i32)> {
+    CR_TABLE
+        .iter()
+        .find(|(rating, _, _)| *rating == cr.trim())
+        .map(|&(_, exp, pb)| (exp, pb))
+}
+
+/// Numeric value of a challenge rating, so "1/8" sorts below "1". `None` for unknown ratings.
+pub fn cr_value(cr: &str) -> Option<f32> {
+    let cr = cr.trim();
+    cr_stats(cr)?;
+    match cr.split_once('/') {
+        Some((numerator, denominator)) => Some(numerator.parse::<f32>().ok()? / denominator.parse::<f32>().ok()?),
+        None => cr.parse().ok(),
+    }
+}
+
+/// The challenge rating whose XP and proficiency bonus match exactly, if any.
+pub fn cr_for_stats(exp: i32, pb: i32) -> Option<&'static str> {
+    CR_TABLE
+        .iter()
+        .find(|&&(_, table_exp, table_pb)| table_exp == exp && table_pb == pb)
+        .map(|&(rating, _, _)| rating)
+}
+
+/// Describes how a monster's XP and proficiency bonus disagree with its challenge rating.
+/// `None` when they match or no rating is set.
+pub fn check_cr(monster: &Monster) -> Option<String> {
+    if monster.cr.is_empty() {
+        return None;
+    }
+    let Some((exp, pb)) = cr_stats(&monster.cr) else {
+        return Some(format!("\"{}\" is not a challenge rating", monster.cr));
+    };
+
+    let mut problems = Vec::new();
+    if monster.exp != exp {
+        problems.push(format!("{} XP instead of {}", monster.exp, exp));
+    }
+    if monster.pb != pb {
+        problems.push(format!("PB {:+} instead of {:+}", monster.pb, pb));
+    }
+    if problems.is_empty() {
+        None
+    } else {
+        Some(format!("CR {} usually means {} XP and PB {:+}; this monster has {}.", monster.cr, exp, pb, problems.join(" and ")))
+    }
+}
+
+/// Total XP of an encounter and the XP adjusted for the number of monsters,
+/// using the multipliers from the 2014 Dungeon Master's Guide.
+pub fn encounter_xp(monsters: &[(Monster, i32)]) -> (i32, i32) {
+    let total: i32 = monsters.iter().map(|(monster, count)| monster.exp * count).sum();
+    let count: i32 = monsters.iter().map(|(_, count)| count).sum();
+    let multiplier = match count {
+        ..=1 => 1.0,
+        2 => 1.5,
+        3..=6 => 2.0,
+        7..=10 => 2.5,
+        11..=14 => 3.0,
+        _ => 4.0,
+    };
+    (total, (total as f32 * multiplier) as i32)
 }
 
 /// Checks if the "Monsters" directory exists.
@@ -68,36 +947,145 @@ pub fn get_base_path() -> io::Result<PathBuf> {
 }
 
 
-/// Saves a monster to a JSON file.
-pub fn save_monster(monster: Monster) -> io::Result<()> {
-    // Ensure the Monsters directory exists.
+/// Returns the path to the "Monsters" directory.
+fn get_monsters_path() -> io::Result<PathBuf> {
     let mut path = get_base_path()?;
     path.push("Monsters");
+    Ok(path)
+}
+
+/// Turns a display name into a lowercase, file-system safe slug.
+fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug.trim_matches('-').chars().take(40).collect();
+    if slug.is_empty() { "monster".to_string() } else { slug }
+}
+
+/// Creates a new stable id for a monster, e.g. "goblin-boss-1a2b3c4d".
+fn generate_monster_id(name: &str) -> String {
+    format!("{}-{:08x}", slugify(name), rand::random::<u32>())
+}
+
+/// Rejects ids that could escape the monsters folder.
+fn validate_monster_id(monster_id: &str) -> io::Result<()> {
+    if monster_id.is_empty() || !monster_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid monster id"));
+    }
+    Ok(())
+}
+
+/// Dropdown entry standing for the top level of the monsters folder.
+pub const NO_FOLDER_LABEL: &str = "(No folder)";
+
+/// Cleans up a "/" separated folder path and rejects anything that could escape the monsters folder.
+pub fn normalize_folder(folder: &str) -> io::Result<String> {
+    let mut parts = Vec::new();
+    for part in folder.split('/') {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        if part == "." || part == ".." || part.chars().any(|c| c == '\\' || c == ':' || c.is_control()) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid folder name"));
+        }
+        parts.push(part);
+    }
+    Ok(parts.join("/"))
+}
+
+/// Returns the directory for a folder path without creating it.
+fn folder_path(folder: &str) -> io::Result<PathBuf> {
+    let mut path = get_monsters_path()?;
+    for part in normalize_folder(folder)?.split('/').filter(|p| !p.is_empty()) {
+        path.push(part);
+    }
+    Ok(path)
+}
+
+/// Returns the directory for a folder path, creating it if needed.
+fn folder_dir(folder: &str) -> io::Result<PathBuf> {
+    let path = folder_path(folder)?;
     if !path.exists() {
-        fs::create_dir_all(&path)?; 
+        fs::create_dir_all(&path)?;
+    }
+    Ok(path)
+}
+
+/// Walks the monsters folder, collecting every monster file (with its folder) and every sub folder.
+fn collect_monster_tree(dir: &Path, folder: &str, files: &mut Vec<(PathBuf, String)>, folders: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let dir_name = entry.file_name().to_string_lossy().to_string();
+            let sub_folder = if folder.is_empty() { dir_name } else { format!("{}/{}", folder, dir_name) };
+            collect_monster_tree(&path, &sub_folder, files, folders);
+            folders.push(sub_folder);
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            files.push((path, folder.to_string()));
+        }
+    }
+}
+
+/// Finds the file of a monster by id, wherever it is filed.
+fn find_monster_path(monster_id: &str) -> Option<(PathBuf, String)> {
+    validate_monster_id(monster_id).ok()?;
+    let file_name = format!("{}.json", monster_id);
+
+    let mut files = Vec::new();
+    let mut folders = Vec::new();
+    collect_monster_tree(&get_monsters_path().ok()?, "", &mut files, &mut folders);
+    files.into_iter().find(|(path, _)| path.file_name().is_some_and(|n| n.to_string_lossy() == file_name))
+}
+
+/// Saves a monster to a JSON file named after its id inside its folder, giving it an id
+/// first if it is new. A monster whose folder changed is moved. Returns the monster's id.
+pub fn save_monster(mut monster: Monster) -> io::Result<String> {
+    monster.schema_version = MONSTER_SCHEMA_VERSION;
+    if monster.id.is_empty() {
+        monster.id = generate_monster_id(&monster.name);
     }
+    validate_monster_id(&monster.id)?;
+    monster.folder = normalize_folder(&monster.folder)?;
 
-    // Create the file path for the new monster.
-    path.push(format!("{}.json", monster.name));
+    // Create the file path for the monster (this also ensures the folder exists).
+    let mut path = folder_dir(&monster.folder)?;
+    path.push(format!("{}.json", monster.id));
+    let previous_path = find_monster_path(&monster.id).map(|(p, _)| p);
 
     let json_data = serde_json::to_string_pretty(&monster)?;
     let mut file = File::create(&path)?;
     file.write_all(json_data.as_bytes())?;
 
+    if let Some(previous_path) = previous_path
+        && previous_path != path
+    {
+        fs::remove_file(&previous_path)?;
+    }
+
     println!("Saved monster to file: {:?}", path);
-    Ok(())
+    Ok(monster.id)
 }
 
-/// Reads a monster's data from a JSON file by name.
-pub fn read_monster(monster_name: &str) -> Option<Monster> {
-    let mut path = match get_base_path() {
-        Ok(p) => p,
-        Err(_) => return None,
-    };
-    path.push("Monsters");
-    path.push(format!("{}.json", monster_name));
+/// Reads a monster's data by id.
+pub fn read_monster(monster_id: &str) -> Option<Monster> {
+    let (path, folder) = find_monster_path(monster_id)?;
+    read_monster_file(&path, &folder)
+}
 
-    let mut file = match File::open(&path) {
+/// Reads a monster's data from a JSON file in the given folder.
+/// Files from older schema versions are migrated, backed up and rewritten.
+fn read_monster_file(path: &Path, folder: &str) -> Option<Monster> {
+    let mut file = match File::open(path) {
         Ok(f) => f,
         Err(_) => return None,
     };
@@ -107,68 +1095,491 @@ pub fn read_monster(monster_name: &str) -> Option<Monster> {
         return None;
     }
 
-    match serde_json::from_str(&contents) {
-        Ok(monster) => Some(monster),
+    let mut value: Value = match serde_json::from_str(&contents) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to parse monster JSON for {:?}: {}", path, e);
+            return None;
+        }
+    };
+
+    let old_version = migrate_monster_value(&mut value);
+
+    let mut monster: Monster = match serde_json::from_value(value) {
+        Ok(monster) => monster,
         Err(e) => {
-            eprintln!("Failed to parse monster JSON for '{}': {}", monster_name, e);
-            None
+            eprintln!("Failed to parse monster JSON for {:?}: {}", path, e);
+            return None;
+        }
+    };
+    monster.folder = folder.to_string();
+
+    if old_version < MONSTER_SCHEMA_VERSION {
+        if let Err(e) = backup_monster_file(path, old_version) {
+            // Never rewrite a file we couldn't back up
+            eprintln!("Failed to back up {:?}, leaving it unmigrated: {}", path, e);
+            return Some(monster);
+        }
+        match save_monster(monster.clone()) {
+            Ok(_) => {
+                // Older files were named after the monster; drop them once the id file exists
+                if path.file_stem().is_some_and(|stem| stem.to_string_lossy() != monster.id)
+                    && let Err(e) = fs::remove_file(path)
+                {
+                    eprintln!("Failed to remove old monster file {:?}: {}", path, e);
+                }
+            }
+            Err(e) => eprintln!("Failed to rewrite migrated monster {:?}: {}", path, e),
         }
     }
+
+    Some(monster)
+}
+
+/// Converts a raw monster of any schema version (e.g. one shipped inside a bundle) into the current `Monster`.
+pub fn monster_from_value(mut value: Value) -> serde_json::Result<Monster> {
+    migrate_monster_value(&mut value);
+    let mut monster: Monster = serde_json::from_value(value)?;
+    monster.schema_version = MONSTER_SCHEMA_VERSION;
+    Ok(monster)
 }
 
-/// Reads all monsters from the "Monsters" directory.
+/// Runs every migration step needed to bring a raw monster up to the current schema.
+/// Returns the version the file was at before migrating.
+pub fn migrate_monster_value(value: &mut Value) -> u32 {
+    let old_version = value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as u32;
+
+    for version in old_version..MONSTER_SCHEMA_VERSION {
+        if let Some(step) = MIGRATIONS.get(version as usize) {
+            step(value);
+        }
+    }
+
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("schema_version".to_string(), Value::from(MONSTER_SCHEMA_VERSION));
+    }
+    old_version
+}
+
+/// v0 -> v1: the resistances list used to be saved as "restistances".
+fn migrate_v0_fix_resistances_name(value: &mut Value) {
+    if let Some(obj) = value.as_object_mut()
+        && let Some(list) = obj.remove("restistances")
+    {
+        obj.entry("resistances").or_insert(list);
+    }
+}
+
+/// v1 -> v2: monsters get a stable id instead of being keyed by their name.
+fn migrate_v1_assign_id(value: &mut Value) {
+    if let Some(obj) = value.as_object_mut() {
+        let has_id = obj.get("id").and_then(Value::as_str).is_some_and(|id| !id.is_empty());
+        if !has_id {
+            let name = obj.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+            obj.insert("id".to_string(), Value::from(generate_monster_id(&name)));
+        }
+    }
+}
+
+/// v2 -> v3: monsters get a challenge rating, filled in when their XP and PB match the table.
+fn migrate_v2_derive_cr(value: &mut Value) {
+    if let Some(obj) = value.as_object_mut() {
+        let has_cr = obj.get("cr").and_then(Value::as_str).is_some_and(|cr| !cr.is_empty());
+        if !has_cr {
+            let stat = |key: &str| obj.get(key).and_then(Value::as_i64).unwrap_or(0) as i32;
+            let cr = cr_for_stats(stat("exp"), stat("pb")).unwrap_or_default();
+            obj.insert("cr".to_string(), Value::from(cr));
+        }
+    }
+}
+
+/// v3 -> v4: only modifiers used to be stored; back-fill scores as 10 + 2 x modifier.
+fn migrate_v3_mods_to_scores(value: &mut Value) {
+    if let Some(obj) = value.as_object_mut() {
+        let mods: Vec<i64> = obj
+            .remove("mods")
+            .and_then(|mods| mods.as_array().map(|m| m.iter().map(|v| v.as_i64().unwrap_or(0)).collect()))
+            .unwrap_or_default();
+        if !obj.contains_key("scores") {
+            let scores: Vec<i64> = (0..6).map(|i| 10 + 2 * mods.get(i).copied().unwrap_or(0)).collect();
+            obj.insert("scores".to_string(), Value::from(scores));
+        }
+    }
+}
+
+/// v4 -> v5: the single walking speed becomes one of several movement speeds.
+fn migrate_v4_speed_to_speeds(value: &mut Value) {
+    if let Some(obj) = value.as_object_mut() {
+        let walk = obj.remove("speed").and_then(|speed| speed.as_i64()).unwrap_or(0);
+        obj.entry("speeds").or_insert_with(|| serde_json::json!({ "walk": walk }));
+    }
+}
+
+/// v5 -> v6: vulnerability, resistance and immunity lists of plain names become damage
+/// modifiers, which also fixes the "Bludgening" spelling the old form saved.
+fn migrate_v5_damage_lists(value: &mut Value) {
+    if let Some(obj) = value.as_object_mut() {
+        for key in ["vulnerabilities", "resistances", "immunities"] {
+            let Some(list) = obj.get(key).and_then(Value::as_array) else {
+                continue;
+            };
+            let modifiers: Vec<Value> = list
+                .iter()
+                .filter_map(|entry| match entry.as_str() {
+                    Some(name) => DamageModifier::parse(name).and_then(|m| serde_json::to_value(m).ok()),
+                    None => Some(entry.clone()),
+                })
+                .collect();
+            obj.insert(key.to_string(), Value::from(modifiers));
+        }
+    }
+}
+
+/// Copies a monster file into the "Backups" directory before it gets rewritten.
+fn backup_monster_file(path: &Path, old_version: u32) -> io::Result<()> {
+    let mut backup_path = get_base_path()?;
+    backup_path.push("Backups");
+    if !backup_path.exists() {
+        fs::create_dir_all(&backup_path)?;
+    }
+
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("monster");
+    backup_path.push(format!(
+        "{}.v{}.{}.json",
+        stem,
+        old_version,
+        chrono::Local::now().format("%Y%m%d%H%M%S")
+    ));
+    fs::copy(path, &backup_path)?;
+
+    println!("Backed up monster file to: {:?}", backup_path);
+    Ok(())
+}
+
+/// Reads all monsters from the "Monsters" directory and its folders.
 pub fn read_all_monsters() -> Vec<Monster> {
-    let mut path = match get_base_path() {
+    let path = match get_monsters_path() {
         Ok(p) => p,
         Err(_) => return Vec::new(),
     };
-    path.push("Monsters");
 
-    let mut monsters = Vec::new();
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.extension().map_or(false, |ext| ext == "json") {
-                let monster_name = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
-                if let Some(monster) = read_monster(monster_name) {
-                    monsters.push(monster);
-                }
-            }
-        }
-    }
+    let mut files = Vec::new();
+    let mut folders = Vec::new();
+    collect_monster_tree(&path, "", &mut files, &mut folders);
+
+    let mut monsters: Vec<Monster> = files
+        .iter()
+        .filter_map(|(path, folder)| read_monster_file(path, folder))
+        .collect();
     monsters.sort_by( |a,b| a.name.cmp(&b.name));
     monsters
 }
 
-/// Deletes a monster's JSON file.
-pub fn delete_monster(monster_name: &str) -> io::Result<()> {
-    let mut path = get_base_path()?;
-    path.push("Monsters");
-    path.push(format!("{}.json", monster_name));
+/// Deletes a monster's JSON file by id.
+pub fn delete_monster(monster_id: &str) -> io::Result<()> {
+    let (path, _) = find_monster_path(monster_id)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Monster not found"))?;
     fs::remove_file(&path)?;
     println!("Deleted monster file: {:?}", path);
     Ok(())
 }
 
+/// Lists every folder (including empty ones) as "/" separated paths, sorted.
+pub fn list_folders() -> Vec<String> {
+    let Ok(path) = get_monsters_path() else {
+        return Vec::new();
+    };
+    let mut files = Vec::new();
+    let mut folders = Vec::new();
+    collect_monster_tree(&path, "", &mut files, &mut folders);
+    folders.sort();
+    folders
+}
+
+/// Lists every tag used by the given monsters, sorted and without duplicates.
+pub fn list_tags(monsters: &[Monster]) -> Vec<String> {
+    let mut tags: Vec<String> = monsters.iter().flat_map(|m| m.tags.iter().cloned()).collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// Lists every damage type some monster resists or is immune to, sorted and without duplicates.
+pub fn list_resistance_types(monsters: &[Monster]) -> Vec<String> {
+    let mut types: Vec<String> = monsters
+        .iter()
+        .flat_map(|m| m.resistances.iter().chain(m.immunities.iter()).map(|r| r.damage_type.clone()))
+        .collect();
+    types.sort();
+    types.dedup();
+    types
+}
+
+/// Creature types used by the given monsters, for filter dropdowns.
+pub fn list_creature_types(monsters: &[Monster]) -> Vec<String> {
+    let mut types: Vec<String> = monsters
+        .iter()
+        .map(|m| m.creature_type.clone())
+        .filter(|t| !t.is_empty())
+        .collect();
+    types.sort();
+    types.dedup();
+    types
+}
+
+/// Criteria used to narrow down monster lists. `None` means "don't filter on this".
+#[derive(Debug, Clone, Default)]
+pub struct MonsterFilter {
+    pub search: String,
+    pub folder: Option<String>,
+    pub tag: Option<String>,
+    pub resistance: Option<String>,
+    pub creature_type: Option<String>,
+    pub exp_range: (Option<i32>, Option<i32>),
+    // monsters without a challenge rating are left out once this is set
+    pub cr_range: (Option<f32>, Option<f32>),
+    pub ac_range: (Option<i32>, Option<i32>),
+}
+
+impl MonsterFilter {
+    /// True when at least one criterion is set.
+    pub fn is_active(&self) -> bool {
+        !self.search.trim().is_empty()
+            || self.folder.is_some()
+            || self.tag.is_some()
+            || self.resistance.is_some()
+            || self.creature_type.is_some()
+            || self.exp_range != (None, None)
+            || self.cr_range != (None, None)
+            || self.ac_range != (None, None)
+    }
+
+    /// Checks a monster against every criterion. The search looks at the name, tags, subtypes, ability text and attack names.
+    pub fn matches(&self, monster: &Monster) -> bool {
+        let search = self.search.trim().to_lowercase();
+        if !search.is_empty() {
+            let found = monster.name.to_lowercase().contains(&search)
+                || monster.abilities.to_lowercase().contains(&search)
+                || monster.tags.iter().any(|t| t.to_lowercase().contains(&search))
+                || monster.subtypes.iter().any(|t| t.to_lowercase().contains(&search))
+                || monster.attacks.iter().any(|a| a.attack_name.to_lowercase().contains(&search));
+            if !found {
+                return false;
+            }
+        }
+
+        // Folders include their subfolders
+        if let Some(folder) = &self.folder
+            && monster.folder != *folder
+            && !monster.folder.starts_with(&format!("{}/", folder))
+        {
+            return false;
+        }
+        if let Some(tag) = &self.tag
+            && !monster.tags.contains(tag)
+        {
+            return false;
+        }
+        if let Some(resistance) = &self.resistance
+            && !monster.resistances.iter().chain(monster.immunities.iter()).any(|r| r.damage_type == *resistance)
+        {
+            return false;
+        }
+
+        if let Some(creature_type) = &self.creature_type
+            && !monster.creature_type.eq_ignore_ascii_case(creature_type)
+        {
+            return false;
+        }
+        if self.cr_range != (None, None)
+            && !cr_value(&monster.cr).is_some_and(|cr| in_range(cr, self.cr_range))
+        {
+            return false;
+        }
+
+        in_range(monster.exp, self.exp_range) && in_range(monster.ac, self.ac_range)
+    }
+}
+
+fn in_range<T: PartialOrd + Copy>(value: T, (min, max): (Option<T>, Option<T>)) -> bool {
+    min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+}
+
+/// Orderings offered for monster lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MonsterSort {
+    #[default]
+    Name,
+    ExpHighest,
+    ExpLowest,
+    CrHighest,
+    CrLowest,
+    AcHighest,
+    HpHighest,
+    Folder,
+}
+
+impl MonsterSort {
+    pub const ALL: [MonsterSort; 8] = [
+        MonsterSort::Name,
+        MonsterSort::ExpHighest,
+        MonsterSort::ExpLowest,
+        MonsterSort::CrHighest,
+        MonsterSort::CrLowest,
+        MonsterSort::AcHighest,
+        MonsterSort::HpHighest,
+        MonsterSort::Folder,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MonsterSort::Name => "Name",
+            MonsterSort::ExpHighest => "XP (highest)",
+            MonsterSort::ExpLowest => "XP (lowest)",
+            MonsterSort::CrHighest => "CR (highest)",
+            MonsterSort::CrLowest => "CR (lowest)",
+            MonsterSort::AcHighest => "AC (highest)",
+            MonsterSort::HpHighest => "HP (highest)",
+            MonsterSort::Folder => "Folder",
+        }
+    }
+
+    /// Compares two monsters, falling back to the name so the order is stable.
+    pub fn compare(self, a: &Monster, b: &Monster) -> std::cmp::Ordering {
+        // Monsters without a rating sort below CR 0
+        let cr = |monster: &Monster| cr_value(&monster.cr).unwrap_or(-1.0);
+        let primary = match self {
+            MonsterSort::Name => std::cmp::Ordering::Equal,
+            MonsterSort::ExpHighest => b.exp.cmp(&a.exp),
+            MonsterSort::ExpLowest => a.exp.cmp(&b.exp),
+            MonsterSort::CrHighest => cr(b).total_cmp(&cr(a)),
+            MonsterSort::CrLowest => cr(a).total_cmp(&cr(b)),
+            MonsterSort::AcHighest => b.ac.cmp(&a.ac),
+            MonsterSort::HpHighest => b.hp.cmp(&a.hp),
+            MonsterSort::Folder => a.folder.cmp(&b.folder),
+        };
+        primary.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    }
+}
+
+/// Rejects folder names the folder dropdowns couldn't tell apart from "no folder".
+fn check_new_folder_name(folder: &str) -> io::Result<()> {
+    if folder.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Folder name is empty"));
+    }
+    if folder == NO_FOLDER_LABEL {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("\"{}\" is reserved", NO_FOLDER_LABEL)));
+    }
+    Ok(())
+}
+
+/// Creates a (possibly nested) folder.
+pub fn create_folder(folder: &str) -> io::Result<()> {
+    check_new_folder_name(&normalize_folder(folder)?)?;
+    folder_dir(folder)?;
+    Ok(())
+}
+
+/// Renames or moves a folder along with everything in it.
+pub fn rename_folder(old_folder: &str, new_folder: &str) -> io::Result<()> {
+    let old_folder = normalize_folder(old_folder)?;
+    let new_folder = normalize_folder(new_folder)?;
+    if old_folder.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Folder name is empty"));
+    }
+    check_new_folder_name(&new_folder)?;
+    if new_folder.starts_with(&format!("{}/", old_folder)) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "A folder can't be moved into itself"));
+    }
+
+    let old_path = folder_path(&old_folder)?;
+    if !old_path.is_dir() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "Folder not found"));
+    }
+    let new_path = folder_path(&new_folder)?;
+    if new_path.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, "A folder with that name already exists"));
+    }
+    if let Some(parent) = new_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&old_path, &new_path)?;
+    println!("Moved folder {:?} to {:?}", old_path, new_path);
+    Ok(())
+}
+
+/// Deletes a folder, which must be empty.
+pub fn delete_folder(folder: &str) -> io::Result<()> {
+    let folder = normalize_folder(folder)?;
+    if folder.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Folder name is empty"));
+    }
+    let path = folder_path(&folder)?;
+    if !path.is_dir() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "Folder not found"));
+    }
+    fs::remove_dir(path)
+}
+
+/// Moves a monster into another folder.
+pub fn move_monster(monster_id: &str, folder: &str) -> io::Result<()> {
+    let mut monster_data = read_monster(monster_id)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Monster not found"))?;
+    monster_data.folder = folder.to_string();
+    save_monster(monster_data)?;
+    Ok(())
+}
+
 /// Adds a new attack to an existing monster.
-pub fn add_attack_to_monster(monster_name: &str, new_attack: Attack) -> io::Result<()> {
-    let mut monster_data = read_monster(monster_name)
+pub fn add_attack_to_monster(monster_id: &str, new_attack: Attack) -> io::Result<()> {
+    let mut monster_data = read_monster(monster_id)
         .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Monster not found"))?;
 
     monster_data.attacks.push(new_attack);
 
-    save_monster(monster_data)
+    save_monster(monster_data)?;
+    Ok(())
+}
+
+/// Replaces a monster's spellcasting; `None` removes it.
+pub fn set_spellcasting(monster_id: &str, spellcasting: Option<Spellcasting>) -> io::Result<()> {
+    let mut monster_data = read_monster(monster_id)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Monster not found"))?;
+
+    monster_data.spellcasting = spellcasting;
+
+    save_monster(monster_data)?;
+    Ok(())
+}
+
+/// Replaces a monster's multiattack routines.
+pub fn set_multiattacks(monster_id: &str, multiattacks: Vec<Multiattack>) -> io::Result<()> {
+    let mut monster_data = read_monster(monster_id)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Monster not found"))?;
+
+    monster_data.multiattacks = multiattacks;
+
+    save_monster(monster_data)?;
+    Ok(())
 }
 
 /// Deletes an attack from a monster by name.
-pub fn delete_attack_from_monster(monster_name: &str, attack_name: &str) -> io::Result<()> {
-    let mut monster_data = read_monster(monster_name)
+pub fn delete_attack_from_monster(monster_id: &str, attack_name: &str) -> io::Result<()> {
+    let mut monster_data = read_monster(monster_id)
         .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Monster not found"))?;
     
     let original_len = monster_data.attacks.len();
     monster_data.attacks.retain(|a| a.attack_name != attack_name);
     
     if monster_data.attacks.len() < original_len {
+        // Routines can't use an attack that no longer exists
+        for routine in &mut monster_data.multiattacks {
+            routine.steps.retain(|step| step.attack_name != attack_name);
+        }
+        monster_data.multiattacks.retain(|routine| !routine.steps.is_empty());
         save_monster(monster_data)?;
         Ok(())
     } else {
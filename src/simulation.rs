@@ -4,16 +4,33 @@ use gtk::{prelude::*, Align, Box, DropDown, FlowBox, Frame, Label, ListBox, Orie
 use libadwaita::Application as AdwApplication;
 use gtk::ApplicationWindow as AdwWindow;
 use std::collections::HashMap;
-use std::fs::{ self, File };
-use std::io::{ self, Read, Write };
+use std::io;
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{ Cell, RefCell };
 use rand::Rng;
-use chrono;
 
-use super::monster_manager::{ self, Monster, Attack, get_base_path };
+use super::monster_manager::{ self, Monster, Attack, DamageModifier, WeaponMaterial };
 use super::ui_factory::{ UiFactory };
-use super::interface;
+use super::{ encounter_manager, interface };
+
+/// Options of the global roll mode dropdown, in display order.
+const ROLL_MODES: [&str; 3] = ["Natural", "Advantage", "Disadvantage"];
+
+/// Options of the per-card roll mode dropdown; "Global" follows the bottom bar dropdown.
+const CARD_ROLL_MODES: [&str; 4] = ["Global", "Natural", "Advantage", "Disadvantage"];
+
+/// What a d20 roll is for, since conditions affect attacks and saves differently.
+#[derive(Clone, Copy)]
+enum RollKind {
+    Attack,
+    // ability index in mod order
+    Save(usize),
+    // ability or skill check
+    Check,
+}
+
+/// How often the live simulation is written to the autosave file, on top of saving on every change.
+const AUTOSAVE_INTERVAL_SECS: u32 = 30;
 
 // =========================================================================
 // Data Models & States
@@ -26,6 +43,30 @@ struct Combatant {
     monster_template: Monster,
     current_hp: i32,
     max_hp: i32,
+    // card override of the global roll mode, None follows the global dropdown
+    #[serde(default)]
+    roll_mode: Option<String>,
+    #[serde(default)]
+    conditions: Vec<String>,
+    // picked on the card for group checks
+    #[serde(default)]
+    selected: bool,
+    // picked on the card as a target for other combatants' attacks
+    #[serde(default)]
+    target: bool,
+    // spell slots spent per level (index 0 is 1st level) and uses of per-day spells by name
+    #[serde(default)]
+    slots_used: [i32; 9],
+    #[serde(default)]
+    spell_uses: HashMap<String, i32>,
+    // name of the spell the combatant is concentrating on
+    #[serde(default)]
+    concentration: Option<String>,
+    // reset when the combatant's turn starts
+    #[serde(default)]
+    bonus_action_used: bool,
+    #[serde(default)]
+    reaction_used: bool,
 }
 
 /// A struct to hold the shared state of the simulation.
@@ -37,19 +78,88 @@ pub struct SimulationState {
     pub console_buffer: Rc<RefCell<gtk::TextBuffer>>,
     pub console_text_view: gtk::TextView,
     pub roll_mode_dropdown: gtk::DropDown,
+    pub round_spin_button: SpinButton,
+    encounter_name: Rc<RefCell<String>>,
+    created_at: String,
+    active_turn: Rc<RefCell<Option<String>>>,
+    unsaved_changes: Rc<Cell<bool>>,
+    // whether this session has a file in the encounter library that saving may replace
+    in_library: Rc<Cell<bool>>,
+    // the window's close handler for this view, disconnected when the view is left
+    close_handler: Rc<RefCell<Option<gtk::glib::SignalHandlerId>>>,
 }
 
+/// Current version of the saved session format. Files without a version are the old
+/// `active_simulation.json` layout and are upgraded when read.
+pub const SESSION_FORMAT_VERSION: u32 = 2;
+
+/// The saved form of an encounter, either prepared (not started yet) or paused mid-fight.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct StaticSimulationState {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub started: bool,
+    #[serde(default = "default_round")]
+    round: i32,
+    // instance name of the combatant whose turn it is
+    #[serde(default)]
+    active_turn: Option<String>,
+    #[serde(default = "default_roll_mode")]
+    roll_mode: String,
+    // RFC 3339 timestamps
+    #[serde(default)]
+    created_at: String,
+    #[serde(default)]
+    updated_at: String,
+    // set for encounters read from the library; kept in the autosave so a restored session knows it too
+    #[serde(default)]
+    pub in_library: bool,
     combatants: Vec<Combatant>,
     killed_monsters: Vec<Combatant>,
 }
 
+fn default_round() -> i32 {
+    1
+}
+
+fn default_roll_mode() -> String {
+    "Natural".to_string()
+}
+
 impl StaticSimulationState {
+    fn new_prepared(name: String, combatants: Vec<Combatant>) -> Self {
+        let now = chrono::Local::now().to_rfc3339();
+        Self {
+            version: SESSION_FORMAT_VERSION,
+            name,
+            started: false,
+            round: default_round(),
+            active_turn: None,
+            roll_mode: default_roll_mode(),
+            created_at: now.clone(),
+            updated_at: now,
+            in_library: false,
+            combatants,
+            killed_monsters: Vec::new(),
+        }
+    }
+
     fn make_static(simulation_state: &SimulationState) -> Self {
         let combatants = simulation_state.combatants.borrow().clone();
         let killed_monsters = simulation_state.killed_monsters.borrow().clone();
         Self {
+            version: SESSION_FORMAT_VERSION,
+            name: simulation_state.encounter_name.borrow().clone(),
+            started: true,
+            round: simulation_state.round_spin_button.value() as i32,
+            active_turn: simulation_state.active_turn.borrow().clone(),
+            roll_mode: get_dropdown_text(&simulation_state.roll_mode_dropdown),
+            created_at: simulation_state.created_at.clone(),
+            updated_at: chrono::Local::now().to_rfc3339(),
+            in_library: simulation_state.in_library.get(),
             combatants,
             killed_monsters,
         }
@@ -65,6 +175,45 @@ impl StaticSimulationState {
         for mon in self.killed_monsters {
             killed.push(mon);
         }
+
+        simulation_state.round_spin_button.set_value(self.round as f64);
+        *simulation_state.active_turn.borrow_mut() = self.active_turn;
+        if let Some(pos) = ROLL_MODES.iter().position(|m| *m == self.roll_mode) {
+            simulation_state.roll_mode_dropdown.set_selected(pos as u32);
+        }
+    }
+
+    /// Brings a session read from disk up to the current format.
+    pub fn upgrade_format(&mut self) {
+        if self.version < 1 {
+            // Version 0 is the old single active_simulation.json; it only lacked the fields
+            // that serde already defaulted, so just stamp it.
+            if self.created_at.is_empty() {
+                self.created_at = chrono::Local::now().to_rfc3339();
+            }
+            if self.updated_at.is_empty() {
+                self.updated_at = self.created_at.clone();
+            }
+        }
+        if self.version < 2 {
+            // Monsters used to be keyed by name; link the copies back to library ids
+            let library = monster_manager::read_all_monsters();
+            for combatant in self.combatants.iter_mut().chain(self.killed_monsters.iter_mut()) {
+                if combatant.monster_template.id.is_empty()
+                    && let Some(monster) = library.iter().find(|m| m.name == combatant.monster_template.name)
+                {
+                    combatant.monster_template.id = monster.id.clone();
+                }
+            }
+        }
+        self.version = SESSION_FORMAT_VERSION;
+    }
+
+    /// Human readable "last saved" time for lists.
+    fn updated_at_display(&self) -> String {
+        chrono::DateTime::parse_from_rfc3339(&self.updated_at)
+            .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|_| "unknown".to_string())
     }
 }
 
@@ -90,23 +239,38 @@ pub fn show_simulation_setup_menu(app: &AdwApplication, parent_window: &AdwWindo
     let title = UiFactory::create_label("Select Combatants", Align::Center, false, &["title-3"]);
     main_vbox.append(&title);
 
+    let all_monsters = monster_manager::read_all_monsters();
+
+    // Filtering only hides rows; quantities already entered are kept
+    let filter_bar = interface::MonsterFilterBar::new(&all_monsters);
+    main_vbox.append(&filter_bar.container);
+
     let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
     let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
     list_box.add_css_class("boxed-list");
 
-    let all_monsters = monster_manager::read_all_monsters();
     let mut spin_buttons: Vec<(SpinButton, Monster)> = Vec::new();
 
     if all_monsters.is_empty() {
         list_box.append(&Label::new(Some("No monsters exist. Please create one first.")));
     } else {
-        for monster in all_monsters {
+        for (index, monster) in all_monsters.into_iter().enumerate() {
             let row = UiFactory::create_box(Orientation::Horizontal, 6, (6, 6, 6, 6));
+            row.set_widget_name(&index.to_string());
             let name_label = UiFactory::create_label(&monster.name, Align::Start, false, &[]);
             name_label.set_hexpand(true);
+            row.append(&name_label);
+
+            if !monster.folder.is_empty() {
+                let folder_label = UiFactory::create_label(&monster.folder, Align::End, false, &["dim-label"]);
+                row.append(&folder_label);
+            }
+            if !monster.cr.is_empty() {
+                let cr_label = UiFactory::create_label(&format!("CR {}", monster.cr), Align::End, false, &["dim-label"]);
+                row.append(&cr_label);
+            }
 
             let spin_button = UiFactory::create_spin_button(0.0, 100.0, 1.0, 0.0);
-            row.append(&name_label);
             row.append(&spin_button);
             list_box.append(&row);
 
@@ -114,50 +278,167 @@ pub fn show_simulation_setup_menu(app: &AdwApplication, parent_window: &AdwWindo
         }
     }
 
+    let spin_buttons = Rc::new(spin_buttons);
+
+    let xp_label = UiFactory::create_label("", Align::End, false, &["dim-label"]);
+    let update_xp_label = {
+        let xp_label = xp_label.clone();
+        let spin_buttons = Rc::clone(&spin_buttons);
+        move || xp_label.set_text(&describe_encounter_xp(&collect_selected_monsters(&spin_buttons)))
+    };
+    update_xp_label();
+    let update_xp_label = Rc::new(update_xp_label);
+    for (spin_button, _) in spin_buttons.iter() {
+        let update_clone = Rc::clone(&update_xp_label);
+        spin_button.connect_value_changed(move |_| update_clone());
+    }
+
+    let spin_buttons_clone = Rc::clone(&spin_buttons);
+    let filter_bar_clone = filter_bar.clone();
+    list_box.set_filter_func(move |row| {
+        setup_row_monster(row, &spin_buttons_clone)
+            .is_none_or(|monster| filter_bar_clone.filter().matches(monster))
+    });
+
+    let spin_buttons_clone = Rc::clone(&spin_buttons);
+    let filter_bar_clone = filter_bar.clone();
+    list_box.set_sort_func(move |row_a, row_b| {
+        match (setup_row_monster(row_a, &spin_buttons_clone), setup_row_monster(row_b, &spin_buttons_clone)) {
+            (Some(a), Some(b)) => filter_bar_clone.sort().compare(a, b).into(),
+            _ => gtk::Ordering::Equal,
+        }
+    });
+
+    let list_box_clone = list_box.clone();
+    filter_bar.connect_changed(move || {
+        list_box_clone.invalidate_filter();
+        list_box_clone.invalidate_sort();
+    });
+
     scrolled_window.set_child(Some(&list_box));
     main_vbox.append(&scrolled_window);
+    main_vbox.append(&xp_label);
 
+    let name_row = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    let name_label = UiFactory::create_label("Encounter Name:", Align::Start, false, &[]);
+    let name_entry = UiFactory::create_entry(None, Some("Enter name..."), 20);
+    name_entry.set_hexpand(true);
+    name_row.append(&name_label);
+    name_row.append(&name_entry);
+    main_vbox.append(&name_row);
+    let name_error_label = UiFactory::create_label("", Align::Start, false, &["error"]);
+    main_vbox.append(&name_error_label);
+
+    let button_row = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_row.set_halign(Align::End);
+    let prepare_button = UiFactory::create_button("Save as Prepared", Align::End, None);
     let start_button = UiFactory::create_button(
         "Start Simulation",
         Align::End,
         Some("suggested-action")
     );
-    main_vbox.append(&start_button);
+    button_row.append(&prepare_button);
+    button_row.append(&start_button);
+    main_vbox.append(&button_row);
+
+    let window_clone = window.clone();
+    let name_entry_clone = name_entry.clone();
+    let name_error_label_clone = name_error_label.clone();
+    let spin_buttons_clone = Rc::clone(&spin_buttons);
+    prepare_button.connect_clicked(move |_| {
+        let selected_monsters = collect_selected_monsters(&spin_buttons_clone);
+        if selected_monsters.is_empty() {
+            println!("No monsters selected for the encounter.");
+            return;
+        }
+        let Some(name) = new_encounter_name(&name_entry_clone, &name_error_label_clone) else {
+            return;
+        };
+
+        let encounter = StaticSimulationState::new_prepared(name, build_combatants(selected_monsters));
+        if let Err(e) = encounter_manager::save_new_encounter(&encounter) {
+            name_error_label_clone.set_text(&format!("Failed to save encounter: {}", e));
+            return;
+        }
+        window_clone.close();
+    });
 
     let window_clone = window.clone();
     let app_clone = app.clone();
     let parent_window_clone = parent_window.clone();
 
     start_button.connect_clicked(move |_| {
-        let mut selected_monsters: Vec<(Monster, i32)> = Vec::new();
-        for (spin_button, monster) in &spin_buttons {
-            let count = spin_button.value() as i32;
-            if count > 0 {
-                selected_monsters.push((monster.clone(), count));
-            }
-        }
+        let selected_monsters = collect_selected_monsters(&spin_buttons);
 
         if selected_monsters.is_empty() {
             println!("No monsters selected for the simulation.");
             return;
         }
+        let Some(name) = new_encounter_name(&name_entry, &name_error_label) else {
+            return;
+        };
 
+        let encounter = StaticSimulationState::new_prepared(name, build_combatants(selected_monsters));
         window_clone.close();
-        start_simulation_view(&app_clone, &parent_window_clone, selected_monsters);
+        start_simulation_view(&app_clone, &parent_window_clone, encounter);
     });
 
     window.set_child(Some(&main_vbox));
     window.present();
 }
 
-/// This function takes the selected monsters and builds the main card view.
-pub fn start_simulation_view(
-    app: &AdwApplication,
-    window: &AdwWindow,
-    selected_monsters: Vec<(Monster, i32)>
-) {
-    window.set_title(Some("Mass Combat Decider - Simulation"));
-    // --- Generate the list of individual combatants ---
+/// Looks up the monster shown in a setup row. Rows are named after their original index,
+/// since `ListBoxRow::index` changes once the list is sorted.
+fn setup_row_monster<'a>(row: &gtk::ListBoxRow, spin_buttons: &'a [(SpinButton, Monster)]) -> Option<&'a Monster> {
+    let index: usize = row.child()?.widget_name().parse().ok()?;
+    spin_buttons.get(index).map(|(_, monster)| monster)
+}
+
+/// Reads the chosen quantity for each monster out of a setup list.
+fn collect_selected_monsters(spin_buttons: &[(SpinButton, Monster)]) -> Vec<(Monster, i32)> {
+    let mut selected_monsters: Vec<(Monster, i32)> = Vec::new();
+    for (spin_button, monster) in spin_buttons {
+        let count = spin_button.value() as i32;
+        if count > 0 {
+            selected_monsters.push((monster.clone(), count));
+        }
+    }
+    selected_monsters
+}
+
+/// Summarises the XP of the selected monsters, including the multiplier for fighting several at once.
+fn describe_encounter_xp(selected_monsters: &[(Monster, i32)]) -> String {
+    let count: i32 = selected_monsters.iter().map(|(_, count)| count).sum();
+    if count == 0 {
+        return "No monsters selected".to_string();
+    }
+    let (total, adjusted) = monster_manager::encounter_xp(selected_monsters);
+    let highest_cr = selected_monsters
+        .iter()
+        .filter_map(|(monster, _)| monster_manager::cr_value(&monster.cr).map(|value| (value, monster.cr.as_str())))
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, cr)| format!(", highest CR {}", cr))
+        .unwrap_or_default();
+    format!("{} monster(s): {} XP, {} adjusted for group size{}", count, total, adjusted, highest_cr)
+}
+
+/// Uses the typed encounter name, or falls back to a unique one stamped with the current time.
+/// Typed names that are already in the library are refused so a paused fight is never replaced.
+fn new_encounter_name(name_entry: &gtk::Entry, error_label: &Label) -> Option<String> {
+    let name = name_entry.text().trim().to_string();
+    if name.is_empty() {
+        let stamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+        return Some(encounter_manager::unique_encounter_name(&format!("Encounter {}", stamp)));
+    }
+    if encounter_manager::encounter_exists(&name) {
+        error_label.set_text(&format!("An encounter named \"{}\" already exists. Choose another name or load it from the library.", name));
+        return None;
+    }
+    Some(name)
+}
+
+/// Generates the list of individual combatants for the selected monsters.
+fn build_combatants(selected_monsters: Vec<(Monster, i32)>) -> Vec<Combatant> {
     let mut combatants: Vec<Combatant> = Vec::new();
     let mut name_counts: HashMap<String, i32> = HashMap::new();
 
@@ -177,9 +458,101 @@ pub fn start_simulation_view(
                 current_hp: monster.hp,
                 monster_template: monster.clone(),
                 max_hp: monster.hp,
+                roll_mode: None,
+                conditions: Vec::new(),
+                selected: false,
+                target: false,
+                slots_used: [0; 9],
+                spell_uses: HashMap::new(),
+                concentration: None,
+                bonus_action_used: false,
+                reaction_used: false,
             });
         }
     }
+    combatants
+}
+
+/// This function takes a prepared or paused encounter and builds the main card view.
+pub fn start_simulation_view(
+    app: &AdwApplication,
+    window: &AdwWindow,
+    encounter: StaticSimulationState
+) {
+    // A fight that isn't in the library yet would be lost on exit
+    let unsaved = !encounter.in_library;
+    build_simulation_view(app, window, encounter, unsaved);
+}
+
+/// Offers to restore the autosaved simulation left behind by a crash or an unclean exit.
+pub fn show_autosave_recovery_menu(app: &AdwApplication, parent_window: &AdwWindow) {
+    let Some(encounter) = encounter_manager::read_autosave() else {
+        let _ = encounter_manager::clear_autosave();
+        return;
+    };
+
+    let window = AdwWindow::builder()
+        .application(app)
+        .title("Restore Simulation")
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(400)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let message = UiFactory::create_label(
+        &format!(
+            "The simulation \"{}\" (round {}, {} combatants) was not exited cleanly.\nDo you want to restore it?",
+            encounter.name,
+            encounter.round,
+            encounter.combatants.len()
+        ),
+        Align::Center,
+        false,
+        &[]
+    );
+    message.set_wrap(true);
+    main_vbox.append(&message);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let restore_button = UiFactory::create_button("Restore", Align::Center, Some("suggested-action"));
+    let discard_button = UiFactory::create_button("Discard", Align::Center, Some("destructive-action"));
+    button_box.append(&restore_button);
+    button_box.append(&discard_button);
+    main_vbox.append(&button_box);
+
+    let window_clone = window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    restore_button.connect_clicked(move |_| {
+        window_clone.close();
+        build_simulation_view(&app_clone, &parent_window_clone, encounter.clone(), true);
+    });
+
+    let window_clone = window.clone();
+    discard_button.connect_clicked(move |_| {
+        if let Err(e) = encounter_manager::clear_autosave() {
+            eprintln!("Failed to remove autosave: {}", e);
+        }
+        window_clone.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// Builds the live card view. `unsaved` marks the fight as having changes the library doesn't have yet.
+fn build_simulation_view(
+    app: &AdwApplication,
+    window: &AdwWindow,
+    encounter: StaticSimulationState,
+    unsaved: bool
+) {
+    window.set_title(Some(&format!("Mass Combat Decider - {}", encounter.name)));
 
     let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
     main_vbox.set_vexpand(true);
@@ -200,9 +573,11 @@ pub fn start_simulation_view(
 
     let round_label = Label::new(Some("Round:"));
     let round_spin_button = UiFactory::create_spin_button(1.0, 999.0, 1.0, 1.0);
+    let next_turn_button = UiFactory::create_button("Next Turn", Align::Center, None);
 
     round_box.append(&round_label);
     round_box.append(&round_spin_button);
+    round_box.append(&next_turn_button);
     top_row.append(&simulation_title);
     top_row.append(&round_box);
     main_vbox.append(&top_row);
@@ -244,11 +619,10 @@ pub fn start_simulation_view(
         .margin_end(12)
         .build();
 
-    let shared_state = Rc::new(RefCell::new(combatants));
+    let shared_state = Rc::new(RefCell::new(Vec::new()));
 
     // --- Roll Mode DropDown Setup ---
-    let mode_options = ["Natural", "Advantage", "Disadvantage"];
-    let string_list = gtk::StringList::new(&mode_options);
+    let string_list = gtk::StringList::new(&ROLL_MODES);
     let roll_mode_dropdown = gtk::DropDown
         ::builder()
         .model(&string_list)
@@ -263,19 +637,46 @@ pub fn start_simulation_view(
         console_buffer: Rc::clone(&console_buffer),
         console_text_view: console_text_view.clone(),
         roll_mode_dropdown: roll_mode_dropdown.clone(),
+        round_spin_button: round_spin_button.clone(),
+        encounter_name: Rc::new(RefCell::new(encounter.name.clone())),
+        created_at: encounter.created_at.clone(),
+        active_turn: Rc::new(RefCell::new(None)),
+        unsaved_changes: Rc::new(Cell::new(unsaved)),
+        in_library: Rc::new(Cell::new(encounter.in_library)),
+        close_handler: Rc::new(RefCell::new(None)),
     };
 
-    if check_for_simulation() {
-        if let Some(static_sim) = get_simulation() {
-            let _ = remove_simulation_file();
-            static_sim.replace_with_static(&simulation_state);
+    encounter.replace_with_static(&simulation_state);
+    autosave_simulation(&simulation_state);
+
+    let simulation_state_clone = simulation_state.clone();
+    round_spin_button.connect_value_changed(move |_| {
+        mark_simulation_changed(&simulation_state_clone);
+    });
+
+    let simulation_state_clone = simulation_state.clone();
+    roll_mode_dropdown.connect_selected_notify(move |_| {
+        // cards show their effective mode, so they need redrawing
+        rebuild_cards(&simulation_state_clone);
+        mark_simulation_changed(&simulation_state_clone);
+    });
+
+    let simulation_state_clone = simulation_state.clone();
+    next_turn_button.connect_clicked(move |_| {
+        advance_turn(&simulation_state_clone);
+    });
+
+    // Periodic autosave; stops once the simulation view has been replaced
+    let simulation_state_clone = simulation_state.clone();
+    gtk::glib::timeout_add_seconds_local(AUTOSAVE_INTERVAL_SECS, move || {
+        if simulation_state_clone.flow_box.root().is_none() {
+            return gtk::glib::ControlFlow::Break;
         }
-    }
+        autosave_simulation(&simulation_state_clone);
+        gtk::glib::ControlFlow::Continue
+    });
 
-    for combatant in shared_state.borrow().iter() {
-        let card = create_combatant_card(combatant, &simulation_state);
-        simulation_state.flow_box.insert(&card, -1);
-    }
+    rebuild_cards(&simulation_state);
 
     // --- Bottom Layout: Split Button Action Bar ---
     let bottom_bar = UiFactory::create_box(Orientation::Horizontal, 0, (0, 12, 0, 0));
@@ -307,8 +708,12 @@ pub fn start_simulation_view(
     let window_clone_save = window.clone();
     let simulation_state_clone = simulation_state.clone();
     save_button.connect_clicked(move |_| {
-        let _ = save_simulation(&simulation_state_clone);
-        interface::switch_to_monster_list(&app_clone_save, &window_clone_save);
+        let app_exit = app_clone_save.clone();
+        let window_exit = window_clone_save.clone();
+        let state_exit = simulation_state_clone.clone();
+        save_simulation_then(&app_clone_save, &window_clone_save, &simulation_state_clone, move || {
+            exit_simulation(&app_exit, &window_exit, &state_exit);
+        });
     });
     left_actions_box.append(&save_button);
 
@@ -319,11 +724,32 @@ pub fn start_simulation_view(
     );
     let app_clone_exit = app.clone();
     let window_clone_exit = window.clone();
+    let simulation_state_clone = simulation_state.clone();
     exit_button.connect_clicked(move |_| {
-        interface::switch_to_monster_list(&app_clone_exit, &window_clone_exit);
+        if simulation_state_clone.unsaved_changes.get() {
+            show_confirm_exit_menu(&app_clone_exit, &window_clone_exit, simulation_state_clone.clone(), false);
+        } else {
+            exit_simulation(&app_clone_exit, &window_clone_exit, &simulation_state_clone);
+        }
     });
     left_actions_box.append(&exit_button);
 
+    // Closing the window goes through the same unsaved-changes check as "Exit Simulation"
+    let app_clone_close = app.clone();
+    let simulation_state_clone = simulation_state.clone();
+    let close_handler = window.connect_close_request(move |window| {
+        if simulation_state_clone.unsaved_changes.get() {
+            show_confirm_exit_menu(&app_clone_close, window, simulation_state_clone.clone(), true);
+            return gtk::glib::Propagation::Stop;
+        }
+        if let Err(e) = encounter_manager::clear_autosave() {
+            eprintln!("Failed to remove autosave: {}", e);
+        }
+        gtk::glib::Propagation::Proceed
+    });
+    // The window outlives the simulation view, so the handler is dropped again on exit
+    *simulation_state.close_handler.borrow_mut() = Some(close_handler);
+
     let right_actions_box = UiFactory::create_box(Orientation::Horizontal, 12, (0, 0, 0, 0));
     right_actions_box.set_halign(Align::End);
 
@@ -339,6 +765,22 @@ pub fn start_simulation_view(
         show_killed_monsters_menu(&app_clone, &window_clone_killed, simulation_state_clone.clone());
     });
 
+    // Group checks roll for every combatant ticked on its card
+    let group_skills: Vec<&str> = monster_manager::SKILLS.iter().map(|(skill, _)| *skill).collect();
+    let stealth_index = group_skills.iter().position(|s| *s == "Stealth").unwrap_or(0) as u32;
+    let group_skill_dropdown = UiFactory::create_dropdown(&group_skills, Some(stealth_index), None);
+    group_skill_dropdown.set_valign(Align::Center);
+    let group_check_button = UiFactory::create_button("Group Check", Align::Center, None);
+    let simulation_state_clone = simulation_state.clone();
+    let group_skill_dropdown_clone = group_skill_dropdown.clone();
+    group_check_button.connect_clicked(move |_| {
+        if let Some(&(skill, _)) = monster_manager::SKILLS.get(group_skill_dropdown_clone.selected() as usize) {
+            roll_group_check(&simulation_state_clone, skill);
+        }
+    });
+
+    right_actions_box.append(&group_skill_dropdown);
+    right_actions_box.append(&group_check_button);
     right_actions_box.append(&roll_mode_dropdown);
     right_actions_box.append(&killed_button);
 
@@ -352,95 +794,119 @@ pub fn start_simulation_view(
     gtk::prelude::RootExt::set_focus(window, Some(&main_vbox)); // fixes a minor bug where the round scroll box would get automaticly focused (anoying)
 }
 
-// =========================================================================
-// Modal Menu Operations (Killed List & Live Editing)
-// =========================================================================
+/// Leaves the live view for the monster list and drops the crash-recovery autosave.
+fn exit_simulation(app: &AdwApplication, window: &AdwWindow, simulation_state: &SimulationState) {
+    release_close_handler(window, simulation_state);
+    if let Err(e) = encounter_manager::clear_autosave() {
+        eprintln!("Failed to remove autosave: {}", e);
+    }
+    interface::switch_to_monster_list(app, window);
+}
 
-fn show_killed_monsters_menu(
+/// Disconnects the view's close handler from the window, freeing the state it holds.
+fn release_close_handler(window: &AdwWindow, simulation_state: &SimulationState) {
+    if let Some(handler) = simulation_state.close_handler.borrow_mut().take() {
+        window.disconnect(handler);
+    }
+}
+
+/// Leaves the simulation after the user confirmed: closes the whole window when
+/// `close_window` is set, otherwise goes back to the monster list.
+fn finish_exit(app: &AdwApplication, window: &AdwWindow, simulation_state: &SimulationState, close_window: bool) {
+    if close_window {
+        // nothing is left to confirm, so the close request goes through this time
+        release_close_handler(window, simulation_state);
+        if let Err(e) = encounter_manager::clear_autosave() {
+            eprintln!("Failed to remove autosave: {}", e);
+        }
+        window.close();
+    } else {
+        exit_simulation(app, window, simulation_state);
+    }
+}
+
+/// Asks before throwing away a simulation with unsaved changes.
+/// `close_window` is set when the window itself is being closed rather than the simulation exited.
+fn show_confirm_exit_menu(
     app: &AdwApplication,
     parent_window: &AdwWindow,
-    simulation_state: SimulationState
+    simulation_state: SimulationState,
+    close_window: bool
 ) {
     let window = AdwWindow::builder()
         .application(app)
-        .title("Killed Monsters")
+        .title("Exit Simulation")
         .transient_for(parent_window)
         .modal(true)
-        .default_width(450)
-        .default_height(500)
+        .default_width(400)
         .build();
 
     let header_bar = libadwaita::HeaderBar::new();
     window.set_titlebar(Some(&header_bar));
 
     let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
-    let title = UiFactory::create_label(
-        "Killed Monsters and Total XP",
+    let message = UiFactory::create_label(
+        "This simulation has unsaved changes. Save them before exiting?",
         Align::Center,
         false,
-        &["title-3"]
+        &[]
     );
-    main_vbox.append(&title);
-
-    let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
-    let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
-    list_box.add_css_class("boxed-list");
-
-    let killed_monsters = simulation_state.killed_monsters.borrow();
-    let mut total_xp = 0;
-
-    if killed_monsters.is_empty() {
-        list_box.append(&Label::new(Some("No monsters have been killed yet.")));
-    } else {
-        for combatant in killed_monsters.iter() {
-            let row = UiFactory::create_box(Orientation::Horizontal, 6, (6, 6, 6, 6));
-            let label_text = format!(
-                "{} ({} XP)",
-                combatant.instance_name,
-                combatant.monster_template.exp
-            );
-            let name_label = UiFactory::create_label(&label_text, Align::Start, false, &[]);
-            name_label.set_hexpand(true);
-
-            row.append(&name_label);
-            list_box.append(&row);
-            total_xp += combatant.monster_template.exp;
-        }
-    }
+    message.set_wrap(true);
+    main_vbox.append(&message);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let save_button = UiFactory::create_button("Save and Exit", Align::Center, Some("suggested-action"));
+    let discard_button = UiFactory::create_button("Discard", Align::Center, Some("destructive-action"));
+    let cancel_button = UiFactory::create_button("Cancel", Align::Center, None);
+    button_box.append(&save_button);
+    button_box.append(&discard_button);
+    button_box.append(&cancel_button);
+    main_vbox.append(&button_box);
 
-    scrolled_window.set_child(Some(&list_box));
-    main_vbox.append(&scrolled_window);
+    let window_clone = window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    let simulation_state_clone = simulation_state.clone();
+    save_button.connect_clicked(move |_| {
+        window_clone.close();
+        let app_exit = app_clone.clone();
+        let parent_exit = parent_window_clone.clone();
+        let state_exit = simulation_state_clone.clone();
+        save_simulation_then(&app_clone, &parent_window_clone, &simulation_state_clone, move || {
+            finish_exit(&app_exit, &parent_exit, &state_exit, close_window);
+        });
+    });
 
-    let xp_label = UiFactory::create_label(
-        &format!("Total XP: {}", total_xp),
-        Align::End,
-        false,
-        &["title-4", "suggested-action"]
-    );
-    main_vbox.append(&xp_label);
+    let window_clone = window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    discard_button.connect_clicked(move |_| {
+        window_clone.close();
+        finish_exit(&app_clone, &parent_window_clone, &simulation_state, close_window);
+    });
 
-    let close_button = UiFactory::create_button("Close", Align::End, Some("destructive-action"));
     let window_clone = window.clone();
-    close_button.connect_clicked(move |_| {
+    cancel_button.connect_clicked(move |_| {
         window_clone.close();
     });
-    main_vbox.append(&close_button);
 
     window.set_child(Some(&main_vbox));
     window.present();
 }
 
-pub fn show_edit_simulation_menu(
-    app: &AdwApplication,
-    parent_window: &AdwWindow,
-    simulation_state: SimulationState
-) {
+// =========================================================================
+// Encounter Library
+// =========================================================================
+
+/// Lists every saved encounter with controls to load, rename, duplicate or delete it.
+pub fn show_encounter_library(app: &AdwApplication, parent_window: &AdwWindow) {
     let window = AdwWindow::builder()
         .application(app)
-        .title("Edit Simulation")
+        .title("Encounters")
         .transient_for(parent_window)
         .modal(true)
-        .default_width(450)
+        .default_width(600)
         .default_height(500)
         .build();
 
@@ -448,33 +914,402 @@ pub fn show_edit_simulation_menu(
     window.set_titlebar(Some(&header_bar));
 
     let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
-    let title = UiFactory::create_label("Edit Combatants", Align::Center, false, &["title-3"]);
+    let title = UiFactory::create_label("Saved Encounters", Align::Center, false, &["title-3"]);
     main_vbox.append(&title);
 
     let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
     let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
     list_box.add_css_class("boxed-list");
 
-    let all_monsters = monster_manager::read_all_monsters();
-    let mut spin_buttons: Vec<(SpinButton, Monster)> = Vec::new();
-    let initial_counts: HashMap<String, i32> = {
-        let combatants = simulation_state.combatants.borrow();
-        let mut counts = HashMap::new();
-        for combatant in combatants.iter() {
-            *counts.entry(combatant.monster_template.name.clone()).or_insert(0) += 1;
+    let encounters = encounter_manager::list_encounters();
+    if encounters.is_empty() {
+        list_box.append(&Label::new(Some("No saved encounters yet.")));
+    } else {
+        for encounter in encounters {
+            let row = create_encounter_row(encounter, &window, app, parent_window);
+            list_box.append(&row);
         }
-        counts
-    };
+    }
 
-    if all_monsters.is_empty() {
-        list_box.append(&Label::new(Some("No monsters exist. Please create one first.")));
-    } else {
-        for monster in all_monsters {
-            let row = UiFactory::create_box(Orientation::Horizontal, 6, (6, 6, 6, 6));
-            let name_label = UiFactory::create_label(&monster.name, Align::Start, false, &[]);
-            name_label.set_hexpand(true);
+    scrolled_window.set_child(Some(&list_box));
+    main_vbox.append(&scrolled_window);
 
-            let initial_value = *initial_counts.get(&monster.name).unwrap_or(&0) as f64;
+    let close_button = UiFactory::create_button("Close", Align::End, None);
+    let window_clone = window.clone();
+    close_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+    main_vbox.append(&close_button);
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// Helper function for a single row of the encounter library
+fn create_encounter_row(
+    encounter: StaticSimulationState,
+    library_window: &AdwWindow,
+    app: &AdwApplication,
+    parent_window: &AdwWindow
+) -> Box {
+    let row = UiFactory::create_box(Orientation::Horizontal, 12, (6, 6, 12, 12));
+
+    let info_vbox = UiFactory::create_box(Orientation::Vertical, 3, (0, 0, 0, 0));
+    info_vbox.set_hexpand(true);
+    let name_label = UiFactory::create_label(
+        &format!("<b>{}</b>", gtk::glib::markup_escape_text(&encounter.name)),
+        Align::Start,
+        true,
+        &[]
+    );
+    let status_label = UiFactory::create_label(
+        &format!(
+            "{} (round {}), {} combatants, {} killed\nLast saved: {}",
+            if encounter.started { "In progress" } else { "Prepared" },
+            encounter.round,
+            encounter.combatants.len(),
+            encounter.killed_monsters.len(),
+            encounter.updated_at_display()
+        ),
+        Align::Start,
+        false,
+        &[]
+    );
+    info_vbox.append(&name_label);
+    info_vbox.append(&status_label);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let load_button = UiFactory::create_button("Load", Align::Center, Some("suggested-action"));
+    let rename_button = UiFactory::create_button("Rename", Align::Center, None);
+    let duplicate_button = UiFactory::create_button("Duplicate", Align::Center, None);
+    let delete_button = UiFactory::create_button("Delete", Align::Center, Some("destructive-action"));
+    button_box.append(&load_button);
+    button_box.append(&rename_button);
+    button_box.append(&duplicate_button);
+    button_box.append(&delete_button);
+
+    let name = encounter.name.clone();
+
+    let library_window_clone = library_window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    load_button.connect_clicked(move |_| {
+        library_window_clone.close();
+        start_simulation_view(&app_clone, &parent_window_clone, encounter.clone());
+    });
+
+    let library_window_clone = library_window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    let old_name = name.clone();
+    rename_button.connect_clicked(move |_| {
+        let app_refresh = app_clone.clone();
+        let parent_refresh = parent_window_clone.clone();
+        let library_refresh = library_window_clone.clone();
+        let old_name = old_name.clone();
+        let initial_name = old_name.clone();
+        show_encounter_name_prompt(
+            &app_clone,
+            &library_window_clone,
+            "Rename Encounter",
+            &initial_name,
+            move |new_name| {
+                encounter_manager::rename_encounter(&old_name, new_name)?;
+                library_refresh.close();
+                show_encounter_library(&app_refresh, &parent_refresh);
+                Ok(())
+            }
+        );
+    });
+
+    let library_window_clone = library_window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    let source_name = name.clone();
+    duplicate_button.connect_clicked(move |_| {
+        let app_refresh = app_clone.clone();
+        let parent_refresh = parent_window_clone.clone();
+        let library_refresh = library_window_clone.clone();
+        let source_name = source_name.clone();
+        show_encounter_name_prompt(
+            &app_clone,
+            &library_window_clone,
+            "Duplicate Encounter",
+            &format!("{} (copy)", source_name),
+            move |new_name| {
+                encounter_manager::duplicate_encounter(&source_name, new_name)?;
+                library_refresh.close();
+                show_encounter_library(&app_refresh, &parent_refresh);
+                Ok(())
+            }
+        );
+    });
+
+    let library_window_clone = library_window.clone();
+    let app_clone = app.clone();
+    let parent_window_clone = parent_window.clone();
+    delete_button.connect_clicked(move |_| {
+        let name = name.clone();
+        let library_refresh = library_window_clone.clone();
+        let app_refresh = app_clone.clone();
+        let parent_refresh = parent_window_clone.clone();
+        show_confirm_menu(
+            &app_clone,
+            &library_window_clone,
+            "Delete Encounter",
+            &format!("Delete the encounter \"{}\"? This can't be undone.", name),
+            "Delete",
+            move || {
+                if let Err(e) = encounter_manager::delete_encounter(&name) {
+                    eprintln!("Failed to delete encounter '{}': {}", name, e);
+                }
+                library_refresh.close();
+                interface::switch_to_monster_list(&app_refresh, &parent_refresh);
+                show_encounter_library(&app_refresh, &parent_refresh);
+            }
+        );
+    });
+
+    row.append(&info_vbox);
+    row.append(&button_box);
+    row
+}
+
+/// Small modal asking the user to confirm a destructive action before `on_confirm` runs.
+fn show_confirm_menu<F>(
+    app: &AdwApplication,
+    parent_window: &AdwWindow,
+    title: &str,
+    message: &str,
+    confirm_label: &str,
+    on_confirm: F
+)
+    where F: Fn() + 'static
+{
+    let window = AdwWindow::builder()
+        .application(app)
+        .title(title)
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(400)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let message = UiFactory::create_label(message, Align::Center, false, &[]);
+    message.set_wrap(true);
+    main_vbox.append(&message);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let confirm_button = UiFactory::create_button(confirm_label, Align::Center, Some("destructive-action"));
+    let cancel_button = UiFactory::create_button("Cancel", Align::Center, None);
+    button_box.append(&confirm_button);
+    button_box.append(&cancel_button);
+    main_vbox.append(&button_box);
+
+    let window_clone = window.clone();
+    confirm_button.connect_clicked(move |_| {
+        window_clone.close();
+        on_confirm();
+    });
+
+    let window_clone = window.clone();
+    cancel_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+/// Small modal asking for an encounter name. `on_accept` errors are shown in the window.
+fn show_encounter_name_prompt<F>(
+    app: &AdwApplication,
+    parent_window: &AdwWindow,
+    title: &str,
+    initial_name: &str,
+    on_accept: F
+)
+    where F: Fn(&str) -> io::Result<()> + 'static
+{
+    let window = AdwWindow::builder()
+        .application(app)
+        .title(title)
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(350)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let (name_label, name_entry) = UiFactory::create_label_entry_pair("Encounter Name:", "Enter name...");
+    name_entry.set_text(initial_name);
+    let error_label = UiFactory::create_label("", Align::Center, false, &[]);
+
+    let button_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    button_box.set_halign(Align::End);
+    let save_button = UiFactory::create_button("Save", Align::Center, Some("suggested-action"));
+    let cancel_button = UiFactory::create_button("Cancel", Align::Center, None);
+    button_box.append(&save_button);
+    button_box.append(&cancel_button);
+
+    main_vbox.append(&name_label);
+    main_vbox.append(&name_entry);
+    main_vbox.append(&error_label);
+    main_vbox.append(&button_box);
+
+    let window_clone = window.clone();
+    save_button.connect_clicked(move |_| {
+        let new_name = name_entry.text().trim().to_string();
+        if new_name.is_empty() {
+            error_label.set_text("Please enter a name.");
+            return;
+        }
+        match on_accept(&new_name) {
+            Ok(()) => window_clone.close(),
+            Err(e) => error_label.set_text(&format!("Failed: {}", e)),
+        }
+    });
+
+    let window_clone = window.clone();
+    cancel_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+// =========================================================================
+// Modal Menu Operations (Killed List & Live Editing)
+// =========================================================================
+
+fn show_killed_monsters_menu(
+    app: &AdwApplication,
+    parent_window: &AdwWindow,
+    simulation_state: SimulationState
+) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title("Killed Monsters")
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(450)
+        .default_height(500)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let title = UiFactory::create_label(
+        "Killed Monsters and Total XP",
+        Align::Center,
+        false,
+        &["title-3"]
+    );
+    main_vbox.append(&title);
+
+    let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
+    let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
+    list_box.add_css_class("boxed-list");
+
+    let killed_monsters = simulation_state.killed_monsters.borrow();
+    let mut total_xp = 0;
+
+    if killed_monsters.is_empty() {
+        list_box.append(&Label::new(Some("No monsters have been killed yet.")));
+    } else {
+        for combatant in killed_monsters.iter() {
+            let row = UiFactory::create_box(Orientation::Horizontal, 6, (6, 6, 6, 6));
+            let label_text = format!(
+                "{} ({} XP)",
+                combatant.instance_name,
+                combatant.monster_template.exp
+            );
+            let name_label = UiFactory::create_label(&label_text, Align::Start, false, &[]);
+            name_label.set_hexpand(true);
+
+            row.append(&name_label);
+            list_box.append(&row);
+            total_xp += combatant.monster_template.exp;
+        }
+    }
+
+    scrolled_window.set_child(Some(&list_box));
+    main_vbox.append(&scrolled_window);
+
+    let xp_label = UiFactory::create_label(
+        &format!("Total XP: {}", total_xp),
+        Align::End,
+        false,
+        &["title-4", "suggested-action"]
+    );
+    main_vbox.append(&xp_label);
+
+    let close_button = UiFactory::create_button("Close", Align::End, Some("destructive-action"));
+    let window_clone = window.clone();
+    close_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+    main_vbox.append(&close_button);
+
+    window.set_child(Some(&main_vbox));
+    window.present();
+}
+
+pub fn show_edit_simulation_menu(
+    app: &AdwApplication,
+    parent_window: &AdwWindow,
+    simulation_state: SimulationState
+) {
+    let window = AdwWindow::builder()
+        .application(app)
+        .title("Edit Simulation")
+        .transient_for(parent_window)
+        .modal(true)
+        .default_width(450)
+        .default_height(500)
+        .build();
+
+    let header_bar = libadwaita::HeaderBar::new();
+    window.set_titlebar(Some(&header_bar));
+
+    let main_vbox = UiFactory::create_box(Orientation::Vertical, 12, (12, 12, 12, 12));
+    let title = UiFactory::create_label("Edit Combatants", Align::Center, false, &["title-3"]);
+    main_vbox.append(&title);
+
+    let scrolled_window = UiFactory::create_scrolled_window(true, true, None);
+    let list_box = ListBox::builder().selection_mode(gtk::SelectionMode::None).build();
+    list_box.add_css_class("boxed-list");
+
+    let all_monsters = monster_manager::read_all_monsters();
+    let mut spin_buttons: Vec<(SpinButton, Monster)> = Vec::new();
+    let initial_counts: HashMap<String, i32> = {
+        let combatants = simulation_state.combatants.borrow();
+        let mut counts = HashMap::new();
+        for combatant in combatants.iter() {
+            *counts.entry(combatant.monster_template.id.clone()).or_insert(0) += 1;
+        }
+        counts
+    };
+
+    if all_monsters.is_empty() {
+        list_box.append(&Label::new(Some("No monsters exist. Please create one first.")));
+    } else {
+        for (index, monster) in all_monsters.into_iter().enumerate() {
+            let row = UiFactory::create_box(Orientation::Horizontal, 6, (6, 6, 6, 6));
+            row.set_widget_name(&index.to_string());
+            let name_label = UiFactory::create_label(&monster.name, Align::Start, false, &[]);
+            name_label.set_hexpand(true);
+
+            let initial_value = *initial_counts.get(&monster.id).unwrap_or(&0) as f64;
             let spin_button = UiFactory::create_spin_button(0.0, 100.0, 1.0, initial_value);
 
             row.append(&name_label);
@@ -496,13 +1331,7 @@ pub fn show_edit_simulation_menu(
 
     let window_clone = window.clone();
     start_button.connect_clicked(move |_| {
-        let mut selected_monsters: Vec<(Monster, i32)> = Vec::new();
-        for (spin_button, monster) in &spin_buttons {
-            let count = spin_button.value() as i32;
-            if count > 0 {
-                selected_monsters.push((monster.clone(), count));
-            }
-        }
+        let selected_monsters = collect_selected_monsters(&spin_buttons);
         window_clone.close();
         update_simulation_view(&selected_monsters, &simulation_state);
     });
@@ -520,8 +1349,8 @@ fn update_simulation_view(
     let mut existing_combatants_map: HashMap<String, Vec<Combatant>> = HashMap::new();
     for combatant in current_combatants.drain(..) {
         existing_combatants_map
-            .entry(combatant.monster_template.name.clone())
-            .or_insert_with(Vec::new)
+            .entry(combatant.monster_template.id.clone())
+            .or_default()
             .push(combatant);
     }
 
@@ -531,8 +1360,8 @@ fn update_simulation_view(
         let monster_name = &monster_template.name;
 
         let mut existing_of_type = existing_combatants_map
-            .remove(monster_name)
-            .unwrap_or_else(Vec::new);
+            .remove(&monster_template.id)
+            .unwrap_or_default();
         existing_of_type.sort_by(|a, b| a.instance_name.cmp(&b.instance_name));
 
         let num_existing = existing_of_type.len();
@@ -548,14 +1377,12 @@ fn update_simulation_view(
             } else {
                 let mut max_number = 0;
                 for existing_c in new_combatant_list.iter() {
-                    if existing_c.monster_template.name == *monster_name {
-                        if let Some(num_str) = existing_c.instance_name.split(' ').last() {
-                            if let Ok(num) = num_str.parse::<i32>() {
-                                if num > max_number {
-                                    max_number = num;
-                                }
-                            }
-                        }
+                    if existing_c.monster_template.id == monster_template.id
+                        && let Some(num_str) = existing_c.instance_name.split(' ').next_back()
+                        && let Ok(num) = num_str.parse::<i32>()
+                        && num > max_number
+                    {
+                        max_number = num;
                     }
                 }
                 let new_counter = max_number + 1;
@@ -571,21 +1398,135 @@ fn update_simulation_view(
                     current_hp: monster_template.hp,
                     monster_template: monster_template.clone(),
                     max_hp: monster_template.hp,
+                    roll_mode: None,
+                    conditions: Vec::new(),
+                    selected: false,
+                    target: false,
+                    slots_used: [0; 9],
+                    spell_uses: HashMap::new(),
+                    concentration: None,
+                    bonus_action_used: false,
+                    reaction_used: false,
                 });
             }
         }
     }
 
     *current_combatants = new_combatant_list;
+    drop(current_combatants);
 
+    rebuild_cards(simulation_state);
+    mark_simulation_changed(simulation_state);
+}
+
+/// Recreates every card from the current combatant list.
+fn rebuild_cards(simulation_state: &SimulationState) {
     while let Some(child) = simulation_state.flow_box.first_child() {
         simulation_state.flow_box.remove(&child);
     }
 
-    for combatant in current_combatants.iter() {
+    for combatant in simulation_state.combatants.borrow().iter() {
         let card = create_combatant_card(combatant, simulation_state);
         simulation_state.flow_box.insert(&card, -1);
     }
+    refresh_turn_indicator(simulation_state);
+}
+
+/// Hands the turn to the next combatant, rolling over into a new round after the last one.
+fn advance_turn(simulation_state: &SimulationState) {
+    let next_turn = {
+        let combatants = simulation_state.combatants.borrow();
+        if combatants.is_empty() {
+            return;
+        }
+
+        let current = simulation_state.active_turn.borrow().clone();
+        let current_pos = current.and_then(|name| combatants.iter().position(|c| c.instance_name == name));
+        match current_pos {
+            Some(pos) if pos + 1 < combatants.len() => combatants[pos + 1].instance_name.clone(),
+            Some(_) => {
+                let round = simulation_state.round_spin_button.value();
+                simulation_state.round_spin_button.set_value(round + 1.0);
+                combatants[0].instance_name.clone()
+            }
+            None => combatants[0].instance_name.clone(),
+        }
+    };
+    start_turn(simulation_state, next_turn);
+}
+
+/// Makes it `instance_name`'s turn, giving back its bonus action and reaction.
+fn start_turn(simulation_state: &SimulationState, next_turn: String) {
+    // A new turn gives back the bonus action and reaction
+    let reset = simulation_state.combatants
+        .borrow_mut()
+        .iter_mut()
+        .find(|c| c.instance_name == next_turn)
+        .is_some_and(|c| {
+            let was_used = c.bonus_action_used || c.reaction_used;
+            c.bonus_action_used = false;
+            c.reaction_used = false;
+            was_used
+        });
+    if reset {
+        refresh_card(simulation_state, &next_turn);
+    }
+
+    *simulation_state.active_turn.borrow_mut() = Some(next_turn);
+    refresh_turn_indicator(simulation_state);
+    mark_simulation_changed(simulation_state);
+}
+
+/// Recreates the card of one combatant in place.
+fn refresh_card(simulation_state: &SimulationState, instance_name: &str) {
+    let combatant = simulation_state.combatants
+        .borrow()
+        .iter()
+        .find(|c| c.instance_name == instance_name)
+        .cloned();
+    let Some(combatant) = combatant else {
+        return;
+    };
+
+    let mut child = simulation_state.flow_box.first_child();
+    while let Some(widget) = child {
+        if let Ok(flow_child) = widget.clone().downcast::<gtk::FlowBoxChild>()
+            && flow_child.child().is_some_and(|card| card.widget_name() == instance_name)
+        {
+            flow_child.set_child(Some(&create_combatant_card(&combatant, simulation_state)));
+            refresh_turn_indicator(simulation_state);
+            return;
+        }
+        child = widget.next_sibling();
+    }
+}
+
+/// Like `refresh_card`, but waits until the current signal handler has returned,
+/// for controls that sit on the card being replaced.
+fn refresh_card_later(simulation_state: &SimulationState, instance_name: &str) {
+    let simulation_state = simulation_state.clone();
+    let instance_name = instance_name.to_string();
+    gtk::glib::idle_add_local_once(move || {
+        refresh_card(&simulation_state, &instance_name);
+    });
+}
+
+/// Highlights the card of the combatant whose turn it is.
+fn refresh_turn_indicator(simulation_state: &SimulationState) {
+    let active = simulation_state.active_turn.borrow().clone();
+    let mut child = simulation_state.flow_box.first_child();
+    while let Some(flow_child) = child {
+        if let Some(card) = flow_child.first_child().and_then(|w| w.downcast::<Frame>().ok()) {
+            if active.as_deref() == Some(card.widget_name().as_str()) {
+                card.set_label(Some("Current Turn"));
+                card.add_css_class("active-turn");
+            } else {
+                card.set_label(None);
+                card.remove_css_class("active-turn");
+            }
+        }
+        child = flow_child.next_sibling();
+    }
 }
 
 // =========================================================================
@@ -605,6 +1546,112 @@ fn perform_d20_roll(mode: &str) -> (i32, Option<i32>) {
     }
 }
 
+/// Advantage or disadvantage a condition imposes on its own creature's rolls, if any.
+fn condition_roll_effect(condition: &str, kind: RollKind) -> Option<&'static str> {
+    match (condition, kind) {
+        ("Invisible", RollKind::Attack) => Some("Advantage"),
+        ("Blinded" | "Frightened" | "Poisoned" | "Prone" | "Restrained", RollKind::Attack) =>
+            Some("Disadvantage"),
+        ("Restrained", RollKind::Save(1)) => Some("Disadvantage"),
+        ("Frightened" | "Poisoned", RollKind::Check) => Some("Disadvantage"),
+        _ => None,
+    }
+}
+
+/// The condition that makes a Strength or Dexterity save fail automatically, if any.
+fn save_auto_fail_condition(combatant: &Combatant, ability_index: usize) -> Option<&str> {
+    if ability_index > 1 {
+        return None;
+    }
+    combatant.conditions
+        .iter()
+        .find(|c| matches!(c.as_str(), "Paralyzed" | "Stunned" | "Unconscious" | "Petrified"))
+        .map(String::as_str)
+}
+
+/// Advantage or disadvantage an attack gets from a condition of its target, if any.
+fn target_condition_effect(condition: &str, ranged: bool) -> Option<&'static str> {
+    match condition {
+        "Blinded" | "Paralyzed" | "Petrified" | "Restrained" | "Stunned" | "Unconscious" => Some("Advantage"),
+        "Prone" if ranged => Some("Disadvantage"),
+        "Prone" => Some("Advantage"),
+        "Invisible" => Some("Disadvantage"),
+        _ => None,
+    }
+}
+
+/// Works out the roll mode for one combatant: its card override (or the global dropdown)
+/// combined with its conditions. Advantage and disadvantage cancel out to a natural roll.
+/// Returns the mode and the reasons behind it for display.
+fn effective_roll_mode(
+    combatant: &Combatant,
+    simulation_state: &SimulationState,
+    kind: RollKind
+) -> (String, Vec<String>) {
+    let (advantage, disadvantage) = roll_mode_reasons(combatant, simulation_state, kind);
+    combine_roll_mode(advantage, disadvantage)
+}
+
+/// The reasons a combatant has advantage and disadvantage on a kind of roll.
+fn roll_mode_reasons(
+    combatant: &Combatant,
+    simulation_state: &SimulationState,
+    kind: RollKind
+) -> (Vec<String>, Vec<String>) {
+    let base_mode = combatant.roll_mode
+        .clone()
+        .unwrap_or_else(|| get_dropdown_text(&simulation_state.roll_mode_dropdown));
+
+    let mut advantage = Vec::new();
+    let mut disadvantage = Vec::new();
+    match base_mode.as_str() {
+        "Advantage" => advantage.push(if combatant.roll_mode.is_some() { "Card" } else { "Global" }.to_string()),
+        "Disadvantage" => disadvantage.push(if combatant.roll_mode.is_some() { "Card" } else { "Global" }.to_string()),
+        _ => {}
+    }
+
+    for condition in &combatant.conditions {
+        match condition_roll_effect(condition, kind) {
+            Some("Advantage") => advantage.push(condition.clone()),
+            Some(_) => disadvantage.push(condition.clone()),
+            None => {}
+        }
+    }
+    (advantage, disadvantage)
+}
+
+/// The roll mode of an attack: the attacker's own mode combined with the conditions of its target.
+fn attack_roll_mode(
+    combatant: &Combatant,
+    simulation_state: &SimulationState,
+    target: Option<&Combatant>,
+    ranged: bool
+) -> String {
+    let (mut advantage, mut disadvantage) = roll_mode_reasons(combatant, simulation_state, RollKind::Attack);
+    for condition in target.map(|t| t.conditions.as_slice()).unwrap_or_default() {
+        match target_condition_effect(condition, ranged) {
+            Some("Advantage") => advantage.push(format!("Target {}", condition)),
+            Some(_) => disadvantage.push(format!("Target {}", condition)),
+            None => {}
+        }
+    }
+    combine_roll_mode(advantage, disadvantage).0
+}
+
+/// Turns reasons for advantage and disadvantage into a roll mode and the reasons for display.
+fn combine_roll_mode(advantage: Vec<String>, disadvantage: Vec<String>) -> (String, Vec<String>) {
+    match (advantage.is_empty(), disadvantage.is_empty()) {
+        (false, true) => ("Advantage".to_string(), advantage),
+        (true, false) => ("Disadvantage".to_string(), disadvantage),
+        (false, false) => {
+            let mut reasons = advantage;
+            reasons.extend(disadvantage);
+            ("Natural".to_string(), reasons)
+        }
+        (true, true) => ("Natural".to_string(), Vec::new()),
+    }
+}
+
 fn calculate_damage(num_dice: i32, dice_used: &str, ability_mod: i32) -> (i32, String) {
     let mut rng = rand::rngs::ThreadRng::default();
     let dice_value_str = dice_used.trim_start_matches('d');
@@ -665,6 +1712,29 @@ fn create_card_header(
     );
     name_label.set_hexpand(true);
 
+    let select_check = UiFactory::create_check_button(combatant.selected);
+    select_check.set_tooltip_text(Some("Include in group checks"));
+    let target_check = gtk::CheckButton::with_label("Target");
+    target_check.set_active(combatant.target);
+    target_check.set_tooltip_text(Some("Make other combatants' attacks against this one"));
+
+    for (check, is_target) in [(&select_check, false), (&target_check, true)] {
+        let combatant_instance_name = combatant.instance_name.clone();
+        let simulation_state_clone = simulation_state.clone();
+        check.connect_toggled(move |check| {
+            if let Ok(mut combatants) = simulation_state_clone.combatants.try_borrow_mut()
+                && let Some(c) = combatants.iter_mut().find(|c| c.instance_name == combatant_instance_name)
+            {
+                if is_target {
+                    c.target = check.is_active();
+                } else {
+                    c.selected = check.is_active();
+                }
+            }
+            mark_simulation_changed(&simulation_state_clone);
+        });
+    }
+
     let kill_button = UiFactory::create_button("Kill", Align::End, Some("destructive-action"));
 
     let card_frame_clone = card_frame.clone();
@@ -672,98 +1742,494 @@ fn create_card_header(
     let killed_monsters_clone = Rc::clone(&simulation_state.killed_monsters);
     let combatant_instance_name = combatant.instance_name.clone();
     let flow_box_clone = simulation_state.flow_box.clone();
+    let simulation_state_clone = simulation_state.clone();
 
     kill_button.connect_clicked(move |_| {
-        if let Ok(mut combatants) = combatants_clone.try_borrow_mut() {
-            if
-                let Some(pos) = combatants
-                    .iter()
-                    .position(|c| c.instance_name == combatant_instance_name)
-            {
-                let killed = combatants.remove(pos);
-                killed_monsters_clone.borrow_mut().push(killed);
+        // Whose turn it becomes when the combatant dies on its own turn, and whether that wraps into a new round
+        let mut next_turn = None;
+        if let Ok(mut combatants) = combatants_clone.try_borrow_mut()
+            && let Some(pos) = combatants
+                .iter()
+                .position(|c| c.instance_name == combatant_instance_name)
+        {
+            let killed = combatants.remove(pos);
+            killed_monsters_clone.borrow_mut().push(killed);
+
+            let was_active = simulation_state_clone.active_turn.borrow().as_deref() == Some(combatant_instance_name.as_str());
+            if was_active {
+                next_turn = match combatants.get(pos) {
+                    Some(next) => Some((Some(next.instance_name.clone()), false)),
+                    None => Some((combatants.first().map(|c| c.instance_name.clone()), true)),
+                };
             }
         }
         flow_box_clone.remove(&card_frame_clone);
+        if let Some((next, new_round)) = next_turn {
+            if new_round && next.is_some() {
+                let round = simulation_state_clone.round_spin_button.value();
+                simulation_state_clone.round_spin_button.set_value(round + 1.0);
+            }
+            match next {
+                Some(name) => start_turn(&simulation_state_clone, name),
+                None => *simulation_state_clone.active_turn.borrow_mut() = None,
+            }
+        }
+        mark_simulation_changed(&simulation_state_clone);
     });
 
+    header_box.append(&select_check);
     header_box.append(&name_label);
+    header_box.append(&target_check);
     header_box.append(&kill_button);
     header_box
 }
 
-/// Creates the HP, AC, and Speed control panel
-fn create_stats_row(
-    combatant: &Combatant,
-    card_frame: &Frame,
-    simulation_state: &SimulationState
-) -> Box {
+/// Creates the HP, AC, and Speed control panel. HP only changes through the Damage and Heal
+/// buttons, so each hit rolls a single concentration save.
+fn create_stats_row(combatant: &Combatant, card_frame: &Frame, simulation_state: &SimulationState) -> Box {
     let stats_box = UiFactory::create_box(Orientation::Horizontal, 12, (0, 0, 0, 0));
 
-    let hp_label = Label::new(Some("HP:"));
-    let hp_spin_button = UiFactory::create_spin_button(
-        0.0,
-        combatant.max_hp.into(),
-        1.0,
-        combatant.current_hp as f64
-    );
-    let max_hp_label = Label::new(Some(&format!("Max HP: {}", combatant.max_hp)));
+    let hp_label = Label::new(Some(&format!("HP: {} / {}", combatant.current_hp, combatant.max_hp)));
+    let amount_spin_button = UiFactory::create_spin_button(0.0, 999.0, 1.0, 0.0);
+    let damage_button = UiFactory::create_button("Damage", Align::Center, None);
+    let heal_button = UiFactory::create_button("Heal", Align::Center, None);
 
     if combatant.current_hp <= combatant.max_hp / 2 {
         card_frame.add_css_class("bloodied");
     }
 
-    let combatants_clone = Rc::clone(&simulation_state.combatants);
-    let combatant_instance_name_clone = combatant.instance_name.clone();
-    let card_frame_clone = card_frame.clone();
-    let max_hp = combatant.max_hp;
+    for (button, healing) in [(&damage_button, false), (&heal_button, true)] {
+        let amount_spin_button = amount_spin_button.clone();
+        let instance_name = combatant.instance_name.clone();
+        let simulation_state_clone = simulation_state.clone();
+        button.connect_clicked(move |_| {
+            let amount = amount_spin_button.value() as i32;
+            if amount <= 0 {
+                return;
+            }
+            if let Ok(buffer) = simulation_state_clone.console_buffer.try_borrow_mut() {
+                let mut iter = buffer.end_iter();
+                buffer.insert(
+                    &mut iter,
+                    &format!(
+                        "{}: {} {} {} HP.\n",
+                        chrono::Local::now().format("%H:%M:%S"),
+                        instance_name,
+                        if healing { "regained" } else { "lost" },
+                        amount
+                    )
+                );
+                if healing {
+                    apply_healing(&buffer, &simulation_state_clone, &instance_name, amount);
+                } else {
+                    apply_damage(&buffer, &simulation_state_clone, &instance_name, amount);
+                }
+                limit_console_buffer(&buffer);
+            }
+            scroll_console_to_bottom(&simulation_state_clone.console_text_view);
+        });
+    }
 
-    hp_spin_button.connect_value_changed(move |btn| {
-        let current_hp = btn.value() as i32;
+    let ac_label = Label::new(Some(&format!("AC: {}", combatant.monster_template.ac)));
+    let speed_label = Label::new(Some(&format!("Speed: {}", combatant.monster_template.speeds.describe())));
 
-        if let Ok(mut combatants) = combatants_clone.try_borrow_mut() {
-            if
-                let Some(c) = combatants
-                    .iter_mut()
-                    .find(|c| c.instance_name == combatant_instance_name_clone)
+    stats_box.append(&hp_label);
+    stats_box.append(&amount_spin_button);
+    stats_box.append(&damage_button);
+    stats_box.append(&heal_button);
+    stats_box.append(&ac_label);
+    stats_box.append(&speed_label);
+    stats_box
+}
+
+/// Creates the bonus action and reaction indicators, ticked when used and cleared when the turn starts.
+fn create_action_economy_row(
+    combatant: &Combatant,
+    simulation_state: &SimulationState
+) -> (Box, gtk::CheckButton, gtk::CheckButton) {
+    let economy_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    let label = Label::new(Some("Used this turn:"));
+    let bonus_check = gtk::CheckButton::with_label("Bonus Action");
+    bonus_check.set_active(combatant.bonus_action_used);
+    let reaction_check = gtk::CheckButton::with_label("Reaction");
+    reaction_check.set_active(combatant.reaction_used);
+
+    for (check, is_reaction) in [(&bonus_check, false), (&reaction_check, true)] {
+        let instance_name = combatant.instance_name.clone();
+        let simulation_state_clone = simulation_state.clone();
+        check.connect_toggled(move |check| {
+            if let Some(c) = simulation_state_clone.combatants
+                .borrow_mut()
+                .iter_mut()
+                .find(|c| c.instance_name == instance_name)
             {
-                c.current_hp = current_hp;
+                if is_reaction {
+                    c.reaction_used = check.is_active();
+                } else {
+                    c.bonus_action_used = check.is_active();
+                }
             }
+            mark_simulation_changed(&simulation_state_clone);
+        });
+    }
+
+    economy_box.append(&label);
+    economy_box.append(&bonus_check);
+    economy_box.append(&reaction_check);
+    (economy_box, bonus_check, reaction_check)
+}
+
+/// Creates the concentration entry naming the spell being concentrated on, with a button to end it.
+fn create_concentration_row(combatant: &Combatant, simulation_state: &SimulationState) -> Box {
+    let concentration_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    let label = Label::new(Some("Concentration:"));
+    let entry = UiFactory::create_entry(
+        combatant.concentration.as_deref(),
+        Some("Spell name..."),
+        15
+    );
+    let end_button = UiFactory::create_button("End", Align::Center, None);
+
+    let instance_name = combatant.instance_name.clone();
+    let simulation_state_clone = simulation_state.clone();
+    entry.connect_changed(move |entry| {
+        let spell = Some(entry.text().trim().to_string()).filter(|s| !s.is_empty());
+        let mut changed = false;
+        if let Ok(mut combatants) = simulation_state_clone.combatants.try_borrow_mut()
+            && let Some(c) = combatants.iter_mut().find(|c| c.instance_name == instance_name)
+            && c.concentration != spell
+        {
+            c.concentration = spell;
+            changed = true;
+        }
+        if changed {
+            mark_simulation_changed(&simulation_state_clone);
         }
+    });
+
+    let entry_clone = entry.clone();
+    end_button.connect_clicked(move |_| {
+        entry_clone.set_text("");
+    });
+
+    concentration_box.append(&label);
+    concentration_box.append(&entry);
+    concentration_box.append(&end_button);
+    concentration_box
+}
+
+/// Rolls a Con save against DC max(10, half the damage) for a concentrating combatant and logs it.
+/// Dropping to 0 HP ends concentration without a save. Returns whether concentration held,
+/// or `None` if the combatant wasn't concentrating.
+fn roll_concentration_save(
+    buffer: &gtk::TextBuffer,
+    instance_name: &str,
+    simulation_state: &SimulationState,
+    damage: i32,
+    current_hp: i32
+) -> Option<bool> {
+    let combatant = simulation_state.combatants
+        .borrow()
+        .iter()
+        .find(|c| c.instance_name == instance_name)
+        .cloned()?;
+    let spell = combatant.concentration.clone()?;
+    let timestamp = chrono::Local::now().format("%H:%M:%S");
+
+    let held = if current_hp <= 0 {
+        let mut iter = buffer.end_iter();
+        buffer.insert(
+            &mut iter,
+            &format!("{}: {} dropped to 0 HP and lost concentration on {}.\n", timestamp, instance_name, spell)
+        );
+        false
+    } else {
+        let monster = &combatant.monster_template;
+        let dc = (damage / 2).max(10);
+        let (mode, _) = effective_roll_mode(&combatant, simulation_state, RollKind::Save(2));
+        let (d20_roll, lost_roll) = perform_d20_roll(&mode);
+        let bonus = monster.ability_mod(2) + monster.pb * (monster.saves[2] as i32);
+        let total = d20_roll + bonus;
+        let held = total >= dc;
+
+        let prefix = format!(
+            "{}: {} took {} damage while concentrating on {}. Con save DC {}: (",
+            timestamp,
+            instance_name,
+            damage,
+            spell,
+            dc
+        );
+        let suffix = format!(
+            ") + {} = {} -> {}\n",
+            bonus,
+            total,
+            if held { "concentration held" } else { "concentration lost!" }
+        );
+        append_roll_to_console(buffer, &prefix, d20_roll, lost_roll, &suffix);
+        held
+    };
+
+    if !held
+        && let Some(c) = simulation_state.combatants
+            .borrow_mut()
+            .iter_mut()
+            .find(|c| c.instance_name == instance_name)
+    {
+        c.concentration = None;
+    }
+    Some(held)
+}
 
-        if current_hp <= max_hp / 2 {
-            card_frame_clone.add_css_class("bloodied");
+/// Applies a target's immunities, resistances and vulnerabilities to damage of one type.
+/// Returns the damage it takes and why it changed, if it did.
+fn adjust_for_target(damage: i32, damage_type: &str, material: WeaponMaterial, target: &Monster) -> (i32, Option<String>) {
+    if damage <= 0 || damage_type.is_empty() {
+        return (damage, None);
+    }
+    let covers = |list: &[DamageModifier]| {
+        list.iter().any(|m| m.damage_type.eq_ignore_ascii_case(damage_type) && m.qualifier.applies_to(material))
+    };
+    let damage_type = damage_type.to_lowercase();
+    if covers(&target.immunities) {
+        return (0, Some(format!("immune to {}", damage_type)));
+    }
+
+    // Resistance and vulnerability to the same damage both apply, resistance first
+    let mut adjusted = damage;
+    let mut reasons = Vec::new();
+    if covers(&target.resistances) {
+        adjusted /= 2;
+        reasons.push(format!("resistant to {}", damage_type));
+    }
+    if covers(&target.vulnerabilities) {
+        adjusted *= 2;
+        reasons.push(format!("vulnerable to {}", damage_type));
+    }
+    (adjusted, Some(reasons.join(" and ")).filter(|r| !r.is_empty()))
+}
+
+/// Runs `adjust_for_target` for damage about to be dealt to a combatant and logs the reason.
+fn adjust_damage_for_target(
+    buffer: &gtk::TextBuffer,
+    target: &Combatant,
+    damage: i32,
+    damage_type: &str,
+    material: WeaponMaterial
+) -> i32 {
+    let (adjusted, reason) = adjust_for_target(damage, damage_type, material, &target.monster_template);
+    if let Some(reason) = reason {
+        let mut iter = buffer.end_iter();
+        buffer.insert(
+            &mut iter,
+            &format!("    {} is {}: {} -> {} damage.\n", target.instance_name, reason, damage, adjusted)
+        );
+    }
+    adjusted
+}
+
+/// Lowers a combatant's HP by the damage taken, logs what is left and rolls its concentration save.
+/// Every source of damage goes through here so each hit rolls the save once.
+fn apply_damage(buffer: &gtk::TextBuffer, simulation_state: &SimulationState, instance_name: &str, damage: i32) {
+    if damage <= 0 {
+        return;
+    }
+    let hp = simulation_state.combatants
+        .borrow_mut()
+        .iter_mut()
+        .find(|c| c.instance_name == instance_name)
+        .map(|c| {
+            c.current_hp = (c.current_hp - damage).max(0);
+            (c.current_hp, c.max_hp)
+        });
+    let Some((current_hp, max_hp)) = hp else {
+        return;
+    };
+
+    let mut iter = buffer.end_iter();
+    buffer.insert(&mut iter, &format!("    {} is at {}/{} HP.\n", instance_name, current_hp, max_hp));
+    roll_concentration_save(buffer, instance_name, simulation_state, damage, current_hp);
+
+    refresh_card_later(simulation_state, instance_name);
+    mark_simulation_changed(simulation_state);
+}
+
+/// Raises a combatant's HP by the amount healed, up to its maximum, and logs what it is at.
+fn apply_healing(buffer: &gtk::TextBuffer, simulation_state: &SimulationState, instance_name: &str, amount: i32) {
+    let hp = simulation_state.combatants
+        .borrow_mut()
+        .iter_mut()
+        .find(|c| c.instance_name == instance_name)
+        .map(|c| {
+            c.current_hp = (c.current_hp + amount).min(c.max_hp);
+            (c.current_hp, c.max_hp)
+        });
+    let Some((current_hp, max_hp)) = hp else {
+        return;
+    };
+
+    let mut iter = buffer.end_iter();
+    buffer.insert(&mut iter, &format!("    {} is at {}/{} HP.\n", instance_name, current_hp, max_hp));
+
+    refresh_card_later(simulation_state, instance_name);
+    mark_simulation_changed(simulation_state);
+}
+
+/// Creates the per-card roll mode override and a label showing the mode attacks will use.
+fn create_roll_mode_row(combatant: &Combatant, simulation_state: &SimulationState) -> Box {
+    let mode_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+
+    let mode_label = Label::new(Some("Roll Mode:"));
+    let selected = combatant.roll_mode
+        .as_ref()
+        .and_then(|m| CARD_ROLL_MODES.iter().position(|c| c == m))
+        .unwrap_or(0) as u32;
+    let mode_dropdown = UiFactory::create_dropdown(&CARD_ROLL_MODES, Some(selected), None);
+
+    let (attack_mode, reasons) = effective_roll_mode(combatant, simulation_state, RollKind::Attack);
+    let active_text = if reasons.is_empty() {
+        format!("Attacks: <b>{}</b>", attack_mode)
+    } else {
+        format!("Attacks: <b>{}</b> ({})", attack_mode, reasons.join(", "))
+    };
+    let active_label = UiFactory::create_label(&active_text, Align::Start, true, &[]);
+    active_label.set_wrap(true);
+
+    let instance_name = combatant.instance_name.clone();
+    let simulation_state_clone = simulation_state.clone();
+    mode_dropdown.connect_selected_notify(move |dropdown| {
+        let mode = UiFactory::get_dropdown_text(dropdown);
+        if let Some(c) = simulation_state_clone.combatants
+            .borrow_mut()
+            .iter_mut()
+            .find(|c| c.instance_name == instance_name)
+        {
+            c.roll_mode = if mode == "Global" { None } else { Some(mode) };
+        }
+        refresh_card_later(&simulation_state_clone, &instance_name);
+        mark_simulation_changed(&simulation_state_clone);
+    });
+
+    mode_box.append(&mode_label);
+    mode_box.append(&mode_dropdown);
+    mode_box.append(&active_label);
+    mode_box
+}
+
+/// Creates the condition chips plus a dropdown (with free text for "other") to add more.
+fn create_conditions_row(combatant: &Combatant, simulation_state: &SimulationState) -> Box {
+    let container = UiFactory::create_box(Orientation::Vertical, 4, (0, 0, 0, 0));
+    let label = UiFactory::create_label("<b>Conditions</b>", Align::Start, true, &[]);
+    container.append(&label);
+
+    if !combatant.conditions.is_empty() {
+        let chips_box = FlowBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .max_children_per_line(4)
+            .build();
+        for condition in &combatant.conditions {
+            let chip = UiFactory::create_box(Orientation::Horizontal, 3, (0, 0, 0, 0));
+            chip.append(&Label::new(Some(condition)));
+            let remove_button = UiFactory::create_button("x", Align::Center, None);
+            chip.append(&remove_button);
+
+            let instance_name = combatant.instance_name.clone();
+            let condition_clone = condition.clone();
+            let simulation_state_clone = simulation_state.clone();
+            remove_button.connect_clicked(move |_| {
+                if let Some(c) = simulation_state_clone.combatants
+                    .borrow_mut()
+                    .iter_mut()
+                    .find(|c| c.instance_name == instance_name)
+                {
+                    c.conditions.retain(|existing| *existing != condition_clone);
+                }
+                refresh_card_later(&simulation_state_clone, &instance_name);
+                mark_simulation_changed(&simulation_state_clone);
+            });
+            chips_box.insert(&chip, -1);
+        }
+        container.append(&chips_box);
+    }
+
+    let add_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    let condition_dropdown = UiFactory::create_dropdown(&monster_manager::CONDITIONS, None, None);
+    let other_entry = UiFactory::create_entry(None, Some("Other..."), 10);
+    let add_button = UiFactory::create_button("Add", Align::Center, None);
+    add_box.append(&condition_dropdown);
+    add_box.append(&other_entry);
+    add_box.append(&add_button);
+    container.append(&add_box);
+
+    let instance_name = combatant.instance_name.clone();
+    let simulation_state_clone = simulation_state.clone();
+    add_button.connect_clicked(move |_| {
+        let other = other_entry.text().trim().to_string();
+        let condition = if other.is_empty() {
+            UiFactory::get_dropdown_text(&condition_dropdown)
         } else {
-            card_frame_clone.remove_css_class("bloodied");
+            other
+        };
+        if condition.is_empty() {
+            return;
         }
+        if let Some(c) = simulation_state_clone.combatants
+            .borrow_mut()
+            .iter_mut()
+            .find(|c| c.instance_name == instance_name)
+        {
+            if c.conditions.contains(&condition) {
+                return;
+            }
+            c.conditions.push(condition);
+        }
+        refresh_card_later(&simulation_state_clone, &instance_name);
+        mark_simulation_changed(&simulation_state_clone);
     });
 
-    let ac_label = Label::new(Some(&format!("AC: {}", combatant.monster_template.ac)));
-    let speed_label = Label::new(Some(&format!("Speed: {}", combatant.monster_template.speed)));
+    container
+}
 
-    stats_box.append(&hp_label);
-    stats_box.append(&hp_spin_button);
-    stats_box.append(&max_hp_label);
-    stats_box.append(&ac_label);
-    stats_box.append(&speed_label);
-    stats_box
+/// Creates the size/type, senses and languages summary
+fn create_details_label(combatant: &Combatant) -> Label {
+    let monster = &combatant.monster_template;
+    let mut lines = Vec::new();
+    let type_text = monster.describe_type();
+    if !type_text.is_empty() {
+        lines.push(format!("<i>{}</i>", gtk::glib::markup_escape_text(&type_text)));
+    }
+    lines.push(format!("<b>Senses:</b> {}", gtk::glib::markup_escape_text(&monster.describe_senses())));
+    if !monster.languages.is_empty() {
+        lines.push(format!("<b>Languages:</b> {}", gtk::glib::markup_escape_text(&monster.languages.join(", "))));
+    }
+    UiFactory::create_label(&lines.join("\n"), Align::Start, true, &[])
 }
 
-/// Creates Damage Vulnerability indicator if present
+/// Creates the vulnerability, resistance and immunity indicators if present
 fn create_vulnerabilities_label(combatant: &Combatant) -> Option<Label> {
-    if combatant.monster_template.vulnerabilities.is_empty() {
-        return None;
+    let monster = &combatant.monster_template;
+    let mut lines = Vec::new();
+    for (heading, list) in [
+        ("Damage Vulnerabilities", &monster.vulnerabilities),
+        ("Damage Resistances", &monster.resistances),
+        ("Damage Immunities", &monster.immunities),
+    ] {
+        if !list.is_empty() {
+            let entries: Vec<String> = list.iter().map(|m| m.describe()).collect();
+            lines.push(format!("<b>{}:</b> {}", heading, gtk::glib::markup_escape_text(&entries.join(", "))));
+        }
     }
-
-    let mut label_text = String::from("<b>Damage Vulnerabilities:</b>");
-    for i in &combatant.monster_template.vulnerabilities {
-        label_text.push(' ');
-        label_text.push_str(i);
-        label_text.push(',');
+    if !monster.condition_immunities.is_empty() {
+        lines.push(format!(
+            "<b>Condition Immunities:</b> {}",
+            gtk::glib::markup_escape_text(&monster.condition_immunities.join(", "))
+        ));
+    }
+    if lines.is_empty() {
+        return None;
     }
-    label_text.pop(); // Remove trailing comma
 
-    let vuln_label = UiFactory::create_label(&label_text, Align::Start, true, &[]);
+    let vuln_label = UiFactory::create_label(&lines.join("\n"), Align::Start, true, &[]);
     vuln_label.set_margin_top(6);
     Some(vuln_label)
 }
@@ -808,9 +2274,16 @@ fn create_saves_grid(combatant: &Combatant, simulation_state: &SimulationState)
     let saves_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
     let stats = ["Str", "Dex", "Con", "Int", "Wis", "Cha"];
 
-    for stat_name in stats.iter() {
+    for (stat_index, stat_name) in stats.iter().enumerate() {
         let stat_vbox = UiFactory::create_box(Orientation::Vertical, 3, (0, 0, 0, 0));
-        let save_button = UiFactory::create_button(*stat_name, Align::Center, None);
+        let (save_mode, _) = effective_roll_mode(combatant, simulation_state, RollKind::Save(stat_index));
+        let save_button_text = match save_mode.as_str() {
+            _ if save_auto_fail_condition(combatant, stat_index).is_some() => format!("{} (Fail)", stat_name),
+            "Advantage" => format!("{} (Adv)", stat_name),
+            "Disadvantage" => format!("{} (Dis)", stat_name),
+            _ => stat_name.to_string(),
+        };
+        let save_button = UiFactory::create_button(&save_button_text, Align::Center, None);
 
         let combatant_clone = combatant.clone();
         let console_buffer_clone = Rc::clone(&simulation_state.console_buffer);
@@ -819,12 +2292,12 @@ fn create_saves_grid(combatant: &Combatant, simulation_state: &SimulationState)
         let mon = combatant_clone.monster_template.clone();
 
         let modifier = match stat_name_clone.to_lowercase().as_str() {
-            "str" => mon.mods[0],
-            "dex" => mon.mods[1],
-            "con" => mon.mods[2],
-            "int" => mon.mods[3],
-            "wis" => mon.mods[4],
-            "cha" => mon.mods[5],
+            "str" => mon.ability_mod(0),
+            "dex" => mon.ability_mod(1),
+            "con" => mon.ability_mod(2),
+            "int" => mon.ability_mod(3),
+            "wis" => mon.ability_mod(4),
+            "cha" => mon.ability_mod(5),
             _ => 0,
         };
 
@@ -837,7 +2310,29 @@ fn create_saves_grid(combatant: &Combatant, simulation_state: &SimulationState)
         let save_simulation_state_clone = simulation_state.clone();
 
         save_button.connect_clicked(move |_| {
-            let mode = get_dropdown_text(&save_simulation_state_clone.roll_mode_dropdown);
+            if let Some(condition) = save_auto_fail_condition(&combatant_clone, stat_index) {
+                if let Ok(buffer) = console_buffer_clone.try_borrow_mut() {
+                    let mut iter = buffer.end_iter();
+                    buffer.insert(
+                        &mut iter,
+                        &format!(
+                            "{}: {} automatically fails the {} Save ({})\n",
+                            chrono::Local::now().format("%H:%M:%S"),
+                            combatant_clone.instance_name,
+                            stat_name_clone,
+                            condition
+                        )
+                    );
+                    limit_console_buffer(&buffer);
+                }
+                scroll_console_to_bottom(&console_text_view_clone);
+                return;
+            }
+            let (mode, _) = effective_roll_mode(
+                &combatant_clone,
+                &save_simulation_state_clone,
+                RollKind::Save(stat_index)
+            );
             let (d20_roll, lost_roll) = perform_d20_roll(&mode);
 
             let save_bonus = match stat_name_clone.to_lowercase().as_str() {
@@ -877,12 +2372,145 @@ fn create_saves_grid(combatant: &Combatant, simulation_state: &SimulationState)
         saves_box.append(&stat_vbox);
     }
 
-    container.append(&saves_box);
+    container.append(&saves_box);
+    container
+}
+
+/// Creates the ability and skill check panel.
+fn create_checks_panel(combatant: &Combatant, simulation_state: &SimulationState) -> Box {
+    let container = UiFactory::create_box(Orientation::Vertical, 4, (0, 0, 0, 0));
+    let monster = &combatant.monster_template;
+
+    let checks_vbox = UiFactory::create_box(Orientation::Vertical, 6, (0, 0, 0, 0));
+
+    let abilities_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    for (ability_index, ability_name) in ["Str", "Dex", "Con", "Int", "Wis", "Cha"].iter().enumerate() {
+        let bonus = monster.ability_mod(ability_index);
+        let check_button = UiFactory::create_button(&format!("{} {:+}", ability_name, bonus), Align::Center, None);
+
+        let instance_name = combatant.instance_name.clone();
+        let simulation_state_clone = simulation_state.clone();
+        let check_name = ability_name.to_string();
+        check_button.connect_clicked(move |_| {
+            roll_check(&instance_name, &simulation_state_clone, &check_name, bonus);
+        });
+        abilities_box.append(&check_button);
+    }
+    checks_vbox.append(&abilities_box);
+
+    // Proficient skills first, since those are the ones usually asked for
+    let mut skills: Vec<(&str, usize)> = monster_manager::SKILLS.to_vec();
+    skills.sort_by_key(|(skill, _)| !monster.skills.iter().any(|s| s.skill == *skill));
+    let skill_options: Vec<String> = skills
+        .iter()
+        .map(|(skill, _)| format!("{} {:+}", skill, monster.skill_bonus(skill)))
+        .collect();
+    let skill_option_refs: Vec<&str> = skill_options.iter().map(|s| s.as_str()).collect();
+
+    let skill_row = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    let skill_dropdown = UiFactory::create_dropdown(&skill_option_refs, Some(0), None);
+    let skill_button = UiFactory::create_button("Roll Skill", Align::Center, None);
+    skill_row.append(&skill_dropdown);
+    skill_row.append(&skill_button);
+    checks_vbox.append(&skill_row);
+
+    let instance_name = combatant.instance_name.clone();
+    let simulation_state_clone = simulation_state.clone();
+    let monster_clone = monster.clone();
+    skill_button.connect_clicked(move |_| {
+        if let Some(&(skill, _)) = skills.get(skill_dropdown.selected() as usize) {
+            let bonus = monster_clone.skill_bonus(skill);
+            roll_check(&instance_name, &simulation_state_clone, skill, bonus);
+        }
+    });
+
+    let expander = gtk::Expander::builder().label("Checks").child(&checks_vbox).build();
+    container.append(&expander);
     container
 }
 
-/// Creates the Interactive Attacks List for the monster
-fn create_attacks_list(combatant: &Combatant, simulation_state: &SimulationState) -> Option<Box> {
+/// Rolls an ability or skill check for a combatant with its current roll mode and logs it.
+/// Returns the total, or `None` if the combatant is gone.
+fn roll_check(
+    instance_name: &str,
+    simulation_state: &SimulationState,
+    check_name: &str,
+    bonus: i32
+) -> Option<i32> {
+    let combatant = simulation_state.combatants
+        .borrow()
+        .iter()
+        .find(|c| c.instance_name == instance_name)
+        .cloned()?;
+    let (mode, _) = effective_roll_mode(&combatant, simulation_state, RollKind::Check);
+    let (d20_roll, lost_roll) = perform_d20_roll(&mode);
+    let total = d20_roll + bonus;
+
+    if let Ok(buffer) = simulation_state.console_buffer.try_borrow_mut() {
+        let prefix = format!(
+            "{}: {} rolled a {} Check: (",
+            chrono::Local::now().format("%H:%M:%S"),
+            combatant.instance_name,
+            check_name
+        );
+        let suffix = format!(") + {} = {}\n", bonus, total);
+        append_roll_to_console(&buffer, &prefix, d20_roll, lost_roll, &suffix);
+        limit_console_buffer(&buffer);
+    }
+    scroll_console_to_bottom(&simulation_state.console_text_view);
+    Some(total)
+}
+
+/// Rolls the same skill for every selected combatant and logs the lowest and highest result.
+fn roll_group_check(simulation_state: &SimulationState, skill: &str) {
+    let selected: Vec<(String, i32)> = simulation_state.combatants
+        .borrow()
+        .iter()
+        .filter(|c| c.selected)
+        .map(|c| (c.instance_name.clone(), c.monster_template.skill_bonus(skill)))
+        .collect();
+
+    let summary = if selected.is_empty() {
+        "Select combatants with the box on their cards to roll a group check.".to_string()
+    } else {
+        let results: Vec<(String, i32)> = selected
+            .into_iter()
+            .filter_map(|(name, bonus)| {
+                roll_check(&name, simulation_state, skill, bonus).map(|total| (name, total))
+            })
+            .collect();
+        let lowest = results.iter().min_by_key(|(_, total)| *total);
+        let highest = results.iter().max_by_key(|(_, total)| *total);
+        match (lowest, highest) {
+            (Some(lowest), Some(highest)) => format!(
+                "Group {} ({} combatants): lowest {} ({}), highest {} ({})",
+                skill,
+                results.len(),
+                lowest.1,
+                lowest.0,
+                highest.1,
+                highest.0
+            ),
+            _ => return,
+        }
+    };
+
+    if let Ok(buffer) = simulation_state.console_buffer.try_borrow_mut() {
+        let mut iter = buffer.end_iter();
+        buffer.insert(&mut iter, &format!("{}: {}\n", chrono::Local::now().format("%H:%M:%S"), summary));
+        limit_console_buffer(&buffer);
+    }
+    scroll_console_to_bottom(&simulation_state.console_text_view);
+}
+
+/// Creates the Interactive Attacks List for the monster, grouped by action category.
+/// Using a bonus action or reaction ticks its indicator.
+fn create_attacks_list(
+    combatant: &Combatant,
+    simulation_state: &SimulationState,
+    bonus_check: &gtk::CheckButton,
+    reaction_check: &gtk::CheckButton
+) -> Option<Box> {
     if combatant.monster_template.attacks.is_empty() {
         return None;
     }
@@ -892,28 +2520,71 @@ fn create_attacks_list(combatant: &Combatant, simulation_state: &SimulationState
     header_label.set_margin_top(6);
     container.append(&header_label);
 
-    for attack in &combatant.monster_template.attacks {
+    let mut attacks: Vec<&Attack> = combatant.monster_template.attacks.iter().collect();
+    attacks.sort_by_key(|a| monster_manager::ActionCategory::ALL.iter().position(|c| *c == a.category));
+    let only_actions = attacks.iter().all(|a| a.category == monster_manager::ActionCategory::Action);
+    let mut current_category = None;
+
+    for attack in attacks {
+        if !only_actions && current_category != Some(attack.category) {
+            current_category = Some(attack.category);
+            let category_label = UiFactory::create_label(
+                &format!("<i>{}</i>", attack.category.heading()),
+                Align::Start,
+                true,
+                &[]
+            );
+            container.append(&category_label);
+        }
+
         let attack_hbox = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
         let save_dc = 8 + get_ability_mod(combatant, attack) + combatant.monster_template.pb;
 
+        let damage_type = if attack.damage_type.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", attack.damage_type.to_lowercase())
+        };
         let attack_details = if !attack.saving_throw {
             format!(
-                "• {} ({}{}, {}/turn)",
+                "• {} ({}{}{}, {}/turn)",
                 attack.attack_name,
                 attack.num_dice,
                 attack.dice_used,
+                damage_type,
                 attack.num_attacks
             )
         } else {
             format!(
-                "• {} ({}{}, DC {})",
+                "• {} ({}{}{}, DC {} {} save, {} on a success)",
                 attack.attack_name,
                 attack.num_dice,
                 attack.dice_used,
-                save_dc
+                damage_type,
+                save_dc,
+                attack.target_save_ability().to_uppercase(),
+                attack.on_save.label().to_lowercase()
             )
         };
 
+        let range_text = attack.describe_range();
+        let attack_details = if range_text.is_empty() {
+            attack_details
+        } else {
+            format!("{}\n    {}", attack_details, range_text)
+        };
+        let attack_details = match &attack.area {
+            Some(area) if attack.saving_throw => {
+                format!("{} (~{} targets)", attack_details, area.estimated_targets())
+            }
+            _ => attack_details,
+        };
+
+        let attack_details = match &attack.rider {
+            Some(rider) => format!("{}\n    On hit: {}", attack_details, rider.describe()),
+            None => attack_details,
+        };
+
         let attack_label = UiFactory::create_label(&attack_details, Align::Start, false, &[]);
         attack_label.set_hexpand(true);
         attack_hbox.append(&attack_label);
@@ -925,14 +2596,36 @@ fn create_attacks_list(combatant: &Combatant, simulation_state: &SimulationState
         let console_buffer_clone = Rc::clone(&simulation_state.console_buffer);
         let console_text_view_clone = simulation_state.console_text_view.clone();
         let attack_simulation_state_clone = simulation_state.clone();
+        let economy_check = match attack.category {
+            monster_manager::ActionCategory::BonusAction => Some(bonus_check.clone()),
+            monster_manager::ActionCategory::Reaction => Some(reaction_check.clone()),
+            _ => None,
+        };
 
         use_button.connect_clicked(move |_| {
             let creature_name = combatant_clone.instance_name.clone();
             let attack_name = attack_clone.attack_name.clone();
             let attacks_per_turn = attack_clone.num_attacks;
 
+            let mut already_used = false;
+            if let Some(check) = &economy_check {
+                already_used = check.is_active();
+                check.set_active(true);
+            }
+
             if let Ok(buffer) = console_buffer_clone.try_borrow_mut() {
                 let mut iter = buffer.end_iter();
+                if already_used {
+                    buffer.insert(
+                        &mut iter,
+                        &format!(
+                            "{}: {} already used its {} this turn.\n",
+                            chrono::Local::now().format("%H:%M:%S"),
+                            creature_name,
+                            attack_clone.category.label().to_lowercase()
+                        )
+                    );
+                }
 
                 if !attack_clone.saving_throw {
                     buffer.insert(
@@ -946,55 +2639,45 @@ fn create_attacks_list(combatant: &Combatant, simulation_state: &SimulationState
                         )
                     );
 
+                    // Rolls are spread over the ticked targets in turn, skipping those that go down
+                    let had_targets = !attack_targets(&attack_simulation_state_clone, &creature_name).is_empty();
                     for i in 0..attacks_per_turn {
-                        let mode = get_dropdown_text(
-                            &attack_simulation_state_clone.roll_mode_dropdown
-                        );
-                        let (d20_roll, lost_roll) = perform_d20_roll(&mode);
-
-                        let ability_mod = get_ability_mod(&combatant_clone, &attack_clone);
-                        let total_mod = ability_mod + combatant_clone.monster_template.pb;
-                        let to_hit = d20_roll + total_mod;
-                        let crit_message = if d20_roll == 20 { " -> CRITICAL HIT!" } else { "" };
-
-                        let (_, damage_output) = calculate_damage(
-                            if d20_roll == 20 {
-                                attack_clone.num_dice * 2
-                            } else {
-                                attack_clone.num_dice
-                            },
-                            &attack_clone.dice_used,
-                            ability_mod
-                        );
-
-                        let prefix = format!("  Attack {}: To hit: (", i + 1);
-                        let suffix = format!(
-                            ") + {} (Total Mod) = {}{}; Damage: {}\n",
-                            total_mod,
-                            to_hit,
-                            crit_message,
-                            damage_output
+                        let label = format!("Attack {}", i + 1);
+                        let target = next_target(&attack_simulation_state_clone, &creature_name, i as usize);
+                        if had_targets && target.is_none() {
+                            log_targets_down(&buffer, &label);
+                            break;
+                        }
+                        roll_attack(
+                            &buffer,
+                            &combatant_clone,
+                            &attack_simulation_state_clone,
+                            &attack_clone,
+                            &label,
+                            target.as_ref()
                         );
-
-                        append_roll_to_console(&buffer, &prefix, d20_roll, lost_roll, &suffix);
                     }
                 } else {
                     buffer.insert(
                         &mut iter,
                         &format!(
-                            "{}: {} started an attack using {}",
+                            "{}: {} started an attack using {}.\n",
                             chrono::Local::now().format("%H:%M:%S"),
                             creature_name,
                             attack_name
                         )
                     );
 
-                    let (_, damage_output) = calculate_damage(
-                        attack_clone.num_dice,
-                        &attack_clone.dice_used,
-                        0
+                    // Every ticked target saves against the same damage roll
+                    let targets = attack_targets(&attack_simulation_state_clone, &creature_name);
+                    roll_save_attack(
+                        &buffer,
+                        &combatant_clone,
+                        &attack_simulation_state_clone,
+                        &attack_clone,
+                        "Result",
+                        &targets
                     );
-                    buffer.insert(&mut iter, &format!("  Damage: {}\n", damage_output));
                 }
 
                 limit_console_buffer(&buffer);
@@ -1007,9 +2690,692 @@ fn create_attacks_list(combatant: &Combatant, simulation_state: &SimulationState
         container.append(&attack_hbox);
     }
 
+    for routine in &combatant.monster_template.multiattacks {
+        let routine_hbox = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+        let routine_label = UiFactory::create_label(
+            &format!("• {} ({})", routine.name, routine.describe_steps()),
+            Align::Start,
+            false,
+            &[]
+        );
+        routine_label.set_hexpand(true);
+        routine_hbox.append(&routine_label);
+
+        let use_button = UiFactory::create_button("Use", Align::Center, None);
+        let combatant_clone = combatant.clone();
+        let routine_clone = routine.clone();
+        let routine_simulation_state_clone = simulation_state.clone();
+        use_button.connect_clicked(move |_| {
+            roll_multiattack(&combatant_clone, &routine_clone, &routine_simulation_state_clone);
+        });
+
+        routine_hbox.append(&use_button);
+        container.append(&routine_hbox);
+    }
+
+    Some(container)
+}
+
+/// What one attack roll came to, for summaries.
+struct AttackResult {
+    // None for save based attacks
+    to_hit: Option<i32>,
+    damage: i32,
+    // whether it beat the target's AC; None without a target
+    landed: Option<bool>,
+}
+
+/// The combatants with Target ticked on their cards, other than the attacker, that attacks
+/// are made against. Targets at 0 HP are left out.
+fn attack_targets(simulation_state: &SimulationState, attacker: &str) -> Vec<Combatant> {
+    simulation_state.combatants
+        .borrow()
+        .iter()
+        .filter(|c| c.target && c.current_hp > 0 && c.instance_name != attacker)
+        .cloned()
+        .collect()
+}
+
+/// The target of the `index`th roll of an attack, spreading the rolls over the targets in turn.
+/// Read again before every roll so damage and conditions from earlier rolls count.
+fn next_target(simulation_state: &SimulationState, attacker: &str, index: usize) -> Option<Combatant> {
+    let targets = attack_targets(simulation_state, attacker);
+    targets.get(index % targets.len().max(1)).cloned()
+}
+
+/// Logs that the rest of an attack is skipped because every target is down.
+fn log_targets_down(buffer: &gtk::TextBuffer, label: &str) {
+    let mut iter = buffer.end_iter();
+    buffer.insert(&mut iter, &format!("  {}: skipped, every target is down.\n", label));
+}
+
+/// Rolls one attack into the console under the given label. Against a target the roll is
+/// compared to its AC, and a hit makes it roll the attack's rider save and lose the damage
+/// from its HP.
+fn roll_attack(
+    buffer: &gtk::TextBuffer,
+    combatant: &Combatant,
+    simulation_state: &SimulationState,
+    attack: &Attack,
+    label: &str,
+    target: Option<&Combatant>
+) -> AttackResult {
+    let ability_mod = get_ability_mod(combatant, attack);
+
+    let ranged = attack.range > 0 && attack.reach == 0;
+    let mode = attack_roll_mode(combatant, simulation_state, target, ranged);
+    let (d20_roll, lost_roll) = perform_d20_roll(&mode);
+
+    let total_mod = ability_mod + combatant.monster_template.pb;
+    let to_hit = d20_roll + total_mod;
+    let crit_message = if d20_roll == 20 { " -> CRITICAL HIT!" } else { "" };
+
+    let (mut damage, damage_output) = calculate_damage(
+        if d20_roll == 20 {
+            attack.num_dice * 2
+        } else {
+            attack.num_dice
+        },
+        &attack.dice_used,
+        ability_mod
+    );
+
+    // A natural 20 always hits and a natural 1 always misses
+    let landed = target.map(|t| d20_roll == 20 || (d20_roll != 1 && to_hit >= t.monster_template.ac));
+    let target_text = match (target, landed) {
+        (Some(t), Some(true)) => format!(" vs {} (AC {}) -> hit", t.instance_name, t.monster_template.ac),
+        (Some(t), _) => format!(" vs {} (AC {}) -> miss", t.instance_name, t.monster_template.ac),
+        _ => String::new(),
+    };
+
+    let prefix = format!("  {}: To hit: (", label);
+    let suffix = format!(
+        ") + {} (Total Mod) = {}{}{}; Damage: {}\n",
+        total_mod,
+        to_hit,
+        crit_message,
+        target_text,
+        damage_output
+    );
+
+    append_roll_to_console(buffer, &prefix, d20_roll, lost_roll, &suffix);
+
+    if landed == Some(true)
+        && let Some(t) = target
+    {
+        damage = adjust_damage_for_target(buffer, t, damage, &attack.damage_type, attack.material);
+    }
+    if let Some(rider) = &attack.rider
+        && landed != Some(false)
+    {
+        damage += resolve_rider(buffer, simulation_state, rider, target, attack.material);
+    }
+    if landed == Some(true)
+        && let Some(t) = target
+    {
+        apply_damage(buffer, simulation_state, &t.instance_name, damage);
+    }
+    AttackResult { to_hit: Some(to_hit), damage, landed }
+}
+
+/// Rolls a saving throw attack's damage once, then each target's save against the attack's DC,
+/// taking the full, half or no damage off each one's HP. Without targets only the DC and damage
+/// are logged. The result's damage is the total dealt, or the full roll without targets.
+fn roll_save_attack(
+    buffer: &gtk::TextBuffer,
+    combatant: &Combatant,
+    simulation_state: &SimulationState,
+    attack: &Attack,
+    label: &str,
+    targets: &[Combatant]
+) -> AttackResult {
+    let ability_mod = get_ability_mod(combatant, attack);
+    let save_dc = 8 + ability_mod + combatant.monster_template.pb;
+    let ability_index = monster_manager::ability_index(attack.target_save_ability()).unwrap_or(1);
+    let damage_mod = if attack.add_mod_to_damage { ability_mod } else { 0 };
+    let (damage, damage_output) = calculate_damage(attack.num_dice, &attack.dice_used, damage_mod);
+    let success_damage = match attack.on_save {
+        monster_manager::SaveSuccess::HalfDamage => damage / 2,
+        monster_manager::SaveSuccess::NoDamage => 0,
+    };
+    let damage_type = if attack.damage_type.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", attack.damage_type.to_lowercase())
+    };
+
+    let mut iter = buffer.end_iter();
+    buffer.insert(
+        &mut iter,
+        &format!(
+            "  {}: DC {} {} save; Damage: {}{} ({} on a success)\n",
+            label,
+            save_dc,
+            monster_manager::ABILITY_KEYS[ability_index].to_uppercase(),
+            damage_output,
+            damage_type,
+            success_damage
+        )
+    );
+
+    if targets.is_empty() {
+        // Without ticked targets, the DMG table gives a rough head count for the area
+        if let Some(area) = &attack.area {
+            let estimate = area.estimated_targets();
+            let mut iter = buffer.end_iter();
+            buffer.insert(
+                &mut iter,
+                &format!(
+                    "    Estimated targets in a {}: {} ({} damage if all fail)\n",
+                    area.describe(),
+                    estimate,
+                    damage * estimate
+                )
+            );
+        }
+        return AttackResult { to_hit: None, damage, landed: None };
+    }
+
+    let mut total_dealt = 0;
+    for target in targets {
+        let saved = roll_target_save(buffer, target, simulation_state, ability_index, save_dc);
+        let dealt = if saved { success_damage } else { damage };
+        let dealt = adjust_damage_for_target(buffer, target, dealt, &attack.damage_type, attack.material);
+        total_dealt += dealt;
+
+        let outcome = match (saved, dealt) {
+            (_, 0) => "no damage".to_string(),
+            (true, _) => format!("{}{} damage (half)", dealt, damage_type),
+            (false, _) => format!("{}{} damage", dealt, damage_type),
+        };
+        let mut iter = buffer.end_iter();
+        buffer.insert(&mut iter, &format!("    {} takes {}.\n", target.instance_name, outcome));
+        apply_damage(buffer, simulation_state, &target.instance_name, dealt);
+    }
+    AttackResult { to_hit: None, damage: total_dealt, landed: None }
+}
+
+/// Rolls a target's saving throw against a DC into the console. Returns whether it succeeded.
+fn roll_target_save(
+    buffer: &gtk::TextBuffer,
+    target: &Combatant,
+    simulation_state: &SimulationState,
+    ability_index: usize,
+    dc: i32
+) -> bool {
+    if let Some(condition) = save_auto_fail_condition(target, ability_index) {
+        let mut iter = buffer.end_iter();
+        buffer.insert(
+            &mut iter,
+            &format!(
+                "    {} {} save DC {}: automatically fails ({})\n",
+                target.instance_name,
+                monster_manager::ABILITY_KEYS[ability_index].to_uppercase(),
+                dc,
+                condition
+            )
+        );
+        return false;
+    }
+    let monster = &target.monster_template;
+    let (mode, _) = effective_roll_mode(target, simulation_state, RollKind::Save(ability_index));
+    let (d20_roll, lost_roll) = perform_d20_roll(&mode);
+    let bonus = monster.ability_mod(ability_index) + monster.pb * (monster.saves[ability_index] as i32);
+    let total = d20_roll + bonus;
+    let saved = total >= dc;
+
+    let prefix = format!(
+        "    {} {} save DC {}: (",
+        target.instance_name,
+        monster_manager::ABILITY_KEYS[ability_index].to_uppercase(),
+        dc
+    );
+    let suffix = format!(") + {} = {} -> {}\n", bonus, total, if saved { "saved" } else { "failed" });
+    append_roll_to_console(buffer, &prefix, d20_roll, lost_roll, &suffix);
+    saved
+}
+
+/// Applies an attack's rider after a hit: the target rolls its save and takes the condition
+/// and extra damage on a failure. Without a target the rider is only logged with its damage.
+/// Returns the extra damage dealt.
+fn resolve_rider(
+    buffer: &gtk::TextBuffer,
+    simulation_state: &SimulationState,
+    rider: &monster_manager::AttackRider,
+    target: Option<&Combatant>,
+    material: WeaponMaterial
+) -> i32 {
+    let damage_type = if rider.damage_type.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", rider.damage_type.to_lowercase())
+    };
+    let extra = monster_manager::parse_dice_expression(&rider.extra_damage)
+        .map(|(count, die, bonus)| calculate_damage(count, &die, bonus));
+
+    let Some(target) = target else {
+        let damage_text = extra
+            .as_ref()
+            .map(|(_, text)| format!("; Extra damage: {}{}", text, damage_type))
+            .unwrap_or_default();
+        let mut iter = buffer.end_iter();
+        buffer.insert(&mut iter, &format!("    Rider: {}{}\n", rider.describe(), damage_text));
+        return extra.map_or(0, |(total, _)| total);
+    };
+
+    let ability_index = monster_manager::ability_index(&rider.save_ability).unwrap_or(2);
+    let saved = roll_target_save(buffer, target, simulation_state, ability_index, rider.dc);
+
+    let damage = match extra {
+        Some((total, _)) if !saved => total,
+        Some((total, _)) if rider.half_on_success => total / 2,
+        _ => 0,
+    };
+    let damage = adjust_damage_for_target(buffer, target, damage, &rider.damage_type, material);
+    let mut effects = Vec::new();
+    if damage > 0 {
+        effects.push(format!("takes {}{} extra damage", damage, damage_type));
+    }
+
+    let immune = target.monster_template.condition_immunities
+        .iter()
+        .any(|c| c.eq_ignore_ascii_case(&rider.condition));
+    if !saved && !rider.condition.is_empty() && immune {
+        effects.push(format!("is immune to being {}", rider.condition.to_lowercase()));
+    } else if !saved && !rider.condition.is_empty() {
+        let newly_applied = simulation_state.combatants
+            .borrow_mut()
+            .iter_mut()
+            .find(|c| c.instance_name == target.instance_name)
+            .is_some_and(|c| {
+                if c.conditions.contains(&rider.condition) {
+                    false
+                } else {
+                    c.conditions.push(rider.condition.clone());
+                    true
+                }
+            });
+        let duration = if rider.duration.is_empty() {
+            String::new()
+        } else {
+            format!(" for {}", rider.duration)
+        };
+        effects.push(format!("is {}{}", rider.condition.to_lowercase(), duration));
+        if newly_applied {
+            refresh_card(simulation_state, &target.instance_name);
+            mark_simulation_changed(simulation_state);
+        }
+    }
+
+    if !effects.is_empty() {
+        let mut iter = buffer.end_iter();
+        buffer.insert(&mut iter, &format!("    {} {}.\n", target.instance_name, effects.join(" and ")));
+    }
+    damage
+}
+
+/// Rolls every attack of a multiattack routine in order, then logs a summary of the
+/// results and the total damage.
+fn roll_multiattack(combatant: &Combatant, routine: &monster_manager::Multiattack, simulation_state: &SimulationState) {
+    if let Ok(buffer) = simulation_state.console_buffer.try_borrow_mut() {
+        let mut iter = buffer.end_iter();
+        buffer.insert(
+            &mut iter,
+            &format!(
+                "{}: {} used {}.\n",
+                chrono::Local::now().format("%H:%M:%S"),
+                combatant.instance_name,
+                routine.name
+            )
+        );
+
+        let had_targets = !attack_targets(simulation_state, &combatant.instance_name).is_empty();
+        let mut results = Vec::new();
+        let mut total_damage = 0;
+
+        'routine: for step in &routine.steps {
+            let Some(attack) = combatant
+                .monster_template
+                .attacks
+                .iter()
+                .find(|a| a.attack_name == step.attack_name)
+            else {
+                let mut iter = buffer.end_iter();
+                buffer.insert(
+                    &mut iter,
+                    &format!("  {}: skipped, the monster has no attack with that name.\n", step.attack_name)
+                );
+                continue;
+            };
+
+            for i in 0..step.count {
+                let label = if step.count > 1 {
+                    format!("{} {}", attack.attack_name, i + 1)
+                } else {
+                    attack.attack_name.clone()
+                };
+                // Targets are read again for every roll, so ones that went down are skipped
+                let target = next_target(simulation_state, &combatant.instance_name, results.len());
+                if had_targets && target.is_none() {
+                    log_targets_down(&buffer, &label);
+                    break 'routine;
+                }
+                let result = if attack.saving_throw {
+                    let targets = attack_targets(simulation_state, &combatant.instance_name);
+                    roll_save_attack(&buffer, combatant, simulation_state, attack, &label, &targets)
+                } else {
+                    roll_attack(&buffer, combatant, simulation_state, attack, &label, target.as_ref())
+                };
+                if result.landed != Some(false) {
+                    total_damage += result.damage;
+                }
+                results.push(match (result.to_hit, result.landed, &target) {
+                    (Some(_), Some(true), Some(t)) => format!("{} hits {} for {}", label, t.instance_name, result.damage),
+                    (Some(_), Some(false), Some(t)) => format!("{} misses {}", label, t.instance_name),
+                    (Some(to_hit), _, _) => format!("{} hits AC {} for {}", label, to_hit, result.damage),
+                    (None, _, _) if had_targets => format!("{} deals {} in total (save)", label, result.damage),
+                    (None, _, _) => format!("{} deals {} (save)", label, result.damage),
+                });
+            }
+        }
+
+        let mut iter = buffer.end_iter();
+        buffer.insert(
+            &mut iter,
+            &format!(
+                "  Summary: {}\n  Total damage{}: {}\n",
+                if results.is_empty() { "no attacks made".to_string() } else { results.join(", ") },
+                if had_targets { "" } else { " if everything lands" },
+                total_damage
+            )
+        );
+
+        limit_console_buffer(&buffer);
+    }
+
+    scroll_console_to_bottom(&simulation_state.console_text_view);
+}
+
+/// Creates the spell slot trackers and spell list with "Cast" buttons, if the monster casts spells.
+fn create_spellcasting_panel(combatant: &Combatant, simulation_state: &SimulationState) -> Option<Box> {
+    let monster = &combatant.monster_template;
+    let spellcasting = monster.spellcasting.as_ref()?;
+
+    let container = UiFactory::create_box(Orientation::Vertical, 4, (0, 0, 0, 0));
+    let header_label = UiFactory::create_label(
+        &format!(
+            "<b>Spellcasting</b> ({}, DC {}, {:+} to hit)",
+            spellcasting.ability.to_uppercase(),
+            monster.spell_save_dc().unwrap_or(0),
+            monster.spell_attack_bonus().unwrap_or(0)
+        ),
+        Align::Start,
+        true,
+        &[]
+    );
+    header_label.set_margin_top(6);
+    container.append(&header_label);
+
+    // Remaining slots per level; editing them by hand covers rests and other casters' effects
+    let slots_box = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+    let mut slot_spins: Vec<(usize, SpinButton)> = Vec::new();
+    for (level_index, &max_slots) in spellcasting.slots.iter().enumerate() {
+        if max_slots <= 0 {
+            continue;
+        }
+        let remaining = (max_slots - combatant.slots_used[level_index]).max(0);
+        let label = Label::new(Some(&format!("{}:", monster_manager::spell_level_label(level_index as i32 + 1))));
+        let spin = UiFactory::create_spin_button(0.0, max_slots as f64, 1.0, remaining as f64);
+
+        let instance_name = combatant.instance_name.clone();
+        let simulation_state_clone = simulation_state.clone();
+        spin.connect_value_changed(move |spin| {
+            let used = max_slots - spin.value() as i32;
+            let mut changed = false;
+            if let Some(c) = simulation_state_clone.combatants
+                .borrow_mut()
+                .iter_mut()
+                .find(|c| c.instance_name == instance_name)
+                && c.slots_used[level_index] != used
+            {
+                c.slots_used[level_index] = used;
+                changed = true;
+            }
+            if changed {
+                mark_simulation_changed(&simulation_state_clone);
+            }
+        });
+
+        slots_box.append(&label);
+        slots_box.append(&spin);
+        slot_spins.push((level_index, spin));
+    }
+    if !slot_spins.is_empty() {
+        container.append(&slots_box);
+    }
+
+    for (spell_index, spell) in spellcasting.spells.iter().enumerate() {
+        let spell_hbox = UiFactory::create_box(Orientation::Horizontal, 6, (0, 0, 0, 0));
+        let spell_label = UiFactory::create_label(&spell_row_text(combatant, spell), Align::Start, false, &[]);
+        spell_label.set_hexpand(true);
+        let cast_button = UiFactory::create_button("Cast", Align::Center, None);
+
+        let instance_name = combatant.instance_name.clone();
+        let simulation_state_clone = simulation_state.clone();
+        let slot_spins_clone = slot_spins.clone();
+        let spell_label_clone = spell_label.clone();
+        cast_button.connect_clicked(move |_| {
+            cast_spell(&instance_name, &simulation_state_clone, spell_index, &slot_spins_clone);
+
+            let combatants = simulation_state_clone.combatants.borrow();
+            if let Some(combatant) = combatants.iter().find(|c| c.instance_name == instance_name)
+                && let Some(spell) = combatant.monster_template.spellcasting
+                    .as_ref()
+                    .and_then(|s| s.spells.get(spell_index))
+            {
+                spell_label_clone.set_text(&spell_row_text(combatant, spell));
+            }
+        });
+
+        spell_hbox.append(&spell_label);
+        spell_hbox.append(&cast_button);
+        container.append(&spell_hbox);
+    }
+
     Some(container)
 }
 
+/// "• Fireball (3rd, Dex save, 8d6 fire)", with uses left for per-day spells.
+fn spell_row_text(combatant: &Combatant, spell: &monster_manager::Spell) -> String {
+    match spell.usage {
+        monster_manager::SpellUsage::PerDay(uses) => {
+            let used = combatant.spell_uses.get(&spell.name).copied().unwrap_or(0);
+            format!("• {} - {} left", spell.describe(), (uses - used).max(0))
+        }
+        _ => format!("• {}", spell.describe()),
+    }
+}
+
+/// Spends the slot or daily use a spell needs, then rolls its attack or saves against the ticked
+/// targets like a weapon or save attack, taking the damage off their HP.
+/// Slot spinners of the card are updated to the new remaining count.
+fn cast_spell(
+    instance_name: &str,
+    simulation_state: &SimulationState,
+    spell_index: usize,
+    slot_spins: &[(usize, SpinButton)]
+) {
+    let timestamp = chrono::Local::now().format("%H:%M:%S");
+    let (combatant, spell, resource) = {
+        let mut combatants = simulation_state.combatants.borrow_mut();
+        let Some(combatant) = combatants.iter_mut().find(|c| c.instance_name == instance_name) else {
+            return;
+        };
+        let Some(spellcasting) = combatant.monster_template.spellcasting.clone() else {
+            return;
+        };
+        let Some(spell) = spellcasting.spells.get(spell_index).cloned() else {
+            return;
+        };
+
+        // What the cast costs: nothing, a daily use or the lowest free slot of the spell's level or higher
+        let resource = match spell.usage {
+            _ if spell.level == 0 => Ok(None),
+            monster_manager::SpellUsage::AtWill => Ok(None),
+            monster_manager::SpellUsage::PerDay(uses) => {
+                let used = combatant.spell_uses.entry(spell.name.clone()).or_insert(0);
+                if *used < uses {
+                    *used += 1;
+                    Ok(Some(format!("{} of {} daily uses left", uses - *used, uses)))
+                } else {
+                    Err(format!("{} has no uses of {} left today.", combatant.instance_name, spell.name))
+                }
+            }
+            monster_manager::SpellUsage::Slot => {
+                let first = (spell.level - 1).max(0) as usize;
+                match (first..9).find(|&i| spellcasting.slots[i] > combatant.slots_used[i]) {
+                    Some(i) => {
+                        combatant.slots_used[i] += 1;
+                        Ok(Some(format!(
+                            "{} level slot, {} left",
+                            monster_manager::spell_level_label(i as i32 + 1),
+                            spellcasting.slots[i] - combatant.slots_used[i]
+                        )))
+                    }
+                    None => Err(format!(
+                        "{} has no spell slots of {} level or higher left for {}.",
+                        combatant.instance_name,
+                        monster_manager::spell_level_label(spell.level),
+                        spell.name
+                    )),
+                }
+            }
+        };
+        (combatant.clone(), spell, resource)
+    };
+
+    let resource = match resource {
+        Ok(resource) => resource,
+        Err(message) => {
+            if let Ok(buffer) = simulation_state.console_buffer.try_borrow_mut() {
+                let mut iter = buffer.end_iter();
+                buffer.insert(&mut iter, &format!("{}: {}\n", timestamp, message));
+                limit_console_buffer(&buffer);
+            }
+            scroll_console_to_bottom(&simulation_state.console_text_view);
+            return;
+        }
+    };
+
+    // The spinners write back to the same state, which already matches
+    let max_slots = combatant.monster_template.spellcasting.as_ref().map_or([0; 9], |s| s.slots);
+    for (level_index, spin) in slot_spins {
+        spin.set_value((max_slots[*level_index] - combatant.slots_used[*level_index]).max(0) as f64);
+    }
+    mark_simulation_changed(simulation_state);
+
+    let monster = &combatant.monster_template;
+    if let Ok(buffer) = simulation_state.console_buffer.try_borrow_mut() {
+        let mut iter = buffer.end_iter();
+        buffer.insert(
+            &mut iter,
+            &format!(
+                "{}: {} cast {}{}.\n",
+                timestamp,
+                combatant.instance_name,
+                spell.name,
+                resource.map(|r| format!(" ({})", r)).unwrap_or_default()
+            )
+        );
+
+        let dice = monster_manager::parse_dice_expression(&spell.damage);
+        let damage_type = if spell.damage_type.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", spell.damage_type.to_lowercase())
+        };
+        match &spell.roll {
+            monster_manager::SpellRoll::Attack => {
+                // Spell attacks go against the first ticked target like a weapon attack's first roll
+                let target = next_target(simulation_state, instance_name, 0);
+                let mode = attack_roll_mode(&combatant, simulation_state, target.as_ref(), true);
+                let (d20_roll, lost_roll) = perform_d20_roll(&mode);
+                let bonus = monster.spell_attack_bonus().unwrap_or(0);
+                let to_hit = d20_roll + bonus;
+                let crit_message = if d20_roll == 20 { " -> CRITICAL HIT!" } else { "" };
+                let landed = target
+                    .as_ref()
+                    .map(|t| d20_roll == 20 || (d20_roll != 1 && to_hit >= t.monster_template.ac));
+                let target_text = match (&target, landed) {
+                    (Some(t), Some(true)) => format!(" vs {} (AC {}) -> hit", t.instance_name, t.monster_template.ac),
+                    (Some(t), _) => format!(" vs {} (AC {}) -> miss", t.instance_name, t.monster_template.ac),
+                    _ => String::new(),
+                };
+                let rolled = dice.map(|(count, die, extra)| {
+                    calculate_damage(if d20_roll == 20 { count * 2 } else { count }, &die, extra)
+                });
+                let damage_text = rolled
+                    .as_ref()
+                    .map(|(_, text)| format!("; Damage: {}{}", text, damage_type))
+                    .unwrap_or_default();
+
+                let prefix = "  Spell attack: (".to_string();
+                let suffix = format!(") + {} = {}{}{}{}\n", bonus, to_hit, crit_message, target_text, damage_text);
+                append_roll_to_console(&buffer, &prefix, d20_roll, lost_roll, &suffix);
+
+                if landed == Some(true)
+                    && let (Some(t), Some((damage, _))) = (&target, rolled)
+                {
+                    let damage = adjust_damage_for_target(&buffer, t, damage, &spell.damage_type, WeaponMaterial::Magical);
+                    apply_damage(&buffer, simulation_state, &t.instance_name, damage);
+                }
+            }
+            monster_manager::SpellRoll::Save(ability) => {
+                let dc = monster.spell_save_dc().unwrap_or(0);
+                let ability_index = monster_manager::ability_index(ability).unwrap_or(1);
+                let rolled = dice.map(|(count, die, extra)| calculate_damage(count, &die, extra));
+                // Cantrips usually do nothing on a success; leveled spells usually deal half
+                let success_damage = match &rolled {
+                    Some((damage, _)) if spell.level > 0 => damage / 2,
+                    _ => 0,
+                };
+                let damage_text = rolled
+                    .as_ref()
+                    .map(|(_, text)| format!("; Damage: {}{} ({} on a success)", text, damage_type, success_damage))
+                    .unwrap_or_default();
+                buffer.insert(
+                    &mut iter,
+                    &format!("  DC {} {} save{}\n", dc, ability.to_uppercase(), damage_text)
+                );
+
+                for target in attack_targets(simulation_state, instance_name) {
+                    let saved = roll_target_save(&buffer, &target, simulation_state, ability_index, dc);
+                    let Some((damage, _)) = rolled else {
+                        continue;
+                    };
+                    let dealt = if saved { success_damage } else { damage };
+                    let dealt = adjust_damage_for_target(&buffer, &target, dealt, &spell.damage_type, WeaponMaterial::Magical);
+                    let outcome = match (saved, dealt) {
+                        (_, 0) => "no damage".to_string(),
+                        (true, _) => format!("{}{} damage (half)", dealt, damage_type),
+                        (false, _) => format!("{}{} damage", dealt, damage_type),
+                    };
+                    let mut iter = buffer.end_iter();
+                    buffer.insert(&mut iter, &format!("    {} takes {}.\n", target.instance_name, outcome));
+                    apply_damage(&buffer, simulation_state, &target.instance_name, dealt);
+                }
+            }
+            monster_manager::SpellRoll::None => {
+                if let Some((_, text)) = dice.map(|(count, die, extra)| calculate_damage(count, &die, extra)) {
+                    buffer.insert(&mut iter, &format!("  Damage: {}{}\n", text, damage_type));
+                }
+            }
+        }
+        limit_console_buffer(&buffer);
+    }
+    scroll_console_to_bottom(&simulation_state.console_text_view);
+}
+
 // =========================================================================
 // Central Assembler Function
 // =========================================================================
@@ -1017,6 +3383,7 @@ fn create_attacks_list(combatant: &Combatant, simulation_state: &SimulationState
 /// Helper function to create a single monster card for the simulation view.
 fn create_combatant_card(combatant: &Combatant, simulation_state: &SimulationState) -> Frame {
     let card_frame = Frame::builder()
+        .name(combatant.instance_name.as_str())
         .margin_top(6)
         .margin_bottom(6)
         .margin_start(6)
@@ -1030,24 +3397,45 @@ fn create_combatant_card(combatant: &Combatant, simulation_state: &SimulationSta
     vbox.append(&header_box);
 
     // Append 2: Statistics Panel (HP, AC, Speed)
+    let concentration_row = create_concentration_row(combatant, simulation_state);
     let stats_box = create_stats_row(combatant, &card_frame, simulation_state);
     vbox.append(&stats_box);
-
-    // Append 3: Vulnerabilities (Optional)
+    let details_label = create_details_label(combatant);
+    vbox.append(&details_label);
+
+    // Append 3: Roll Mode, Conditions, Concentration & Action Economy
+    let roll_mode_row = create_roll_mode_row(combatant, simulation_state);
+    vbox.append(&roll_mode_row);
+    let conditions_row = create_conditions_row(combatant, simulation_state);
+    vbox.append(&conditions_row);
+    vbox.append(&concentration_row);
+    let (economy_row, bonus_check, reaction_check) = create_action_economy_row(combatant, simulation_state);
+    vbox.append(&economy_row);
+
+    // Append 4: Vulnerabilities (Optional)
     if let Some(vuln_label) = create_vulnerabilities_label(combatant) {
         vbox.append(&vuln_label);
     }
 
-    // Append 4: Abilities Text Block
+    // Append 5: Abilities Text Block
     let abilities_text = create_abilities_label(combatant);
     vbox.append(&abilities_text);
 
-    // Append 5: Saves Controls
+    // Append 6: Saves Controls
     let saves_control_panel = create_saves_grid(combatant, simulation_state);
     vbox.append(&saves_control_panel);
 
-    // Append 6: Attacks Controls (Optional)
-    if let Some(attacks_list) = create_attacks_list(combatant, simulation_state) {
+    // Append 7: Ability & Skill Checks
+    let checks_panel = create_checks_panel(combatant, simulation_state);
+    vbox.append(&checks_panel);
+
+    // Append 8: Spellcasting Controls (Optional)
+    if let Some(spellcasting_panel) = create_spellcasting_panel(combatant, simulation_state) {
+        vbox.append(&spellcasting_panel);
+    }
+
+    // Append 9: Attacks Controls (Optional)
+    if let Some(attacks_list) = create_attacks_list(combatant, simulation_state, &bonus_check, &reaction_check) {
         vbox.append(&attacks_list);
     }
 
@@ -1059,68 +3447,68 @@ fn create_combatant_card(combatant: &Combatant, simulation_state: &SimulationSta
 // Storage / Serialization Systems
 // =========================================================================
 
+/// Saves the simulation to the library. A session that was never saved refuses to
+/// replace another encounter that happens to share its name.
 fn save_simulation(simulation_state: &SimulationState) -> io::Result<()> {
-    let mut path = get_base_path()?;
-    if !path.exists() {
-        fs::create_dir(&path)?;
-    }
-
-    path.push("active_simulation.json");
     let static_sim = StaticSimulationState::make_static(simulation_state);
-    let json_data = serde_json::to_string_pretty(&static_sim)?;
-    let mut file = File::create(&path)?;
-    file.write_all(json_data.as_bytes())?;
+    if simulation_state.in_library.get() {
+        encounter_manager::save_encounter(&static_sim)?;
+    } else {
+        encounter_manager::save_new_encounter(&static_sim)?;
+    }
+    simulation_state.in_library.set(true);
+    simulation_state.unsaved_changes.set(false);
     Ok(())
 }
 
-fn get_simulation() -> Option<StaticSimulationState> {
-    let mut path = get_base_path().ok()?;
-    if !path.exists() {
-        return None;
-    }
-    path.push("active_simulation.json");
-
-    let mut file = File::open(&path).ok()?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).ok()?;
-
-    match serde_json::from_str(&contents) {
-        Ok(simulation) => Some(simulation),
-        Err(e) => {
-            eprintln!("Failed to parse monster JSON for active_simulation.json: {}", e);
-            None
+/// Saves the simulation and then runs `on_saved`. If the name is taken by another
+/// encounter, asks for a new name first.
+fn save_simulation_then<F>(app: &AdwApplication, window: &AdwWindow, simulation_state: &SimulationState, on_saved: F)
+    where F: Fn() + 'static
+{
+    match save_simulation(simulation_state) {
+        Ok(()) => on_saved(),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            let suggested = encounter_manager::unique_encounter_name(&simulation_state.encounter_name.borrow());
+            let simulation_state = simulation_state.clone();
+            let window_clone = window.clone();
+            show_encounter_name_prompt(app, window, "Save Encounter As", &suggested, move |new_name| {
+                let old_name = simulation_state.encounter_name.replace(new_name.to_string());
+                if let Err(e) = save_simulation(&simulation_state) {
+                    *simulation_state.encounter_name.borrow_mut() = old_name;
+                    return Err(e);
+                }
+                window_clone.set_title(Some(&format!("Mass Combat Decider - {}", new_name)));
+                on_saved();
+                Ok(())
+            });
         }
+        Err(e) => eprintln!("Failed to save simulation: {}", e),
     }
 }
 
-pub fn remove_simulation_file() -> io::Result<()> {
-    if check_for_simulation() {
-        let mut path = get_base_path()?;
-        if !path.exists() {
-            fs::create_dir(&path)?;
-        }
-        path.push("active_simulation.json");
-        fs::remove_file(&path)?;
+/// Writes the live simulation to the crash-recovery autosave.
+fn autosave_simulation(simulation_state: &SimulationState) {
+    let static_sim = StaticSimulationState::make_static(simulation_state);
+    if let Err(e) = encounter_manager::write_autosave(&static_sim) {
+        eprintln!("Failed to autosave simulation: {}", e);
     }
-    Ok(())
 }
 
-pub fn check_for_simulation() -> bool {
-    if let Ok(mut path) = get_base_path() {
-        path.push("active_simulation.json");
-        return path.exists();
-    }
-    false
+/// Flags the simulation as changed since the last save and autosaves it right away.
+fn mark_simulation_changed(simulation_state: &SimulationState) {
+    simulation_state.unsaved_changes.set(true);
+    autosave_simulation(simulation_state);
 }
 
 fn get_ability_mod(combatant: &Combatant, attack: &Attack) -> i32 {
     match attack.ability_used.as_str() {
-        "str" => combatant.monster_template.mods[0],
-        "dex" => combatant.monster_template.mods[1],
-        "con" => combatant.monster_template.mods[2],
-        "int" => combatant.monster_template.mods[3],
-        "wis" => combatant.monster_template.mods[4],
-        "cha" => combatant.monster_template.mods[5],
+        "str" => combatant.monster_template.ability_mod(0),
+        "dex" => combatant.monster_template.ability_mod(1),
+        "con" => combatant.monster_template.ability_mod(2),
+        "int" => combatant.monster_template.ability_mod(3),
+        "wis" => combatant.monster_template.ability_mod(4),
+        "cha" => combatant.monster_template.ability_mod(5),
         _ => 0,
     }
 }
@@ -0,0 +1,605 @@
+// statblock_export.rs
+//
+// This file renders monsters as classic stat blocks, either as Markdown (for wikis) or as a
+// standalone HTML page with its own CSS (for handouts). It is used by the monster list and
+// by the `export-statblock` command line mode.
+
+use std::fs;
+use std::io;
+
+use super::monster_manager::{ self, ActionCategory, Attack, AttackRider, DamageModifier, DamageQualifier, Monster, SaveSuccess, Multiattack, SpellUsage };
+
+const ABILITY_NAMES: [&str; 6] = ["STR", "DEX", "CON", "INT", "WIS", "CHA"];
+const FULL_ABILITY_NAMES: [&str; 6] = ["Strength", "Dexterity", "Constitution", "Intelligence", "Wisdom", "Charisma"];
+
+/// (name, kind, description); kind is the italic lead-in such as "Weapon Attack:"
+type ActionEntry = (String, String, String);
+
+/// Everything a stat block shows, worked out once so both formats print the same numbers.
+struct StatBlock {
+    name: String,
+    // "Medium humanoid (goblinoid), neutral evil", empty when not recorded
+    subtitle: String,
+    // (label, value) lines between the name and the ability scores
+    top_lines: Vec<(String, String)>,
+    // "8 (-1)" per ability
+    scores: Vec<String>,
+    // (label, value) lines between the ability scores and the traits
+    detail_lines: Vec<(String, String)>,
+    traits: Vec<String>,
+    // grouped under headings such as "Actions" and "Reactions", in `ActionCategory::ALL` order
+    actions: Vec<(String, Vec<ActionEntry>)>,
+}
+
+fn build_stat_block(monster: &Monster) -> StatBlock {
+    let mut top_lines = vec![
+        ("Armor Class".to_string(), monster.ac.to_string()),
+        ("Hit Points".to_string(), hit_points_text(monster)),
+        ("Speed".to_string(), monster.speeds.describe()),
+    ];
+    top_lines.retain(|(_, value)| !value.is_empty());
+
+    let scores = monster.scores
+        .iter()
+        .map(|&score| format!("{} ({})", score, signed(monster_manager::score_to_mod(score))))
+        .collect();
+
+    let mut detail_lines = Vec::new();
+    let saves: Vec<String> = (0..6)
+        .filter(|&i| monster.saves[i])
+        .map(|i| format!("{} {}", capitalize(ABILITY_NAMES[i]), signed(monster.ability_mod(i) + monster.pb)))
+        .collect();
+    if !saves.is_empty() {
+        detail_lines.push(("Saving Throws".to_string(), saves.join(", ")));
+    }
+    if !monster.skills.is_empty() {
+        let skills: Vec<String> = monster.skills
+            .iter()
+            .map(|s| format!("{} {}", s.skill, signed(monster.skill_bonus(&s.skill))))
+            .collect();
+        detail_lines.push(("Skills".to_string(), skills.join(", ")));
+    }
+    for (label, list) in [
+        ("Damage Vulnerabilities", &monster.vulnerabilities),
+        ("Damage Resistances", &monster.resistances),
+        ("Damage Immunities", &monster.immunities),
+    ] {
+        if !list.is_empty() {
+            detail_lines.push((label.to_string(), damage_list_text(list)));
+        }
+    }
+    if !monster.condition_immunities.is_empty() {
+        detail_lines.push((
+            "Condition Immunities".to_string(),
+            monster.condition_immunities.join(", ").to_lowercase(),
+        ));
+    }
+    detail_lines.push(("Senses".to_string(), monster.describe_senses()));
+    detail_lines.push((
+        "Languages".to_string(),
+        if monster.languages.is_empty() { "—".to_string() } else { monster.languages.join(", ") },
+    ));
+    if monster.cr.is_empty() {
+        detail_lines.push(("Experience".to_string(), format!("{} XP", monster.exp)));
+    } else {
+        detail_lines.push(("Challenge".to_string(), format!("{} ({} XP)", monster.cr, monster.exp)));
+    }
+    detail_lines.push(("Proficiency Bonus".to_string(), signed(monster.pb)));
+
+    let mut traits: Vec<String> = monster.abilities
+        .split("\n\n")
+        .map(|paragraph| paragraph.trim().replace('\n', " "))
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect();
+    if let Some(paragraph) = spellcasting_trait(monster) {
+        traits.push(paragraph);
+    }
+
+    let actions = ActionCategory::ALL
+        .iter()
+        .map(|category| {
+            let mut entries: Vec<ActionEntry> = Vec::new();
+            if *category == ActionCategory::Action {
+                // Routines lead the actions, as in printed stat blocks
+                entries.extend(monster.multiattacks.iter().map(|routine| describe_multiattack(monster, routine)));
+            }
+            entries.extend(monster.attacks
+                .iter()
+                .filter(|attack| attack.category == *category)
+                .map(|attack| describe_attack(monster, attack)));
+            (category.heading().to_string(), entries)
+        })
+        .filter(|(_, entries)| !entries.is_empty())
+        .collect();
+
+    StatBlock {
+        name: monster.name.clone(),
+        subtitle: monster.describe_type(),
+        top_lines,
+        scores,
+        detail_lines,
+        traits,
+        actions,
+    }
+}
+
+/// The "Spellcasting." trait: ability, DC and attack bonus, then spells grouped by level and daily uses.
+fn spellcasting_trait(monster: &Monster) -> Option<String> {
+    let spellcasting = monster.spellcasting.as_ref()?;
+    let ability = monster_manager::ability_index(&spellcasting.ability)
+        .map_or(spellcasting.ability.clone(), |i| FULL_ABILITY_NAMES[i].to_string());
+    let mut text = format!(
+        "Spellcasting. The {}'s spellcasting ability is {} (spell save DC {}, {} to hit with spell attacks).",
+        monster.name.to_lowercase(),
+        ability,
+        monster.spell_save_dc().unwrap_or(0),
+        signed(monster.spell_attack_bonus().unwrap_or(0))
+    );
+
+    let names = |filter: &dyn Fn(&monster_manager::Spell) -> bool| {
+        spellcasting.spells
+            .iter()
+            .filter(|spell| filter(spell))
+            .map(|spell| spell.name.to_lowercase())
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    let mut groups = Vec::new();
+    let at_will = names(&|s| s.usage == SpellUsage::AtWill);
+    if !at_will.is_empty() {
+        groups.push(format!("At will: {}", at_will));
+    }
+    let mut daily: Vec<i32> = spellcasting.spells
+        .iter()
+        .filter_map(|s| match s.usage {
+            SpellUsage::PerDay(uses) => Some(uses),
+            _ => None,
+        })
+        .collect();
+    daily.sort_unstable_by(|a, b| b.cmp(a));
+    daily.dedup();
+    for uses in daily {
+        groups.push(format!("{}/day each: {}", uses, names(&|s| s.usage == SpellUsage::PerDay(uses))));
+    }
+    let cantrips = names(&|s| s.usage == SpellUsage::Slot && s.level == 0);
+    if !cantrips.is_empty() {
+        groups.push(format!("Cantrips (at will): {}", cantrips));
+    }
+    for level in 1..=9 {
+        let spells = names(&|s| s.usage == SpellUsage::Slot && s.level == level);
+        let slots = spellcasting.slots[level as usize - 1];
+        if !spells.is_empty() || slots > 0 {
+            groups.push(format!(
+                "{} level ({} slot{}): {}",
+                monster_manager::spell_level_label(level),
+                slots,
+                if slots == 1 { "" } else { "s" },
+                if spells.is_empty() { "-".to_string() } else { spells }
+            ));
+        }
+    }
+    if !groups.is_empty() {
+        text.push_str(&format!(" {}.", groups.join("; ")));
+    }
+    Some(text)
+}
+
+/// "7 (2d6)" when the hit dice add up to the HP with the Con modifier, otherwise "7 (d6)".
+fn hit_points_text(monster: &Monster) -> String {
+    let sides: i32 = monster.hitdie.trim_start_matches('d').parse().unwrap_or(0);
+    if sides == 0 {
+        return monster.hp.to_string();
+    }
+    let con = monster.ability_mod(2);
+    let average_per_die = (sides as f64 + 1.0) / 2.0 + con as f64;
+    let count = if average_per_die > 0.0 { (monster.hp as f64 / average_per_die).round() as i32 } else { 0 };
+    let expected = (count as f64 * (sides as f64 + 1.0) / 2.0).floor() as i32 + count * con;
+
+    if count > 0 && expected == monster.hp {
+        let bonus = count * con;
+        let bonus_text = match bonus {
+            0 => String::new(),
+            b if b > 0 => format!(" + {}", b),
+            b => format!(" - {}", -b),
+        };
+        format!("{} ({}d{}{})", monster.hp, count, sides, bonus_text)
+    } else {
+        format!("{} ({})", monster.hp, monster.hitdie)
+    }
+}
+
+/// "fire; bludgeoning, piercing, slashing from nonmagical attacks", grouping types that share a condition.
+fn damage_list_text(list: &[DamageModifier]) -> String {
+    DamageQualifier::ALL
+        .iter()
+        .filter_map(|qualifier| {
+            let types: Vec<String> = list
+                .iter()
+                .filter(|m| m.qualifier == *qualifier)
+                .map(|m| m.damage_type.to_lowercase())
+                .collect();
+            if types.is_empty() {
+                None
+            } else if qualifier.phrase().is_empty() {
+                Some(types.join(", "))
+            } else {
+                Some(format!("{} {}", types.join(", "), qualifier.phrase()))
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("; ")
+}
+
+/// Builds the text of one attack with its to-hit (or DC) and average damage.
+fn describe_attack(monster: &Monster, attack: &Attack) -> ActionEntry {
+    let ability_mod = ability_mod(monster, attack);
+    let damage_type = if attack.damage_type.is_empty() {
+        "damage".to_string()
+    } else {
+        format!("{} damage", attack.damage_type.to_lowercase())
+    };
+    let per_turn = if attack.num_attacks > 1 {
+        format!(" Used {} times per turn.", attack.num_attacks)
+    } else {
+        String::new()
+    };
+
+    if attack.saving_throw {
+        let dc = 8 + ability_mod + monster.pb;
+        let damage = damage_text(attack, if attack.add_mod_to_damage { ability_mod } else { 0 });
+        let ability = monster_manager::ability_index(attack.target_save_ability())
+            .map_or(attack.target_save_ability().to_string(), |i| FULL_ABILITY_NAMES[i].to_string());
+        let success = match attack.on_save {
+            SaveSuccess::HalfDamage => ", or half as much on a successful one",
+            SaveSuccess::NoDamage => "",
+        };
+        (
+            attack.attack_name.clone(),
+            String::new(),
+            format!(
+                "{} must make a DC {} {} saving throw, taking {} {} on a failed save{}.{}",
+                match &attack.area {
+                    Some(area) => format!("Each creature in a {}", area.describe()),
+                    None => "Each target".to_string(),
+                },
+                dc,
+                ability,
+                damage,
+                damage_type,
+                success,
+                per_turn
+            ),
+        )
+    } else {
+        let kind = match (attack.reach > 0, attack.range > 0) {
+            (true, false) => "Melee Weapon Attack:",
+            (false, true) => "Ranged Weapon Attack:",
+            (true, true) => "Melee or Ranged Weapon Attack:",
+            (false, false) => "Weapon Attack:",
+        };
+        let range = attack.describe_range().replace(", ", " or ");
+        (
+            attack.attack_name.clone(),
+            kind.to_string(),
+            format!(
+                "{} to hit, {}one target. Hit: {} {}.{}{}",
+                signed(ability_mod + monster.pb),
+                if range.is_empty() { String::new() } else { format!("{}, ", range) },
+                damage_text(attack, ability_mod),
+                damage_type,
+                attack.rider.as_ref().map(rider_text).unwrap_or_default(),
+                per_turn
+            ),
+        )
+    }
+}
+
+/// Builds the "The goblin makes two attacks: one with its bite and one with its claws." entry.
+fn describe_multiattack(monster: &Monster, routine: &Multiattack) -> ActionEntry {
+    let total: i32 = routine.steps.iter().map(|step| step.count).sum();
+    let parts: Vec<String> = routine.steps
+        .iter()
+        .map(|step| format!("{} with its {}", number_word(step.count), step.attack_name.to_lowercase()))
+        .collect();
+    let parts_text = match parts.as_slice() {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    };
+
+    (
+        routine.name.clone(),
+        String::new(),
+        format!(
+            "The {} makes {} attack{}: {}.",
+            monster.name.to_lowercase(),
+            number_word(total),
+            if total == 1 { "" } else { "s" },
+            parts_text
+        ),
+    )
+}
+
+/// Spells out small counts the way stat blocks do ("two attacks").
+fn number_word(count: i32) -> String {
+    const WORDS: [&str; 10] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+    WORDS.get(count as usize).map(|word| word.to_string()).unwrap_or_else(|| count.to_string())
+}
+
+/// " The target must succeed on a DC 13 Constitution saving throw or be poisoned for 1 minute."
+fn rider_text(rider: &AttackRider) -> String {
+    let ability = monster_manager::ability_index(&rider.save_ability)
+        .map_or(rider.save_ability.clone(), |i| FULL_ABILITY_NAMES[i].to_string());
+    let mut effects = Vec::new();
+    if !rider.condition.is_empty() {
+        effects.push(format!("be {}", rider.condition.to_lowercase()));
+    }
+    if let Some((count, die, bonus)) = monster_manager::parse_dice_expression(&rider.extra_damage) {
+        let sides: i32 = die.trim_start_matches('d').parse().unwrap_or(0);
+        let average = ((count * (sides + 1)) / 2 + bonus).max(1);
+        let damage_type = if rider.damage_type.is_empty() {
+            "damage".to_string()
+        } else {
+            format!("{} damage", rider.damage_type.to_lowercase())
+        };
+        effects.push(format!("take {} ({}) {}", average, rider.extra_damage, damage_type));
+    }
+
+    let mut text = format!(" The target must succeed on a DC {} {} saving throw", rider.dc, ability);
+    if !effects.is_empty() {
+        text.push_str(&format!(" or {}", effects.join(" and ")));
+    }
+    if !rider.duration.is_empty() {
+        text.push_str(&format!(" for {}", rider.duration));
+    }
+    text.push('.');
+    if rider.half_on_success && !rider.extra_damage.is_empty() {
+        text.push_str(" On a successful save, it takes half as much damage.");
+    }
+    text
+}
+
+/// "7 (1d8 + 3)" style average damage.
+fn damage_text(attack: &Attack, modifier: i32) -> String {
+    let sides: i32 = attack.dice_used.trim_start_matches('d').parse().unwrap_or(0);
+    let average = (attack.num_dice * (sides + 1)) / 2 + modifier;
+    let modifier_text = match modifier {
+        0 => String::new(),
+        m if m > 0 => format!(" + {}", m),
+        m => format!(" - {}", -m),
+    };
+    format!("{} ({}{}{})", average.max(1), attack.num_dice, attack.dice_used, modifier_text)
+}
+
+fn ability_mod(monster: &Monster, attack: &Attack) -> i32 {
+    ABILITY_NAMES
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(&attack.ability_used))
+        .map(|i| monster.ability_mod(i))
+        .unwrap_or(0)
+}
+
+fn signed(value: i32) -> String {
+    format!("{:+}", value)
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
+
+// =========================================================================
+// Markdown
+// =========================================================================
+
+/// Renders a monster as a Markdown stat block.
+pub fn render_markdown(monster: &Monster) -> String {
+    let block = build_stat_block(monster);
+    let mut out = format!("## {}\n\n", block.name);
+    if !block.subtitle.is_empty() {
+        out.push_str(&format!("*{}*\n\n", block.subtitle));
+    }
+
+    for (label, value) in &block.top_lines {
+        out.push_str(&format!("**{}** {}  \n", label, value));
+    }
+    out.push('\n');
+
+    out.push_str(&format!("| {} |\n", ABILITY_NAMES.join(" | ")));
+    out.push_str(&format!("|{}\n", ":---:|".repeat(6)));
+    out.push_str(&format!("| {} |\n\n", block.scores.join(" | ")));
+
+    for (label, value) in &block.detail_lines {
+        out.push_str(&format!("**{}** {}  \n", label, value));
+    }
+
+    if !block.traits.is_empty() {
+        out.push('\n');
+        for paragraph in &block.traits {
+            out.push_str(&format!("{}\n\n", markdown_trait(paragraph)));
+        }
+    }
+
+    for (heading, entries) in &block.actions {
+        out.push_str(&format!("\n### {}\n\n", heading));
+        for (name, kind, description) in entries {
+            let kind = if kind.is_empty() { String::new() } else { format!("*{}* ", kind) };
+            out.push_str(&format!("***{}.*** {}{}\n\n", name, kind, description));
+        }
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+/// Puts the "Name." lead-in of a trait in bold italics, like printed stat blocks.
+fn markdown_trait(paragraph: &str) -> String {
+    match split_trait_name(paragraph) {
+        Some((name, rest)) => format!("***{}.*** {}", name, rest),
+        None => paragraph.to_string(),
+    }
+}
+
+/// Splits "Pack Tactics. The wolf has..." into its name and description.
+fn split_trait_name(paragraph: &str) -> Option<(&str, &str)> {
+    let (name, rest) = paragraph.split_once(". ")?;
+    (name.split_whitespace().count() <= 6 && !name.contains(':')).then_some((name, rest))
+}
+
+// =========================================================================
+// HTML
+// =========================================================================
+
+const STAT_BLOCK_CSS: &str = "
+body { background: #e0e5c1; font-family: 'Bookman Old Style', Georgia, serif; }
+.stat-block { max-width: 420px; margin: 2em auto; padding: 0.6em 0.9em; background: #fdf1dc;
+  box-shadow: 0 0 1.5em #867453; border-top: 5px solid #e69a28; border-bottom: 5px solid #e69a28; }
+.stat-block h1 { margin: 0; color: #7a200d; font-variant: small-caps; font-size: 1.8em; }
+.stat-block h2 { margin: 0.6em 0 0.2em; color: #7a200d; font-variant: small-caps; font-weight: normal;
+  font-size: 1.3em; border-bottom: 1px solid #7a200d; }
+.stat-block .rule { height: 5px; margin: 0.5em 0; background: linear-gradient(10deg, #922610 50%, transparent 50%) left,
+  linear-gradient(-10deg, #922610 50%, transparent 50%) right; background-size: 51% 100%; background-repeat: no-repeat; }
+.stat-block .subtitle { font-style: italic; }
+.stat-block .line { margin: 0.15em 0; color: #7a200d; }
+.stat-block .line b { color: #7a200d; }
+.stat-block table { width: 100%; color: #7a200d; text-align: center; }
+.stat-block p { margin: 0.4em 0; }
+";
+
+/// Renders a monster as a standalone HTML page.
+pub fn render_html(monster: &Monster) -> String {
+    let block = build_stat_block(monster);
+    let mut body = format!("<h1>{}</h1>\n", escape_html(&block.name));
+    if !block.subtitle.is_empty() {
+        body.push_str(&format!("<div class=\"subtitle\">{}</div>\n", escape_html(&block.subtitle)));
+    }
+    body.push_str("<div class=\"rule\"></div>\n");
+
+    for (label, value) in &block.top_lines {
+        body.push_str(&format!("<div class=\"line\"><b>{}</b> {}</div>\n", escape_html(label), escape_html(value)));
+    }
+    body.push_str("<div class=\"rule\"></div>\n<table>\n<tr>");
+    for name in ABILITY_NAMES {
+        body.push_str(&format!("<th>{}</th>", name));
+    }
+    body.push_str("</tr>\n<tr>");
+    for score in &block.scores {
+        body.push_str(&format!("<td>{}</td>", escape_html(score)));
+    }
+    body.push_str("</tr>\n</table>\n<div class=\"rule\"></div>\n");
+
+    for (label, value) in &block.detail_lines {
+        body.push_str(&format!("<div class=\"line\"><b>{}</b> {}</div>\n", escape_html(label), escape_html(value)));
+    }
+    body.push_str("<div class=\"rule\"></div>\n");
+
+    for paragraph in &block.traits {
+        let text = match split_trait_name(paragraph) {
+            Some((name, rest)) => format!("<b><i>{}.</i></b> {}", escape_html(name), escape_html(rest)),
+            None => escape_html(paragraph),
+        };
+        body.push_str(&format!("<p>{}</p>\n", text));
+    }
+
+    for (heading, entries) in &block.actions {
+        body.push_str(&format!("<h2>{}</h2>\n", heading));
+        for (name, kind, description) in entries {
+            let kind = if kind.is_empty() { String::new() } else { format!("<i>{}</i> ", escape_html(kind)) };
+            body.push_str(&format!("<p><b><i>{}.</i></b> {}{}</p>\n", escape_html(name), kind, escape_html(description)));
+        }
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<div class=\"stat-block\">\n{}</div>\n</body>\n</html>\n",
+        escape_html(&block.name),
+        STAT_BLOCK_CSS,
+        body
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// =========================================================================
+// Command Line
+// =========================================================================
+
+const CLI_USAGE: &str = "Usage: MassCombatDecider export-statblock <monster name or id>... [--html] [--output FILE]
+       MassCombatDecider export-statblock --all [--html] [--output FILE]";
+
+/// Handles `export-statblock`: renders the named monsters (or all of them) to stdout or a file.
+pub fn run_cli(args: &[String]) -> io::Result<()> {
+    let mut html = false;
+    let mut all = false;
+    let mut output: Option<String> = None;
+    let mut names: Vec<&str> = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--html" => html = true,
+            "--markdown" | "--md" => html = false,
+            "--all" => all = true,
+            "--output" | "-o" => output = args.next().cloned(),
+            "--help" | "-h" => {
+                println!("{}", CLI_USAGE);
+                return Ok(());
+            }
+            name => names.push(name),
+        }
+    }
+
+    if names.is_empty() && !all {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, CLI_USAGE));
+    }
+
+    let library = monster_manager::read_all_monsters();
+    let mut monsters: Vec<&Monster> = Vec::new();
+    if all {
+        monsters.extend(library.iter());
+    }
+    for name in names {
+        let found = library
+            .iter()
+            .find(|m| m.id == name)
+            .or_else(|| library.iter().find(|m| m.name.eq_ignore_ascii_case(name)))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("No monster named \"{}\"", name)))?;
+        monsters.push(found);
+    }
+
+    let rendered = if html {
+        // One page per monster doesn't work on stdout, so several monsters share one page
+        let pages: Vec<String> = monsters.iter().map(|m| render_html(m)).collect();
+        merge_html_pages(&pages)
+    } else {
+        monsters.iter().map(|m| render_markdown(m)).collect::<Vec<String>>().join("\n---\n\n")
+    };
+
+    match output {
+        Some(path) => fs::write(&path, rendered),
+        None => {
+            print!("{}", rendered);
+            Ok(())
+        }
+    }
+}
+
+/// Combines several rendered pages into one by keeping the first page's head and every stat block.
+fn merge_html_pages(pages: &[String]) -> String {
+    let Some(first) = pages.first() else {
+        return String::new();
+    };
+    let blocks: String = pages
+        .iter()
+        .filter_map(|page| {
+            let start = page.find("<div class=\"stat-block\">")?;
+            let end = page.rfind("</body>")?;
+            Some(page[start..end].to_string())
+        })
+        .collect();
+    let head_end = first.find("<div class=\"stat-block\">").unwrap_or(first.len());
+    format!("{}{}</body>\n</html>\n", &first[..head_end], blocks)
+}
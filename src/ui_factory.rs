@@ -1,5 +1,8 @@
 use gtk::{ Adjustment, Align, Box, Button, CheckButton, DropDown, Entry, Label, Orientation, ScrolledWindow, SpinButton, StringObject, glib::object::Cast, prelude::{EditableExt, WidgetExt}};
 
+use std::cell::RefCell;
+use std::path::PathBuf;
+
 pub struct UiFactory;
 
 impl UiFactory {
@@ -141,6 +144,50 @@ impl UiFactory {
         (label, dropdown)
     }
 
+    /// Shows a native file chooser and passes the chosen path to `on_chosen`.
+    /// `patterns` (e.g. "*.json") limit the files shown; `suggested_name` pre-fills the name when saving.
+    pub fn choose_file<F: Fn(PathBuf) + 'static>(
+        parent: &gtk::ApplicationWindow,
+        title: &str,
+        action: gtk::FileChooserAction,
+        patterns: &[&str],
+        suggested_name: Option<&str>,
+        on_chosen: F
+    ) {
+        use gtk::prelude::{ FileChooserExt, FileExt, NativeDialogExt };
+
+        let accept_label = if action == gtk::FileChooserAction::Save { "Save" } else { "Open" };
+        let chooser = gtk::FileChooserNative::new(
+            Some(title),
+            Some(parent),
+            action,
+            Some(accept_label),
+            Some("Cancel")
+        );
+        if !patterns.is_empty() {
+            let filter = gtk::FileFilter::new();
+            for pattern in patterns {
+                filter.add_pattern(pattern);
+            }
+            chooser.add_filter(&filter);
+        }
+        if let Some(name) = suggested_name {
+            chooser.set_current_name(name);
+        }
+
+        // Native dialogs aren't kept alive by GTK, so the handler holds on to it until it answers
+        let keep_alive = RefCell::new(Some(chooser.clone()));
+        chooser.connect_response(move |chooser, response| {
+            if response == gtk::ResponseType::Accept
+                && let Some(path) = chooser.file().and_then(|file| file.path())
+            {
+                on_chosen(path);
+            }
+            keep_alive.borrow_mut().take();
+        });
+        chooser.show();
+    }
+
     pub fn get_dropdown_text(dropdown: &DropDown) -> String {
         dropdown
             .selected_item()